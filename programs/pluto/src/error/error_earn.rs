@@ -4,7 +4,7 @@ use anchor_lang::error_code;
 pub enum ErrorEarn {
     #[msg["vault frozen"]]
     VaultFrozen,
-    #[msg("Invalid LTV, must be greater than 0")]
+    #[msg("Invalid LTV, must be greater than 0 and at most 100%")]
     InvalidLTV,
     #[msg("Invalid max deposit limit, must be greater than 0")]
     InvalidMaxDepositLimit,
@@ -18,8 +18,12 @@ pub enum ErrorEarn {
     InvalidMaxBorrowLimit,
     #[msg("Invalid max borrow limit, must be greater than min borrow limit")]
     InvalidMaxBorrowLimitLessThanMinBorrowLimit,
-    #[msg("Invalid floor cap rate, must be greater than 0")]
+    #[msg("Invalid floor cap rate, must be greater than 0 and at most the floor cap ratio")]
     InvalidFloorCapRate,
+    #[msg("Invalid optimal utilization, must be greater than 0 and less than 100%")]
+    InvalidOptimalUtilization,
+    #[msg("Invalid rate slope, slope2 must be greater than or equal to slope1")]
+    InvalidRateSlope,
     #[msg("Invalid fund")]
     InvalidFund,
     #[msg("Invalid owner")]
@@ -53,4 +57,40 @@ pub enum ErrorEarn {
 
     #[msg("index factor is zero, holding until next update")]
     IndexFactorIsZero,
+
+    #[msg("Rate model is not configured")]
+    RateModelNotConfigured,
+
+    #[msg("Share mint already created")]
+    ShareMintAlreadyCreated,
+    #[msg("Insufficient share")]
+    InsufficientShare,
+
+    #[msg("Invalid flash fee")]
+    InvalidFlashFee,
+    #[msg("Flash loan already in progress")]
+    FlashLoanInProgress,
+    #[msg("No flash loan to repay")]
+    NoFlashLoan,
+    #[msg("Flash repay must match the flash borrow")]
+    InvalidFlashRepay,
+    #[msg("Missing flash repay")]
+    MissingFlashRepay,
+
+    #[msg("Invalid max utilization")]
+    InvalidMaxUtilization,
+    #[msg("Supply cap exceeded")]
+    SupplyCapExceeded,
+    #[msg("Borrow cap exceeded")]
+    BorrowCapExceeded,
+    #[msg("Max utilization exceeded")]
+    MaxUtilizationExceeded,
+
+    #[msg("Liquidity is sufficient, withdraw directly")]
+    WithdrawQueueNotNeeded,
+    #[msg("Queued withdrawal is not filled yet")]
+    WithdrawNotFilled,
+
+    #[msg("Invalid fee, must be at most 100%")]
+    InvalidFee,
 }
\ No newline at end of file
@@ -26,9 +26,9 @@ pub enum ErrorLeverage {
     InvalidMaxDeleverageLimit,
     #[msg("invalid min deleverage limit")]
     InvalidMinDeleverageLimit,
-    #[msg("spread rate must be greater than 0")]
+    #[msg("spread rate must be greater than 0 and less than 100%")]
     InvalidSpreadRate,
-    #[msg("saver threshold must be greater than 0")]
+    #[msg("saver threshold must be greater than the liquidation health factor")]
     InvalidSaverThreshold,
     #[msg("saver target must be greater leverage step")]
     InvalidSaverTargetLessThanLeverageStep,
@@ -46,6 +46,10 @@ pub enum ErrorLeverage {
     InsufficientLiquidity,
     #[msg("invalid leverage")]
     InvalidLeverage,
+    #[msg("target leverage must be a multiple of leverage step, not less than min leverage and less than current leverage")]
+    InvalidTargetLeverage,
+    #[msg("deleverage amount out of limits")]
+    InvalidDeleverageAmount,
     #[msg("profit taking rate must be greater than profit target rate")]
     ProfitTakingRateMustBeLessThanProfitTargetRate,
     #[msg("invalid profit taking rate")]
@@ -60,23 +64,37 @@ pub enum ErrorLeverage {
     NoPositionFound,
     #[msg("invalid position number")]
     InvalidPositionNumber,
+    #[msg("invalid obligation size")]
+    InvalidObligationSize,
+    #[msg("max obligation positions reached")]
+    MaxObligationPositionsReached,
     #[msg("emergency eject are disabled")]
     EmergencyEjectDisabled,
+    #[msg("emergency eject period not reached")]
+    EmergencyEjectPeriodNotReached,
     #[msg("safety mode are disabled")]
     SafetyModeDisabled,
     #[msg("profit taker are disabled")]
     ProfitTakerDisabled,
+    #[msg("position nft already minted")]
+    PositionNftAlreadyMinted,
+    #[msg("signer does not hold the position")]
+    InvalidPositionHolder,
 
     #[msg("invalid indexer")]
     InvalidIndexer,
     #[msg("invalid keeper")]
     InvalidKeeper,
+    #[msg("position action cannot be processed by keeper")]
+    InvalidKeeperAction,
 
     #[msg("index factor is zero, holding until next update")]
     IndexFactorIsZero,
 
     #[msg("saver only works for reducing leverage")]
     SaverOnlyWorksForReducingLeverage,
+    #[msg("health factor is above saver threshold")]
+    UnmetSaverThreshold,
 
     #[msg("no pending funded position found")]
     NoPendingFundedPositionFound,
@@ -86,8 +104,24 @@ pub enum ErrorLeverage {
     #[msg("slippage reached")]
     SlippageReached,
 
-    #[msg("Missing Jupiter Swap")]
+    #[msg("Missing swap through an allowed router")]
     MissingJupiterSwap,
+    #[msg("Swap router is not allowed by the vault")]
+    SwapRouterNotAllowed,
+    #[msg("Swap router is already allowed by the vault")]
+    SwapRouterAlreadyAllowed,
+    #[msg("Maximum number of swap routers reached")]
+    MaxSwapRoutersReached,
+    #[msg("Invalid swap routers account")]
+    InvalidSwapRouters,
+    #[msg("Mercury swap must use the pinned pool")]
+    InvalidMercuryPool,
+    #[msg("Invalid route accounts")]
+    InvalidRouteAccounts,
+    #[msg("Swap spent more than the amount to swap")]
+    SwapInputTooHigh,
+    #[msg("insurance ratio must be at most 100%")]
+    InvalidInsuranceRatio,
     #[msg("Missing fund")]
     MissingFund,
     #[msg("Missing borrow")]
@@ -132,6 +166,8 @@ pub enum ErrorLeverage {
     NextInstructionMustBeClosing,
     #[msg("Next instruction must be cleanup")]
     NextInstructionMustBeCleanup,
+    #[msg("Next instruction must be confiscate")]
+    NextInstructionMustBeConfiscate,
 
     #[msg("Missing keeper release")]
     MissingKeeperRelease,
@@ -150,5 +186,18 @@ pub enum ErrorLeverage {
     NextInstructionMustBeKeeperClosing,
 
     #[msg("Liquidation failed due to health factor")]
-    UnmetHealthFactorThreshold
+    UnmetHealthFactorThreshold,
+
+    #[msg("fee must be at most 100%")]
+    InvalidFee,
+    #[msg("liquidation threshold must be greater than 0 and at most 100%")]
+    InvalidLiquidationThreshold,
+    #[msg("liquidation protocol ratio must be at most 100%")]
+    InvalidLiquidationProtocolRatio,
+    #[msg("slippage rate must be at most the spread rate")]
+    InvalidSlippageRate,
+    #[msg("max leverage must open positions above the liquidation health factor")]
+    InvalidMaxLeverageHealthFactor,
+    #[msg("emergency eject period must not be negative")]
+    InvalidEmergencyEjectPeriod,
 }
\ No newline at end of file
@@ -26,6 +26,22 @@ pub enum Errors {
     IncompleteDeleveragingProcess,
     #[msg("price oracle error")]
     PriceOracleError,
+    #[msg("Price oracle does not match the oracle source")]
+    InvalidOracleSource,
+    #[msg("Price oracle is stale")]
+    StalePriceOracle,
+    #[msg("Price confidence interval is too wide")]
+    PriceConfidenceTooWide,
+    #[msg("Price deviates too far from the ema price")]
+    PriceEmaDeviationTooHigh,
+    #[msg("Invalid oracle guard")]
+    InvalidOracleGuard,
+    #[msg("Invalid fallback price oracle")]
+    InvalidFallbackPriceOracle,
+    #[msg("Price oracle is already used by the vault")]
+    DuplicatePriceOracle,
+    #[msg("Missing fallback price oracle account")]
+    MissingPriceOracle,
     #[msg("Failed to serialize account")]
     TryToSerializeAccount,
     #[msg("Simulated for error")]
@@ -42,4 +58,28 @@ pub enum Errors {
     InvalidJupiterProgram,
     #[msg("Insufficient funds")]
     InsufficientFunds,
+    #[msg("Invalid fee distribution")]
+    InvalidFeeDistribution,
+    #[msg("Fee distribution is not set")]
+    FeeDistributionNotSet,
+    #[msg("Only the pending owner can call this function")]
+    NotPendingOwner,
+    #[msg("Invalid timelock delay")]
+    InvalidTimelockDelay,
+    #[msg("Config change raises fees or lowers risk limits, queue it behind the timelock")]
+    ConfigChangeRequiresTimelock,
+    #[msg("Timelock has not elapsed")]
+    TimelockNotElapsed,
+    #[msg("Signer does not hold the role required for this change")]
+    MissingRole,
+    #[msg("Role is already granted")]
+    RoleAlreadyGranted,
+    #[msg("Role is not granted")]
+    RoleNotGranted,
+    #[msg("Max role members reached")]
+    MaxRoleMembersReached,
+    #[msg("Operation is paused")]
+    OperationPaused,
+    #[msg("Invalid pause flags")]
+    InvalidPauseFlags,
 }
\ No newline at end of file
@@ -0,0 +1,7 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventConfigChangeCancelled {
+    pub config: Pubkey,
+    pub pending_config: Pubkey,
+}
\ No newline at end of file
@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventConfigChangeQueued {
+    pub config: Pubkey,
+    pub pending_config: Pubkey,
+    pub proposer: Pubkey,
+    pub execute_after: i64,
+}
\ No newline at end of file
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventEarnBadDebt {
+    pub vault: Pubkey,
+    pub leverage_vault: Pubkey,
+    pub obligation: Pubkey,
+    pub position_id: Pubkey,
+    pub position_number: u8,
+    pub shortfall_amount: u64,
+    pub covered_amount: u64, // covered by the insurance fund
+    pub socialized_amount: u64, // taken from lenders by lowering the index
+    pub insurance_fund_amount: u64,
+    pub old_index: u128,
+    pub index: u128,
+}
\ No newline at end of file
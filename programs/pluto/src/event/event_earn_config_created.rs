@@ -7,7 +7,7 @@ pub struct EventEarnConfigCreated {
     pub creator: Pubkey,
     pub indexer: Pubkey,
     pub fee_vault: Pubkey,
-    pub freeze: bool,
+    pub pause_flags: u16,
     pub protocol_fee: u32,
     pub ltv: u32,
     pub deposit_fee: u32,
@@ -1,19 +1,72 @@
 use anchor_lang::prelude::*;
+use crate::state::{EarnConfig, SetEarnConfigParams};
 
+// Old and new values of the provided fields, both None when a field is left unchanged
 #[event]
-pub struct EventEarnConfigSet{
-    pub fee_vault: Pubkey,
-    pub freeze: bool,
-    pub protocol_fee: u32,
-    pub ltv: u32,
-    pub deposit_fee: u32,
-    pub min_deposit_limit: u64,
-    pub max_deposit_limit: u64,
-    pub withdraw_fee: u32,
-    pub min_withdraw_limit: u64,
-    pub max_withdraw_limit: u64,
-    pub borrow_fee: u32,
-    pub min_borrow_limit: u64,
-    pub max_borrow_limit: u64,
-    pub floor_cap_rate: u32,
+pub struct EventEarnConfigSet {
+    pub config: Pubkey,
+    pub old_earn_fee_vault: Option<Pubkey>,
+    pub earn_fee_vault: Option<Pubkey>,
+    pub old_pause_flags: Option<u16>,
+    pub pause_flags: Option<u16>,
+    pub old_protocol_fee: Option<u32>,
+    pub protocol_fee: Option<u32>,
+    pub old_ltv: Option<u32>,
+    pub ltv: Option<u32>,
+    pub old_deposit_fee: Option<u32>,
+    pub deposit_fee: Option<u32>,
+    pub old_min_deposit_limit: Option<u64>,
+    pub min_deposit_limit: Option<u64>,
+    pub old_max_deposit_limit: Option<u64>,
+    pub max_deposit_limit: Option<u64>,
+    pub old_withdraw_fee: Option<u32>,
+    pub withdraw_fee: Option<u32>,
+    pub old_min_withdraw_limit: Option<u64>,
+    pub min_withdraw_limit: Option<u64>,
+    pub old_max_withdraw_limit: Option<u64>,
+    pub max_withdraw_limit: Option<u64>,
+    pub old_borrow_fee: Option<u32>,
+    pub borrow_fee: Option<u32>,
+    pub old_min_borrow_limit: Option<u64>,
+    pub min_borrow_limit: Option<u64>,
+    pub old_max_borrow_limit: Option<u64>,
+    pub max_borrow_limit: Option<u64>,
+    pub old_floor_cap_rate: Option<u32>,
+    pub floor_cap_rate: Option<u32>,
+}
+
+impl EventEarnConfigSet {
+    pub fn new(config: Pubkey, current: &EarnConfig, params: &SetEarnConfigParams) -> Self {
+        Self {
+            config,
+            old_earn_fee_vault: params.earn_fee_vault.map(|_| current.earn_fee_vault),
+            earn_fee_vault: params.earn_fee_vault,
+            old_pause_flags: params.pause_flags.map(|_| current.pause_flags),
+            pause_flags: params.pause_flags,
+            old_protocol_fee: params.protocol_fee.map(|_| current.protocol_fee),
+            protocol_fee: params.protocol_fee,
+            old_ltv: params.ltv.map(|_| current.ltv),
+            ltv: params.ltv,
+            old_deposit_fee: params.deposit_fee.map(|_| current.deposit_fee),
+            deposit_fee: params.deposit_fee,
+            old_min_deposit_limit: params.min_deposit_limit.map(|_| current.min_deposit_limit),
+            min_deposit_limit: params.min_deposit_limit,
+            old_max_deposit_limit: params.max_deposit_limit.map(|_| current.max_deposit_limit),
+            max_deposit_limit: params.max_deposit_limit,
+            old_withdraw_fee: params.withdraw_fee.map(|_| current.withdraw_fee),
+            withdraw_fee: params.withdraw_fee,
+            old_min_withdraw_limit: params.min_withdraw_limit.map(|_| current.min_withdraw_limit),
+            min_withdraw_limit: params.min_withdraw_limit,
+            old_max_withdraw_limit: params.max_withdraw_limit.map(|_| current.max_withdraw_limit),
+            max_withdraw_limit: params.max_withdraw_limit,
+            old_borrow_fee: params.borrow_fee.map(|_| current.borrow_fee),
+            borrow_fee: params.borrow_fee,
+            old_min_borrow_limit: params.min_borrow_limit.map(|_| current.min_borrow_limit),
+            min_borrow_limit: params.min_borrow_limit,
+            old_max_borrow_limit: params.max_borrow_limit.map(|_| current.max_borrow_limit),
+            max_borrow_limit: params.max_borrow_limit,
+            old_floor_cap_rate: params.floor_cap_rate.map(|_| current.floor_cap_rate),
+            floor_cap_rate: params.floor_cap_rate,
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventEarnConfigSetCaps {
+    pub config: Pubkey,
+    pub old_supply_cap: u64,
+    pub supply_cap: u64,
+    pub old_borrow_cap: u64,
+    pub borrow_cap: u64,
+    pub old_max_utilization: u32,
+    pub max_utilization: u32,
+}
\ No newline at end of file
@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventEarnConfigSetFlashFee {
+    pub config: Pubkey,
+    pub old_flash_fee: u32,
+    pub flash_fee: u32,
+}
\ No newline at end of file
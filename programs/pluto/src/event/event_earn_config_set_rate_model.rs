@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventEarnConfigSetRateModel {
+    pub config: Pubkey,
+    pub old_base_rate: u32,
+    pub base_rate: u32,
+    pub old_slope1: u32,
+    pub slope1: u32,
+    pub old_optimal_utilization: u32,
+    pub optimal_utilization: u32,
+    pub old_slope2: u32,
+    pub slope2: u32,
+}
\ No newline at end of file
@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventEarnFlashBorrow {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub repay_instruction_index: u16,
+}
\ No newline at end of file
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventEarnFlashRepay {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub flash_fee: u32,
+    pub fee_amount: u64,
+    pub index: u128,
+}
\ No newline at end of file
@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventEarnInsuranceFunded {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub insurance_fund_amount: u64,
+}
\ No newline at end of file
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventEarnLenderTokenized {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub lender: Pubkey,
+    pub share_mint: Pubkey,
+    pub unit: u64,
+    pub share_unit: u64,
+    pub minted_unit: u64,
+}
\ No newline at end of file
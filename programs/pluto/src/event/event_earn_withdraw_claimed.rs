@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventEarnWithdrawClaimed {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub withdraw_request: Pubkey,
+    pub ticket: u64,
+    pub amount: u64,
+    pub fee_amount: u64,
+    pub protocol_fee_amount: u64,
+    pub withdraw_filled_amount: u64,
+    pub withdraw_claimed_amount: u64,
+}
\ No newline at end of file
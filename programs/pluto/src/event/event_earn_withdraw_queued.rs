@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventEarnWithdrawQueued {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub lender: Pubkey,
+    pub withdraw_request: Pubkey,
+    pub ticket: u64,
+    pub unit: u64,
+    pub index: u128,
+    pub amount: u64,
+    pub fee_amount: u64,
+    pub protocol_fee_amount: u64,
+    pub queued_ahead_amount: u64, // queued amount still to be filled before this request
+    pub unit_supply: u128,
+}
\ No newline at end of file
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventLeverageAddCollateral {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub obligation: Pubkey,
+    pub position_id: Pubkey,
+    pub position_number: u8,
+    pub token_collateral_price: u64,
+    pub token_collateral_price_exponent: u32,
+    pub native_collateral_price: u64,
+    pub native_collateral_price_exponent: u32,
+    pub token_collateral_price_sources: u8, // bit 0 = primary oracle, bit 1 + i = fallback oracle i
+    pub native_collateral_price_sources: u8, // bit 0 = primary oracle, bit 1 + i = fallback oracle i
+    pub fund_amount: u64,
+    pub min_native_collateral_output: u64,
+}
\ No newline at end of file
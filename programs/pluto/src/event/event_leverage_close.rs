@@ -29,4 +29,5 @@ pub struct EventLeverageClose {
     pub borrowing_usd: u128,
     pub fee_amount: u64,
     pub fee_usd: u64,
+    pub swap_router: Pubkey, // router of the release swap, from the position state
 }
\ No newline at end of file
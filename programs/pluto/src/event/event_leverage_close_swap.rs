@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventLeverageCloseSwap {
+    pub borrow_vault: Pubkey,
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub obligation: Pubkey,
+    pub position_id: Pubkey,
+    pub position_number: u8,
+    pub token_collateral_price: u64,
+    pub token_collateral_price_exponent: u32,
+    pub native_collateral_price: u64,
+    pub native_collateral_price_exponent: u32,
+    pub token_collateral_price_sources: u8,
+    pub native_collateral_price_sources: u8,
+    pub release_amount: u64,
+    pub release_unit: u64,
+    pub release_index: u128,
+    pub repay_amount: u64,
+    pub repay_unit: u64,
+    pub repay_index: u128,
+    pub release_min_output: u64,
+    pub release_output: u64,
+    pub protocol_fee_amount: u64,
+    pub remaining_amount: u64,
+    pub swap_router: Pubkey,
+}
\ No newline at end of file
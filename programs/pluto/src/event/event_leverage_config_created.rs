@@ -8,7 +8,7 @@ pub struct EventLeverageConfigCreated {
     pub indexer: Pubkey,
     pub keeper: Pubkey,
     pub fee_vault: Pubkey,
-    pub freeze: bool,
+    pub pause_flags: u16,
     pub protocol_fee: u32,
     pub min_leverage: u32,
     pub max_leverage: u32,
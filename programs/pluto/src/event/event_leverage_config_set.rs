@@ -1,26 +1,100 @@
 use anchor_lang::prelude::*;
+use crate::state::{LeverageConfig, SetLeverageConfigParams};
 
+// Old and new values of the provided fields, both None when a field is left unchanged
 #[event]
 pub struct EventLeverageConfigSet {
-    pub fee_vault: Pubkey,
-    pub freeze: bool,
-    pub protocol_fee: u32,
-    pub min_leverage: u32,
-    pub max_leverage: u32,
-    pub leverage_step: u32,
-    pub leverage_fee: u32,
-    pub min_leverage_limit: u64,
-    pub max_leverage_limit: u64,
-    pub deleverage_fee: u32,
-    pub min_deleverage_limit: u64,
-    pub max_deleverage_limit: u64,
-    pub closing_fee: u32,
-    pub spread_rate: u32,
-    pub liquidation_fee: u32,
-    pub liquidation_threshold: u32,
-    pub liquidation_protocol_ratio: u32,
-    pub slippage_rate: u32,
-    pub emergency_eject_period: i64,
-    pub saver_threshold: u32,
-    pub saver_target_reduction: u32,
+    pub config: Pubkey,
+    pub old_leverage_fee_vault: Option<Pubkey>,
+    pub leverage_fee_vault: Option<Pubkey>,
+    pub old_pause_flags: Option<u16>,
+    pub pause_flags: Option<u16>,
+    pub old_protocol_fee: Option<u32>,
+    pub protocol_fee: Option<u32>,
+    pub old_min_leverage: Option<u32>,
+    pub min_leverage: Option<u32>,
+    pub old_max_leverage: Option<u32>,
+    pub max_leverage: Option<u32>,
+    pub old_leverage_step: Option<u32>,
+    pub leverage_step: Option<u32>,
+    pub old_leverage_fee: Option<u32>,
+    pub leverage_fee: Option<u32>,
+    pub old_min_leverage_limit: Option<u64>,
+    pub min_leverage_limit: Option<u64>,
+    pub old_max_leverage_limit: Option<u64>,
+    pub max_leverage_limit: Option<u64>,
+    pub old_deleverage_fee: Option<u32>,
+    pub deleverage_fee: Option<u32>,
+    pub old_min_deleverage_limit: Option<u64>,
+    pub min_deleverage_limit: Option<u64>,
+    pub old_max_deleverage_limit: Option<u64>,
+    pub max_deleverage_limit: Option<u64>,
+    pub old_closing_fee: Option<u32>,
+    pub closing_fee: Option<u32>,
+    pub old_spread_rate: Option<u32>,
+    pub spread_rate: Option<u32>,
+    pub old_liquidation_fee: Option<u32>,
+    pub liquidation_fee: Option<u32>,
+    pub old_liquidation_threshold: Option<u32>,
+    pub liquidation_threshold: Option<u32>,
+    pub old_liquidation_protocol_ratio: Option<u32>,
+    pub liquidation_protocol_ratio: Option<u32>,
+    pub old_slippage_rate: Option<u32>,
+    pub slippage_rate: Option<u32>,
+    pub old_emergency_eject_period: Option<i64>,
+    pub emergency_eject_period: Option<i64>,
+    pub old_saver_threshold: Option<u32>,
+    pub saver_threshold: Option<u32>,
+    pub old_saver_target_reduction: Option<u32>,
+    pub saver_target_reduction: Option<u32>,
+}
+
+impl EventLeverageConfigSet {
+    pub fn new(config: Pubkey, current: &LeverageConfig, params: &SetLeverageConfigParams) -> Self {
+        Self {
+            config,
+            old_leverage_fee_vault: params.leverage_fee_vault.map(|_| current.leverage_fee_vault),
+            leverage_fee_vault: params.leverage_fee_vault,
+            old_pause_flags: params.pause_flags.map(|_| current.pause_flags),
+            pause_flags: params.pause_flags,
+            old_protocol_fee: params.protocol_fee.map(|_| current.protocol_fee),
+            protocol_fee: params.protocol_fee,
+            old_min_leverage: params.min_leverage.map(|_| current.min_leverage),
+            min_leverage: params.min_leverage,
+            old_max_leverage: params.max_leverage.map(|_| current.max_leverage),
+            max_leverage: params.max_leverage,
+            old_leverage_step: params.leverage_step.map(|_| current.leverage_step),
+            leverage_step: params.leverage_step,
+            old_leverage_fee: params.leverage_fee.map(|_| current.leverage_fee),
+            leverage_fee: params.leverage_fee,
+            old_min_leverage_limit: params.min_leverage_limit.map(|_| current.min_leverage_limit),
+            min_leverage_limit: params.min_leverage_limit,
+            old_max_leverage_limit: params.max_leverage_limit.map(|_| current.max_leverage_limit),
+            max_leverage_limit: params.max_leverage_limit,
+            old_deleverage_fee: params.deleverage_fee.map(|_| current.deleverage_fee),
+            deleverage_fee: params.deleverage_fee,
+            old_min_deleverage_limit: params.min_deleverage_limit.map(|_| current.min_deleverage_limit),
+            min_deleverage_limit: params.min_deleverage_limit,
+            old_max_deleverage_limit: params.max_deleverage_limit.map(|_| current.max_deleverage_limit),
+            max_deleverage_limit: params.max_deleverage_limit,
+            old_closing_fee: params.closing_fee.map(|_| current.closing_fee),
+            closing_fee: params.closing_fee,
+            old_spread_rate: params.spread_rate.map(|_| current.spread_rate),
+            spread_rate: params.spread_rate,
+            old_liquidation_fee: params.liquidation_fee.map(|_| current.liquidation_fee),
+            liquidation_fee: params.liquidation_fee,
+            old_liquidation_threshold: params.liquidation_threshold.map(|_| current.liquidation_threshold),
+            liquidation_threshold: params.liquidation_threshold,
+            old_liquidation_protocol_ratio: params.liquidation_protocol_ratio.map(|_| current.liquidation_protocol_ratio),
+            liquidation_protocol_ratio: params.liquidation_protocol_ratio,
+            old_slippage_rate: params.slippage_rate.map(|_| current.slippage_rate),
+            slippage_rate: params.slippage_rate,
+            old_emergency_eject_period: params.emergency_eject_period.map(|_| current.emergency_eject_period),
+            emergency_eject_period: params.emergency_eject_period,
+            old_saver_threshold: params.saver_threshold.map(|_| current.saver_threshold),
+            saver_threshold: params.saver_threshold,
+            old_saver_target_reduction: params.saver_target_reduction.map(|_| current.saver_target_reduction),
+            saver_target_reduction: params.saver_target_reduction,
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventLeverageConfigSetInsuranceRatio {
+    pub config: Pubkey,
+    pub old_insurance_ratio: u32,
+    pub insurance_ratio: u32,
+}
\ No newline at end of file
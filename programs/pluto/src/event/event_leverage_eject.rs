@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventLeverageEject {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub keeper: Pubkey,
+    pub obligation: Pubkey,
+    pub position_id: Pubkey,
+    pub position_number: u8,
+    pub frozen: bool,
+    pub open_at: i64,
+    pub emergency_eject_period: i64,
+    pub token_collateral_price: u64,
+    pub token_collateral_price_exponent: u32,
+    pub native_collateral_price: u64,
+    pub native_collateral_price_exponent: u32,
+    pub token_collateral_price_sources: u8, // bit 0 = primary oracle, bit 1 + i = fallback oracle i
+    pub native_collateral_price_sources: u8, // bit 0 = primary oracle, bit 1 + i = fallback oracle i
+    pub release_amount: u64,
+    pub release_unit: u64,
+    pub release_index: u128,
+    pub repay_amount: u64,
+    pub repay_unit: u64,
+    pub repay_index: u128,
+    pub release_min_output: u64,
+}
\ No newline at end of file
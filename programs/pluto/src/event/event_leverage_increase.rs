@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventLeverageIncrease {
+    pub borrow_vault: Pubkey,
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub obligation: Pubkey,
+    pub position_id: Pubkey,
+    pub position_number: u8,
+    pub token_collateral_price: u64,
+    pub token_collateral_price_exponent: u32,
+    pub native_collateral_price: u64,
+    pub native_collateral_price_exponent: u32,
+    pub token_collateral_price_sources: u8, // bit 0 = primary oracle, bit 1 + i = fallback oracle i
+    pub native_collateral_price_sources: u8, // bit 0 = primary oracle, bit 1 + i = fallback oracle i
+    pub leverage: u32,
+    pub fund_amount: u64,
+    pub leverage_fee_amount: u64,
+    pub borrowing_amount: u64,
+    pub borrowing_unit: u64,
+    pub borrowing_index: u128,
+    pub borrow_fee_amount: u64,
+    pub leveraged_amount: u64,
+    pub min_native_collateral_output: u64,
+    pub position_leverage: u32,
+}
\ No newline at end of file
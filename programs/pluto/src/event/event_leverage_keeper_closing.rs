@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+use crate::util::action::LeverageAction;
+
+#[event]
+pub struct EventLeverageKeeperClosing {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub keeper: Pubkey,
+    pub obligation: Pubkey,
+    pub position_id: Pubkey,
+    pub position_number: u8,
+    pub action: LeverageAction,
+    pub release_unit: u64,
+    pub repay_unit: u64,
+    pub release_output: u64,
+    pub repay_borrow_amount: u64,
+    pub protocol_fee_amount: u64,
+    pub liquidation_fee_amount: u64,
+    pub deleverage_fee_amount: u64,
+    pub remaining_amount: u64,
+    pub closed: bool,
+    pub swap_router: Pubkey,
+}
\ No newline at end of file
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventLeverageLiquidationFee {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub liquidator: Pubkey,
+    pub obligation: Pubkey,
+    pub position_id: Pubkey,
+    pub position_number: u8,
+    pub release_output: u64,
+    pub liquidation_fee: u32,
+    pub liquidation_protocol_ratio: u32,
+    pub liquidation_fee_amount: u64,
+    pub protocol_fee_amount: u64,
+    pub liquidator_fee_amount: u64,
+}
\ No newline at end of file
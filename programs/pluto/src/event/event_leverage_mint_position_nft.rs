@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventLeverageMintPositionNft {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub obligation: Pubkey,
+    pub position_id: Pubkey,
+    pub position_number: u8,
+    pub nft_mint: Pubkey,
+    pub metadata: Pubkey,
+    pub entry_leverage: u32,
+    pub token_collateral_price: u64,
+    pub token_collateral_price_exponent: u32,
+    pub native_collateral_price: u64,
+    pub native_collateral_price_exponent: u32,
+}
\ No newline at end of file
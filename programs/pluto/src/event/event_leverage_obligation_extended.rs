@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventLeverageObligationExtended {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub obligation: Pubkey,
+    pub old_capacity: u8,
+    pub new_capacity: u8,
+}
\ No newline at end of file
@@ -20,4 +20,5 @@ pub struct EventLeverageOpen {
     pub real_native_collateral_output: u64,
     pub unit: u64,
     pub index: u128,
+    pub swap_router: Pubkey,
 }
\ No newline at end of file
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventLeverageProfitTaken {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub keeper: Pubkey,
+    pub obligation: Pubkey,
+    pub position_id: Pubkey,
+    pub position_number: u8,
+    pub release_output: u64,
+    pub repay_borrow_amount: u64,
+    pub protocol_fee_amount: u64,
+    pub realized_profit: u64,
+}
\ No newline at end of file
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventLeverageSave {
+    pub vault: Pubkey,
+    pub user: Pubkey,
+    pub keeper: Pubkey,
+    pub obligation: Pubkey,
+    pub position_id: Pubkey,
+    pub position_number: u8,
+    pub safety_level: u8,
+    pub token_collateral_price: u64,
+    pub token_collateral_price_exponent: u32,
+    pub native_collateral_price: u64,
+    pub native_collateral_price_exponent: u32,
+    pub token_collateral_price_sources: u8, // bit 0 = primary oracle, bit 1 + i = fallback oracle i
+    pub native_collateral_price_sources: u8, // bit 0 = primary oracle, bit 1 + i = fallback oracle i
+    pub health_factor: u32,
+    pub saver_threshold: u32,
+    pub current_leverage: u32,
+    pub target_leverage: u32,
+    pub release_amount: u64,
+    pub release_unit: u64,
+    pub release_rate: u32,
+    pub repay_amount: u64,
+    pub repay_unit: u64,
+    pub release_min_output: u64,
+}
\ No newline at end of file
@@ -4,8 +4,5 @@ use anchor_lang::prelude::*;
 pub struct EventProtocolCreated {
     pub creator: Pubkey,
     pub owner: Pubkey,
-    pub freeze: bool,
-    pub freeze_earn: bool,
-    pub freeze_lend: bool,
-    pub freeze_leverage: bool,
+    pub pause_flags: u16,
 }
\ No newline at end of file
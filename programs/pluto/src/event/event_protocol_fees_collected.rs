@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventProtocolFeesCollected {
+    pub protocol: Pubkey,
+    pub config: Pubkey,
+    pub fee_vault: Pubkey,
+    pub token_mint: Pubkey,
+    pub amount: u64,
+    pub treasury: Pubkey,
+    pub treasury_amount: u64,
+    pub insurance_fund: Pubkey,
+    pub insurance_amount: u64,
+    pub buyback: Pubkey,
+    pub buyback_amount: u64,
+}
\ No newline at end of file
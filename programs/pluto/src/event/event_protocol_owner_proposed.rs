@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventProtocolOwnerProposed {
+    pub protocol: Pubkey,
+    pub owner: Pubkey,
+    pub pending_owner: Pubkey,
+}
\ No newline at end of file
@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+use crate::state::Role;
+
+#[event]
+pub struct EventRoleGranted {
+    pub protocol: Pubkey,
+    pub member: Pubkey,
+    pub role: Role,
+}
\ No newline at end of file
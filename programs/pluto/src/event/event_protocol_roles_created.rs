@@ -0,0 +1,7 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventRolesCreated {
+    pub protocol: Pubkey,
+    pub roles: Pubkey,
+}
\ No newline at end of file
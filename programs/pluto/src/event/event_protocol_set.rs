@@ -2,8 +2,5 @@ use anchor_lang::prelude::*;
 
 #[event]
 pub struct EventProtocolSet{
-    pub freeze: bool,
-    pub freeze_earn: bool,
-    pub freeze_lend: bool,
-    pub freeze_leverage: bool,
+    pub pause_flags: u16,
 }
\ No newline at end of file
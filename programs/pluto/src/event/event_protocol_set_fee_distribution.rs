@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventProtocolSetFeeDistribution {
+    pub protocol: Pubkey,
+    pub old_treasury: Pubkey,
+    pub treasury: Pubkey,
+    pub old_insurance_fund: Pubkey,
+    pub insurance_fund: Pubkey,
+    pub old_buyback: Pubkey,
+    pub buyback: Pubkey,
+    pub old_treasury_ratio: u32,
+    pub treasury_ratio: u32,
+    pub old_insurance_ratio: u32,
+    pub insurance_ratio: u32,
+    pub old_buyback_ratio: u32,
+    pub buyback_ratio: u32,
+}
\ No newline at end of file
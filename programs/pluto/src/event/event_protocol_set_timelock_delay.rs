@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventProtocolSetTimelockDelay {
+    pub protocol: Pubkey,
+    pub old_timelock_delay: i64,
+    pub timelock_delay: i64,
+    pub pending_timelock_delay: i64,
+    pub pending_timelock_delay_after: i64,
+}
\ No newline at end of file
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventVaultEarnAccruedInterest {
+    pub vault: Pubkey,
+    pub utilization_rate: u32,
+    pub borrow_rate: u32,
+    pub supply_rate: u32,
+    pub old_index: u128,
+    pub index: u128,
+    pub last_index_updated: i64,
+}
\ No newline at end of file
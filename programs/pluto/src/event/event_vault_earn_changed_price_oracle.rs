@@ -1,10 +1,15 @@
 use anchor_lang::prelude::*;
+use crate::util::oracle_source::OracleSource;
 
 #[event]
 pub struct EventVaultEarnChangedPriceOracle {
     pub vault: Pubkey,
+    pub old_price_source: OracleSource,
+    pub new_price_source: OracleSource,
     pub old_price_oracle: Pubkey,
     pub new_price_oracle: Pubkey,
     pub old_price_feed: [u8; 64],
     pub new_price_feed: [u8; 64],
+    pub old_fixed_price: u64,
+    pub new_fixed_price: u64,
 }
\ No newline at end of file
@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventVaultEarnCreatedShareMint {
+    pub vault: Pubkey,
+    pub share_mint: Pubkey,
+    pub decimals: u8,
+    pub unit_supply: u128,
+}
\ No newline at end of file
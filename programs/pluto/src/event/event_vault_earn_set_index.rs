@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventVaultEarnSetIndex {
+    pub vault: Pubkey,
+    pub indexer: Pubkey,
+    pub old_index: u128,
+    pub index: u128,
+    pub apy: u32,
+}
\ No newline at end of file
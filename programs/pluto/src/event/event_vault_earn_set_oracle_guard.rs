@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventVaultEarnSetOracleGuard {
+    pub vault: Pubkey,
+    pub old_max_age: u64,
+    pub new_max_age: u64,
+    pub old_max_confidence_rate: u32,
+    pub new_max_confidence_rate: u32,
+    pub old_max_ema_deviation_rate: u32,
+    pub new_max_ema_deviation_rate: u32,
+}
\ No newline at end of file
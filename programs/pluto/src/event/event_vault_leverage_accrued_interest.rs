@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventVaultLeverageAccruedInterest {
+    pub vault: Pubkey,
+    pub borrow_vault: Pubkey,
+    pub utilization_rate: u32,
+    pub borrow_rate: u32,
+    pub old_borrowing_index: u128,
+    pub borrowing_index: u128,
+    pub borrow_vault_index: u128,
+    pub last_index_updated: i64,
+}
\ No newline at end of file
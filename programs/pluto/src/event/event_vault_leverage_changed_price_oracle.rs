@@ -1,14 +1,23 @@
 use anchor_lang::prelude::*;
+use crate::util::oracle_source::OracleSource;
 
 #[event]
 pub struct EventVaultLeverageChangedPriceOracle {
     pub vault: Pubkey,
+    pub old_token_collateral_price_source: OracleSource,
+    pub new_token_collateral_price_source: OracleSource,
     pub old_token_collateral_price_oracle: Pubkey,
     pub new_token_collateral_price_oracle: Pubkey,
     pub old_token_collateral_price_feed: [u8; 64],
     pub new_token_collateral_price_feed: [u8; 64],
+    pub old_token_collateral_fixed_price: u64,
+    pub new_token_collateral_fixed_price: u64,
+    pub old_native_collateral_price_source: OracleSource,
+    pub new_native_collateral_price_source: OracleSource,
     pub old_native_collateral_price_oracle: Pubkey,
     pub new_native_collateral_price_oracle: Pubkey,
     pub old_native_collateral_price_feed: [u8; 64],
     pub new_native_collateral_price_feed: [u8; 64],
+    pub old_native_collateral_fixed_price: u64,
+    pub new_native_collateral_fixed_price: u64,
 }
\ No newline at end of file
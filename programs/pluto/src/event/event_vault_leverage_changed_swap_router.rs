@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventVaultLeverageChangedSwapRouter {
+    pub vault: Pubkey,
+    pub swap_routers: Pubkey,
+    pub router: Pubkey,
+    pub allowed: bool,
+    pub routers: [Pubkey; 8],
+}
\ No newline at end of file
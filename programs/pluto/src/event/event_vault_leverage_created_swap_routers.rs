@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventVaultLeverageCreatedSwapRouters {
+    pub vault: Pubkey,
+    pub swap_routers: Pubkey,
+    pub routers: [Pubkey; 8],
+}
\ No newline at end of file
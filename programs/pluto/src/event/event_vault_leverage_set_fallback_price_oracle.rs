@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use crate::util::oracle_source::OracleSource;
+
+#[event]
+pub struct EventVaultLeverageSetFallbackPriceOracle {
+    pub vault: Pubkey,
+    pub index: u8,
+    pub old_token_collateral_price_source: OracleSource,
+    pub new_token_collateral_price_source: OracleSource,
+    pub old_token_collateral_price_oracle: Pubkey,
+    pub new_token_collateral_price_oracle: Pubkey,
+    pub old_token_collateral_price_feed: [u8; 64],
+    pub new_token_collateral_price_feed: [u8; 64],
+    pub old_native_collateral_price_source: OracleSource,
+    pub new_native_collateral_price_source: OracleSource,
+    pub old_native_collateral_price_oracle: Pubkey,
+    pub new_native_collateral_price_oracle: Pubkey,
+    pub old_native_collateral_price_feed: [u8; 64],
+    pub new_native_collateral_price_feed: [u8; 64],
+}
\ No newline at end of file
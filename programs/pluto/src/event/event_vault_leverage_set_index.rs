@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct EventVaultLeverageSetIndex {
+    pub vault: Pubkey,
+    pub indexer: Pubkey,
+    pub old_index: u128,
+    pub index: u128,
+    pub apy: u32,
+    pub old_borrowing_index: u128,
+    pub borrowing_index: u128,
+    pub borrowing_apy: u32,
+}
\ No newline at end of file
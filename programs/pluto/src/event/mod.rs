@@ -1,62 +1,149 @@
 pub mod event_protocol_created;
 pub mod event_protocol_set;
 pub mod event_protocol_changed_owner;
+pub mod event_protocol_set_fee_distribution;
+pub mod event_protocol_fees_collected;
+pub mod event_protocol_owner_proposed;
+pub mod event_protocol_set_timelock_delay;
+pub mod event_config_change_queued;
+pub mod event_config_change_cancelled;
+pub mod event_protocol_roles_created;
+pub mod event_protocol_role_granted;
+pub mod event_protocol_role_revoked;
 
 pub mod event_earn_config_created;
 pub mod event_earn_config_set;
 pub mod event_earn_config_changed_indexer;
+pub mod event_earn_config_set_rate_model;
+pub mod event_earn_config_set_flash_fee;
+pub mod event_earn_config_set_caps;
 
 pub mod event_vault_earn_created;
 pub mod event_vault_earn_changed_owner;
 pub mod event_vault_earn_changed_price_oracle;
+pub mod event_vault_earn_set_oracle_guard;
 pub mod event_earn_deposit;
 pub mod event_earn_withdraw;
 pub mod event_earn_withdrawn;
+pub mod event_vault_earn_accrued_interest;
+pub mod event_vault_earn_set_index;
+pub mod event_vault_earn_created_share_mint;
+pub mod event_earn_lender_tokenized;
+pub mod event_earn_flash_borrow;
+pub mod event_earn_flash_repay;
+pub mod event_earn_withdraw_queued;
+pub mod event_earn_withdraw_claimed;
+pub mod event_earn_bad_debt;
+pub mod event_earn_insurance_funded;
 
 pub mod event_leverage_config_created;
 pub mod event_leverage_config_set;
+pub mod event_leverage_config_set_insurance_ratio;
 pub mod event_leverage_config_changed_indexer;
 pub mod event_leverage_config_changed_keeper;
 
 pub mod event_vault_leverage_created;
 pub mod event_vault_leverage_changed_owner;
 pub mod event_vault_leverage_changed_price_oracle;
+pub mod event_vault_leverage_set_oracle_guard;
+pub mod event_vault_leverage_set_fallback_price_oracle;
+pub mod event_vault_leverage_created_swap_routers;
+pub mod event_vault_leverage_changed_swap_router;
+pub mod event_vault_leverage_accrued_interest;
+pub mod event_vault_leverage_set_index;
 pub mod event_leverage_borrow;
 pub mod event_leverage_fund;
 pub mod event_leverage_close;
+pub mod event_leverage_close_swap;
 pub mod event_leverage_release;
 pub mod event_leverage_open;
 pub mod event_leverage_set_safety_mode;
 pub mod event_leverage_set_emergency_eject;
 pub mod event_leverage_set_profit_taker;
+pub mod event_leverage_liquidate;
+pub mod event_leverage_liquidation_fee;
+pub mod event_leverage_keeper_closing;
+pub mod event_leverage_save;
+pub mod event_leverage_eject;
+pub mod event_leverage_take_profit;
+pub mod event_leverage_profit_taken;
+pub mod event_leverage_deleverage;
+pub mod event_leverage_add_collateral;
+pub mod event_leverage_increase;
+pub mod event_leverage_mint_position_nft;
+pub mod event_leverage_obligation_extended;
 
 pub use event_protocol_created::*;
 pub use event_protocol_set::*;
 pub use event_protocol_changed_owner::*;
+pub use event_protocol_set_fee_distribution::*;
+pub use event_protocol_fees_collected::*;
+pub use event_protocol_owner_proposed::*;
+pub use event_protocol_set_timelock_delay::*;
+pub use event_config_change_queued::*;
+pub use event_config_change_cancelled::*;
+pub use event_protocol_roles_created::*;
+pub use event_protocol_role_granted::*;
+pub use event_protocol_role_revoked::*;
 
 pub use event_earn_config_created::*;
 pub use event_earn_config_set::*;
 pub use event_earn_config_changed_indexer::*;
+pub use event_earn_config_set_rate_model::*;
+pub use event_earn_config_set_flash_fee::*;
+pub use event_earn_config_set_caps::*;
 
 pub use event_vault_earn_created::*;
 pub use event_vault_earn_changed_owner::*;
 pub use event_vault_earn_changed_price_oracle::*;
+pub use event_vault_earn_set_oracle_guard::*;
 pub use event_earn_deposit::*;
 pub use event_earn_withdraw::*;
 pub use event_earn_withdrawn::*;
+pub use event_vault_earn_accrued_interest::*;
+pub use event_vault_earn_set_index::*;
+pub use event_vault_earn_created_share_mint::*;
+pub use event_earn_lender_tokenized::*;
+pub use event_earn_flash_borrow::*;
+pub use event_earn_flash_repay::*;
+pub use event_earn_withdraw_queued::*;
+pub use event_earn_withdraw_claimed::*;
+pub use event_earn_bad_debt::*;
+pub use event_earn_insurance_funded::*;
 
 pub use event_leverage_config_created::*;
 pub use event_leverage_config_set::*;
+pub use event_leverage_config_set_insurance_ratio::*;
 pub use event_leverage_config_changed_indexer::*;
 pub use event_leverage_config_changed_keeper::*;
 
 pub use event_vault_leverage_created::*;
 pub use event_vault_leverage_changed_owner::*;
 pub use event_vault_leverage_changed_price_oracle::*;
+pub use event_vault_leverage_set_oracle_guard::*;
+pub use event_vault_leverage_set_fallback_price_oracle::*;
+pub use event_vault_leverage_created_swap_routers::*;
+pub use event_vault_leverage_changed_swap_router::*;
+pub use event_vault_leverage_accrued_interest::*;
+pub use event_vault_leverage_set_index::*;
 pub use event_leverage_borrow::*;
 pub use event_leverage_fund::*;
 pub use event_leverage_close::*;
+pub use event_leverage_close_swap::*;
 pub use event_leverage_release::*;
 pub use event_leverage_open::*;
 pub use event_leverage_set_safety_mode::*;
-pub use event_leverage_set_emergency_eject::*;
\ No newline at end of file
+pub use event_leverage_set_emergency_eject::*;
+pub use event_leverage_set_profit_taker::*;
+pub use event_leverage_liquidate::*;
+pub use event_leverage_liquidation_fee::*;
+pub use event_leverage_keeper_closing::*;
+pub use event_leverage_save::*;
+pub use event_leverage_eject::*;
+pub use event_leverage_take_profit::*;
+pub use event_leverage_profit_taken::*;
+pub use event_leverage_deleverage::*;
+pub use event_leverage_add_collateral::*;
+pub use event_leverage_increase::*;
+pub use event_leverage_mint_position_nft::*;
+pub use event_leverage_obligation_extended::*;
\ No newline at end of file
@@ -9,7 +9,7 @@ use crate::util::{
     constant::{INDEX_ONE},
 };
 
-pub fn handle(ctx: Context<EarnConfigCreate>, freeze: bool, protocol_fee: u32, ltv: u32, deposit_fee: u32, min_deposit_limit: u64, max_deposit_limit: u64, withdraw_fee: u32, min_withdraw_limit: u64, max_withdraw_limit: u64,borrow_fee: u32, min_borrow_limit: u64, max_borrow_limit: u64, floor_cap_rate: u32) -> Result<()> {
+pub fn handle(ctx: Context<EarnConfigCreate>, pause_flags: u16, protocol_fee: u32, ltv: u32, deposit_fee: u32, min_deposit_limit: u64, max_deposit_limit: u64, withdraw_fee: u32, min_withdraw_limit: u64, max_withdraw_limit: u64,borrow_fee: u32, min_borrow_limit: u64, max_borrow_limit: u64, floor_cap_rate: u32) -> Result<()> {
     let indexer = &ctx.accounts.indexer;
     let fee_vault = &ctx.accounts.fee_vault;
     let config = &mut ctx.accounts.config.load_init()?;
@@ -21,7 +21,7 @@ pub fn handle(ctx: Context<EarnConfigCreate>, freeze: bool, protocol_fee: u32, l
         authority: *ctx.accounts.config_authority.key,
         indexer: *indexer.key,
         earn_fee_vault: *fee_vault.key,
-        freeze,
+        pause_flags,
         protocol_fee,
         ltv,
         deposit_fee,
@@ -42,7 +42,7 @@ pub fn handle(ctx: Context<EarnConfigCreate>, freeze: bool, protocol_fee: u32, l
     msg!("earn config owner address: {:?}", owner.key);
     msg!("earn config indexer address: {:?}", indexer.key);
     msg!("earn config fee vault address: {:?}", fee_vault.key);
-    msg!("earn config freeze: {:?}", freeze);
+    msg!("earn config pause flags: {:?}", pause_flags);
     msg!("earn config protocol fee: {:?}", protocol_fee);
     msg!("earn config ltv: {:?}", ltv);
     msg!("earn config deposit fee: {:?}", deposit_fee);
@@ -62,7 +62,7 @@ pub fn handle(ctx: Context<EarnConfigCreate>, freeze: bool, protocol_fee: u32, l
         authority: *ctx.accounts.config_authority.key,
         indexer: *indexer.key,
         fee_vault: *fee_vault.key,
-        freeze,
+        pause_flags,
         protocol_fee,
         ltv,
         deposit_fee,
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::error::Errors;
+use crate::event::EventEarnConfigSetCaps;
+use crate::state::{EarnConfig, Protocol, Roles, SetCapsParams};
+use crate::util::{
+    seeds,
+    access::require_role,
+};
+
+pub fn handle(ctx: Context<EarnConfigSetCaps>, supply_cap: u64, borrow_cap: u64, max_utilization: u32) -> Result<()> {
+    let config = &mut ctx.accounts.config.load_mut()?;
+    let params = SetCapsParams{
+        supply_cap,
+        borrow_cap,
+        max_utilization,
+    };
+
+    // Risk managers can move the caps by a bounded step, larger changes need the owner
+    require_role(&ctx.accounts.protocol, &ctx.accounts.roles, ctx.accounts.payer.key, config.caps_required_role(&params))?;
+
+    let old_supply_cap = config.supply_cap;
+    let old_borrow_cap = config.borrow_cap;
+    let old_max_utilization = config.max_utilization;
+    config.set_caps(params)?;
+
+    msg!("earn config address: {:?}", ctx.accounts.config.key());
+    msg!("earn config supply cap: {:?}", supply_cap);
+    msg!("earn config borrow cap: {:?}", borrow_cap);
+    msg!("earn config max utilization: {:?}", max_utilization);
+
+    emit!(EventEarnConfigSetCaps{
+        config: ctx.accounts.config.key(),
+        old_supply_cap,
+        supply_cap,
+        old_borrow_cap,
+        borrow_cap,
+        old_max_utilization,
+        max_utilization,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct EarnConfigSetCaps<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    /// CHECK CONFIG EARN AUTHORITY
+    #[account(
+        seeds = [seeds::CONFIG_EARN_AUTH, config.key().as_ref()],
+        bump,
+    )]
+    pub config_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+    )]
+    pub config: AccountLoader<'info, EarnConfig>,
+
+    // Only needed when the payer is not the owner
+    #[account()]
+    pub roles: Option<AccountLoader<'info, Roles>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
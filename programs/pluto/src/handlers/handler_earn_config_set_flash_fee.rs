@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::error::Errors;
+use crate::event::EventEarnConfigSetFlashFee;
+use crate::state::{EarnConfig, Protocol, Role, Roles};
+use crate::util::{
+    seeds,
+    access::require_role,
+};
+
+pub fn handle(ctx: Context<EarnConfigSetFlashFee>, flash_fee: u32) -> Result<()> {
+    require_role(&ctx.accounts.protocol, &ctx.accounts.roles, ctx.accounts.payer.key, Some(Role::FeeManager))?;
+
+    let config = &mut ctx.accounts.config.load_mut()?;
+    let old_flash_fee = config.flash_fee;
+    config.set_flash_fee(flash_fee)?;
+
+    msg!("earn config address: {:?}", ctx.accounts.config.key());
+    msg!("earn config flash fee: {:?}", flash_fee);
+
+    emit!(EventEarnConfigSetFlashFee{
+        config: ctx.accounts.config.key(),
+        old_flash_fee,
+        flash_fee,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct EarnConfigSetFlashFee<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    /// CHECK CONFIG EARN AUTHORITY
+    #[account(
+        seeds = [seeds::CONFIG_EARN_AUTH, config.key().as_ref()],
+        bump,
+    )]
+    pub config_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+    )]
+    pub config: AccountLoader<'info, EarnConfig>,
+
+    // Only needed when the payer is not the owner
+    #[account()]
+    pub roles: Option<AccountLoader<'info, Roles>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
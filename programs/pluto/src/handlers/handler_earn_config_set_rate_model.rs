@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::error::Errors;
+use crate::event::EventEarnConfigSetRateModel;
+use crate::state::{EarnConfig, Protocol, SetRateModelParams};
+use crate::util::{
+    seeds,
+};
+
+pub fn handle(ctx: Context<EarnConfigSetRateModel>, base_rate: u32, slope1: u32, optimal_utilization: u32, slope2: u32) -> Result<()> {
+    let config = &mut ctx.accounts.config.load_mut()?;
+    let old_base_rate = config.base_rate;
+    let old_slope1 = config.slope1;
+    let old_optimal_utilization = config.optimal_utilization;
+    let old_slope2 = config.slope2;
+    config.set_rate_model(SetRateModelParams{
+        base_rate,
+        slope1,
+        optimal_utilization,
+        slope2,
+    })?;
+
+    msg!("earn config address: {:?}", ctx.accounts.config.key());
+    msg!("earn config base rate: {:?}", base_rate);
+    msg!("earn config slope1: {:?}", slope1);
+    msg!("earn config optimal utilization: {:?}", optimal_utilization);
+    msg!("earn config slope2: {:?}", slope2);
+
+    emit!(EventEarnConfigSetRateModel{
+        config: ctx.accounts.config.key(),
+        old_base_rate,
+        base_rate,
+        old_slope1,
+        slope1,
+        old_optimal_utilization,
+        optimal_utilization,
+        old_slope2,
+        slope2,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct EarnConfigSetRateModel<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    /// CHECK CONFIG EARN AUTHORITY
+    #[account(
+        seeds = [seeds::CONFIG_EARN_AUTH, config.key().as_ref()],
+        bump,
+    )]
+    pub config_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+    )]
+    pub config: AccountLoader<'info, EarnConfig>,
+
+    #[account(mut, address = protocol.load()?.owner @ Errors::NotOwner)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::error::Errors;
+use crate::event::EventConfigChangeCancelled;
+use crate::state::{LeverageConfig, PendingLeverageConfig, Protocol};
+use crate::util::{
+    seeds,
+};
+
+pub fn handle(ctx: Context<LeverageConfigCancelSet>) -> Result<()> {
+    msg!("leverage config address: {:?}", ctx.accounts.config.key());
+    msg!("leverage pending config address: {:?}", ctx.accounts.pending_config.key());
+
+    emit!(EventConfigChangeCancelled{
+        config: ctx.accounts.config.key(),
+        pending_config: ctx.accounts.pending_config.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct LeverageConfigCancelSet<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account(
+        has_one = protocol @ Errors::InvalidProtocol,
+    )]
+    pub config: AccountLoader<'info, LeverageConfig>,
+    #[account(
+        mut,
+        seeds = [seeds::PENDING_CONFIG, config.key().as_ref()],
+        bump = pending_config.load()?.bump,
+        has_one = config @ Errors::InvalidConfig,
+        close = payer,
+    )]
+    pub pending_config: AccountLoader<'info, PendingLeverageConfig>,
+
+    #[account(mut, address = protocol.load()?.owner @ Errors::NotOwner)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
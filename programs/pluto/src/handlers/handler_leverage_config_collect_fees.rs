@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenInterface, Mint, TokenAccount};
+use crate::error::Errors;
+use crate::event::EventProtocolFeesCollected;
+use crate::state::{LeverageConfig, Protocol, Role, Roles};
+use crate::util::{seeds, access::require_role, fee_sweep::sweep_fees};
+
+pub fn handle(ctx: Context<LeverageConfigCollectFees>) -> Result<()> {
+    require_role(&ctx.accounts.protocol, &ctx.accounts.roles, ctx.accounts.payer.key, Some(Role::FeeManager))?;
+
+    let protocol = &ctx.accounts.protocol.load()?;
+    let amount = ctx.accounts.leverage_fee_vault.amount;
+
+    msg!("protocol address: {:?}", ctx.accounts.protocol.key());
+    msg!("leverage config address: {:?}", ctx.accounts.config.key());
+    msg!("leverage fee vault address: {:?} amount: {:?}", ctx.accounts.leverage_fee_vault.key(), amount);
+
+    require!(amount > 0, Errors::InvalidAmountZero);
+
+    let split = protocol.split_fees(amount)?;
+
+    msg!("treasury_amount: {:?} insurance_amount: {:?} buyback_amount: {:?}", split.treasury_amount, split.insurance_amount, split.buyback_amount);
+
+    let config_key = ctx.accounts.config.key();
+    let seeds = &[
+        seeds::CONFIG_LEVERAGE_AUTH,
+        config_key.as_ref(),
+        &[ctx.bumps.config_authority],
+    ];
+
+    let signer_seeds = &[&seeds[..]];
+
+    sweep_fees(
+        ctx.accounts.leverage_fee_vault.to_account_info(),
+        ctx.accounts.treasury_ata.to_account_info(),
+        ctx.accounts.insurance_fund_ata.to_account_info(),
+        ctx.accounts.buyback_ata.to_account_info(),
+        ctx.accounts.config_authority.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.token_mint.to_account_info(),
+        ctx.accounts.token_mint.decimals,
+        &split,
+        signer_seeds,
+    )?;
+
+    emit!(EventProtocolFeesCollected {
+        protocol: ctx.accounts.protocol.key(),
+        config: ctx.accounts.config.key(),
+        fee_vault: ctx.accounts.leverage_fee_vault.key(),
+        token_mint: ctx.accounts.token_mint.key(),
+        amount,
+        treasury: protocol.treasury,
+        treasury_amount: split.treasury_amount,
+        insurance_fund: protocol.insurance_fund,
+        insurance_amount: split.insurance_amount,
+        buyback: protocol.buyback,
+        buyback_amount: split.buyback_amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct LeverageConfigCollectFees<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    /// CHECK CONFIG LEVERAGE AUTHORITY
+    #[account(
+        seeds = [seeds::CONFIG_LEVERAGE_AUTH, config.key().as_ref()],
+        bump,
+    )]
+    pub config_authority: AccountInfo<'info>,
+    #[account(
+        has_one = protocol @ Errors::InvalidProtocol,
+        has_one = leverage_fee_vault,
+    )]
+    pub config: AccountLoader<'info, LeverageConfig>,
+
+    // Only fee vaults owned by the config authority can be swept
+    #[account(
+        mut,
+        token::token_program = token_program,
+        token::mint = token_mint,
+        token::authority = config_authority,
+    )]
+    pub leverage_fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::token_program = token_program,
+        token::mint = token_mint,
+        constraint = treasury_ata.owner == protocol.load()?.treasury @ Errors::InvalidAddress,
+    )]
+    pub treasury_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::token_program = token_program,
+        token::mint = token_mint,
+        constraint = insurance_fund_ata.owner == protocol.load()?.insurance_fund @ Errors::InvalidAddress,
+    )]
+    pub insurance_fund_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::token_program = token_program,
+        token::mint = token_mint,
+        constraint = buyback_ata.owner == protocol.load()?.buyback @ Errors::InvalidAddress,
+    )]
+    pub buyback_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // Only needed when the payer is not the owner
+    #[account()]
+    pub roles: Option<AccountLoader<'info, Roles>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
\ No newline at end of file
@@ -10,7 +10,7 @@ use crate::util::{
 };
 
 pub fn handle(
-    ctx: Context<LeverageConfigCreate>, freeze: bool,
+    ctx: Context<LeverageConfigCreate>, pause_flags: u16,
     protocol_fee: u32, min_leverage: u32, max_leverage: u32, leverage_step: u32,
     leverage_fee: u32, min_leverage_limit: u64, max_leverage_limit: u64,
     deleverage_fee: u32, min_deleverage_limit: u64, max_deleverage_limit: u64,
@@ -30,7 +30,7 @@ pub fn handle(
         indexer: *indexer.key,
         keeper: *keeper.key,
         leverage_fee_vault: *fee_vault.key,
-        freeze,
+        pause_flags,
         protocol_fee,
         min_leverage,
         max_leverage,
@@ -59,7 +59,7 @@ pub fn handle(
     msg!("leverage config indexer address: {:?}", indexer.key);
     msg!("leverage config keeper address: {:?}", keeper.key);
     msg!("leverage config fee vault address: {:?}", fee_vault.key);
-    msg!("leverage config freeze: {:?}", freeze);
+    msg!("leverage config pause flags: {:?}", pause_flags);
     msg!("leverage config protocol fee: {:?}", protocol_fee);
     msg!("leverage config min leverage: {:?}", min_leverage);
     msg!("leverage config max leverage: {:?}", max_leverage);
@@ -87,7 +87,7 @@ pub fn handle(
         indexer: *indexer.key,
         keeper: *keeper.key,
         fee_vault: *fee_vault.key,
-        freeze,
+        pause_flags,
         protocol_fee,
         min_leverage,
         max_leverage,
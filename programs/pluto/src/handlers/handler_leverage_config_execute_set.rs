@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::error::Errors;
+use crate::event::EventLeverageConfigSet;
+use crate::state::{LeverageConfig, PendingLeverageConfig, Protocol, Roles};
+use crate::util::{
+    seeds,
+    access::require_role,
+};
+
+pub fn handle(ctx: Context<LeverageConfigExecuteSet>) -> Result<()> {
+    let pending_config = &ctx.accounts.pending_config.load()?;
+    let config = &mut ctx.accounts.config.load_mut()?;
+    let params = pending_config.params()?;
+
+    // The role that could queue the change can also execute it once the delay is over
+    require_role(&ctx.accounts.protocol, &ctx.accounts.roles, ctx.accounts.payer.key, config.required_role(&params))?;
+
+    msg!("leverage config address: {:?}", ctx.accounts.config.key());
+    msg!("leverage pending config address: {:?}", ctx.accounts.pending_config.key());
+    msg!("leverage pending config queued at: {:?} execute after: {:?}", pending_config.queued_at, pending_config.execute_after);
+
+    let event = EventLeverageConfigSet::new(ctx.accounts.config.key(), config, &params);
+    config.set_config(&params)?;
+
+    emit!(event);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct LeverageConfigExecuteSet<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+    )]
+    pub config: AccountLoader<'info, LeverageConfig>,
+    #[account(
+        mut,
+        seeds = [seeds::PENDING_CONFIG, config.key().as_ref()],
+        bump = pending_config.load()?.bump,
+        has_one = config @ Errors::InvalidConfig,
+        close = payer,
+    )]
+    pub pending_config: AccountLoader<'info, PendingLeverageConfig>,
+
+    // Only needed when the payer is not the owner
+    #[account()]
+    pub roles: Option<AccountLoader<'info, Roles>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
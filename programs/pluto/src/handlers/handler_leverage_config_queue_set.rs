@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::error::Errors;
+use crate::event::EventConfigChangeQueued;
+use crate::state::{InitPendingConfigParams, LeverageConfig, PendingLeverageConfig, Protocol, Roles, SetLeverageConfigParams};
+use crate::util::{
+    seeds,
+    access::require_role,
+};
+
+pub fn handle(ctx: Context<LeverageConfigQueueSet>, params: SetLeverageConfigParams) -> Result<()> {
+    let pending_config = &mut ctx.accounts.pending_config.load_init()?;
+    let owner = &mut ctx.accounts.payer;
+
+    // Fee and risk managers queue the changes of their role the same way they set them
+    require_role(&ctx.accounts.protocol, &ctx.accounts.roles, owner.key, ctx.accounts.config.load()?.required_role(&params))?;
+    let execute_after = ctx.accounts.protocol.load()?.execute_after()?;
+
+    pending_config.init(InitPendingConfigParams{
+        bump: ctx.bumps.pending_config,
+        config: ctx.accounts.config.key(),
+        proposer: owner.key(),
+        execute_after,
+    }, &params)?;
+
+    msg!("leverage config address: {:?}", ctx.accounts.config.key());
+    msg!("leverage pending config address: {:?}", ctx.accounts.pending_config.key());
+    msg!("leverage config change execute after: {:?}", execute_after);
+
+    emit!(EventConfigChangeQueued{
+        config: ctx.accounts.config.key(),
+        pending_config: ctx.accounts.pending_config.key(),
+        proposer: owner.key(),
+        execute_after,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct LeverageConfigQueueSet<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account(
+        has_one = protocol @ Errors::InvalidProtocol,
+    )]
+    pub config: AccountLoader<'info, LeverageConfig>,
+    #[account(
+        init,
+        seeds = [seeds::PENDING_CONFIG, config.key().as_ref()],
+        bump,
+        payer = payer,
+        space = PendingLeverageConfig::INIT_SPACE+(1*8),
+    )]
+    pub pending_config: AccountLoader<'info, PendingLeverageConfig>,
+
+    // Only needed when the payer is not the owner
+    #[account()]
+    pub roles: Option<AccountLoader<'info, Roles>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::error::Errors;
+use crate::event::EventLeverageConfigSetInsuranceRatio;
+use crate::state::{LeverageConfig, Protocol, Role, Roles};
+use crate::util::{
+    seeds,
+    access::require_role,
+};
+
+pub fn handle(ctx: Context<LeverageConfigSetInsuranceRatio>, insurance_ratio: u32) -> Result<()> {
+    require_role(&ctx.accounts.protocol, &ctx.accounts.roles, ctx.accounts.payer.key, Some(Role::FeeManager))?;
+
+    let config = &mut ctx.accounts.config.load_mut()?;
+    let old_insurance_ratio = config.insurance_ratio;
+    config.set_insurance_ratio(insurance_ratio)?;
+
+    msg!("leverage config address: {:?}", ctx.accounts.config.key());
+    msg!("leverage config insurance ratio: {:?}", insurance_ratio);
+
+    emit!(EventLeverageConfigSetInsuranceRatio{
+        config: ctx.accounts.config.key(),
+        old_insurance_ratio,
+        insurance_ratio,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct LeverageConfigSetInsuranceRatio<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    /// CHECK CONFIG LEVERAGE AUTHORITY
+    #[account(
+        seeds = [seeds::CONFIG_LEVERAGE_AUTH, config.key().as_ref()],
+        bump,
+    )]
+    pub config_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+    )]
+    pub config: AccountLoader<'info, LeverageConfig>,
+
+    // Only needed when the payer is not the owner
+    #[account()]
+    pub roles: Option<AccountLoader<'info, Roles>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::event::EventProtocolChangeOwner;
+use crate::state::{Protocol};
+
+pub fn handle(ctx: Context<ProtocolAcceptOwner>) -> Result<()> {
+    let protocol = &mut ctx.accounts.protocol.load_mut()?;
+    let new_owner = &mut ctx.accounts.payer;
+    let old_owner = protocol.owner;
+
+    protocol.accept_owner(new_owner.key())?;
+
+    msg!("protocol address: {:?}", ctx.accounts.protocol.key());
+    msg!("Config owner changed successfully");
+
+    emit!(EventProtocolChangeOwner{
+        old_owner,
+        owner: new_owner.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProtocolAcceptOwner<'info> {
+    #[account(mut)]
+    pub protocol: AccountLoader<'info, Protocol>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -1,19 +1,22 @@
 use anchor_lang::prelude::*;
 use crate::error::Errors;
-use crate::event::EventProtocolChangeOwner;
+use crate::event::EventProtocolOwnerProposed;
 use crate::state::{Protocol};
 
 pub fn handle(ctx: Context<ProtocolChangeOwner>, new_owner: Pubkey) -> Result<()> {
     let protocol = &mut ctx.accounts.protocol.load_mut()?;
     let owner = &mut ctx.accounts.payer;
-    let old_owner = protocol.owner;
-    protocol.owner = new_owner;
 
-    msg!("Config owner changed successfully");
+    // The new owner only takes over once it accepts
+    protocol.propose_owner(new_owner)?;
 
-    emit!(EventProtocolChangeOwner{
-        old_owner,
-        owner: new_owner,
+    msg!("protocol address: {:?}", ctx.accounts.protocol.key());
+    msg!("Config owner proposed successfully");
+
+    emit!(EventProtocolOwnerProposed{
+        protocol: ctx.accounts.protocol.key(),
+        owner: owner.key(),
+        pending_owner: new_owner,
     });
 
     Ok(())
@@ -21,7 +24,7 @@ pub fn handle(ctx: Context<ProtocolChangeOwner>, new_owner: Pubkey) -> Result<()
 
 #[derive(Accounts)]
 pub struct ProtocolChangeOwner<'info> {
-    #[account()]
+    #[account(mut)]
     pub protocol: AccountLoader<'info, Protocol>,
 
     #[account(mut, address = protocol.load()?.owner @ Errors::NotOwner)]
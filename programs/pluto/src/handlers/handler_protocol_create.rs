@@ -8,33 +8,24 @@ use crate::util::{
     constant::{INDEX_ONE},
 };
 
-pub fn handle(ctx: Context<ProtocolCreate>, freeze: bool, freeze_earn: bool, freeze_lend: bool, freeze_leverage: bool) -> Result<()> {
+pub fn handle(ctx: Context<ProtocolCreate>, pause_flags: u16) -> Result<()> {
     let protocol = &mut ctx.accounts.protocol.load_init()?;
     let owner = &mut ctx.accounts.payer;
     protocol.init(InitProtocolParams{
         bump: ctx.bumps.protocol,
         creator: *owner.key,
         owner: *owner.key,
-        freeze,
-        freeze_earn,
-        freeze_lend,
-        freeze_leverage,
+        pause_flags,
     })?;
 
     msg!("earn protocol address: {:?}", ctx.accounts.protocol.key());
     msg!("earn protocol owner address: {:?}", owner.key);
-    msg!("earn protocol freeze: {:?}", freeze);
-    msg!("earn protocol freeze earn: {:?}", freeze_earn);
-    msg!("earn protocol freeze lend: {:?}", freeze_lend);
-    msg!("earn protocol freeze leverage: {:?}", freeze_leverage);
+    msg!("earn protocol pause flags: {:?}", pause_flags);
 
     emit!(EventProtocolCreated{
         creator: *owner.key,
         owner: *owner.key,
-        freeze,
-        freeze_earn,
-        freeze_lend,
-        freeze_leverage,
+        pause_flags,
     });
 
     Ok(())
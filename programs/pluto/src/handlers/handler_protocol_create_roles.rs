@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::error::Errors;
+use crate::event::EventRolesCreated;
+use crate::state::{InitRolesParams, Protocol, Roles};
+use crate::util::{
+    seeds,
+};
+
+pub fn handle(ctx: Context<ProtocolCreateRoles>) -> Result<()> {
+    let roles = &mut ctx.accounts.roles.load_init()?;
+
+    roles.init(InitRolesParams{
+        bump: ctx.bumps.roles,
+        protocol: ctx.accounts.protocol.key(),
+    })?;
+
+    msg!("protocol address: {:?}", ctx.accounts.protocol.key());
+    msg!("protocol roles address: {:?}", ctx.accounts.roles.key());
+
+    emit!(EventRolesCreated{
+        protocol: ctx.accounts.protocol.key(),
+        roles: ctx.accounts.roles.key(),
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProtocolCreateRoles<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account(
+        init,
+        seeds = [seeds::ROLES, protocol.key().as_ref()],
+        bump,
+        payer = payer,
+        space = Roles::INIT_SPACE+(1*8),
+    )]
+    pub roles: AccountLoader<'info, Roles>,
+
+    #[account(mut, address = protocol.load()?.owner @ Errors::NotOwner)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::error::Errors;
+use crate::event::EventRoleGranted;
+use crate::state::{Protocol, Role, Roles};
+use crate::util::{
+    seeds,
+};
+
+pub fn handle(ctx: Context<ProtocolGrantRole>, member: Pubkey, role: Role) -> Result<()> {
+    let roles = &mut ctx.accounts.roles.load_mut()?;
+    roles.grant(member, role)?;
+
+    msg!("protocol address: {:?}", ctx.accounts.protocol.key());
+    msg!("protocol role granted: {:?} to: {:?}", role, member);
+
+    emit!(EventRoleGranted{
+        protocol: ctx.accounts.protocol.key(),
+        member,
+        role,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProtocolGrantRole<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account(
+        mut,
+        seeds = [seeds::ROLES, protocol.key().as_ref()],
+        bump = roles.load()?.bump,
+        has_one = protocol @ Errors::InvalidProtocol,
+    )]
+    pub roles: AccountLoader<'info, Roles>,
+
+    #[account(mut, address = protocol.load()?.owner @ Errors::NotOwner)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
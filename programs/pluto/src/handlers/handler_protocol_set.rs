@@ -1,36 +1,29 @@
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use crate::error::Errors;
 use crate::event::EventProtocolSet;
-use crate::state::{Protocol, SetProtocolParams};
+use crate::state::{Protocol, Roles, SetProtocolParams};
 use crate::util::{
-    seeds,
+    access::require_role,
     constant::{INDEX_ONE},
 };
 
-pub fn handle(ctx: Context<ProtocolSet>, freeze:bool, freeze_earn: bool, freeze_lend: bool, freeze_leverage: bool) -> Result<()> {
+pub fn handle(ctx: Context<ProtocolSet>, pause_flags: u16) -> Result<()> {
+    let params = SetProtocolParams{
+        pause_flags,
+    };
+    let role = ctx.accounts.protocol.load()?.required_role(&params);
+    require_role(&ctx.accounts.protocol, &ctx.accounts.roles, ctx.accounts.payer.key, role)?;
+
     let protocol = &mut ctx.accounts.protocol.load_mut()?;
     let owner = &mut ctx.accounts.payer;
-    require!(*owner.key == protocol.owner, Errors::NotOwner);
-    protocol.set_protocol(SetProtocolParams{
-        freeze,
-        freeze_earn,
-        freeze_lend,
-        freeze_leverage,
-    })?;
+    protocol.set_protocol(params)?;
 
     msg!("earn protocol address: {:?}", ctx.accounts.protocol.key());
     msg!("earn protocol owner address: {:?}", owner.key);
-    msg!("earn protocol freeze: {:?}", freeze);
-    msg!("earn protocol freeze_earn: {:?}", freeze_earn);
-    msg!("earn protocol freeze_lend: {:?}", freeze_lend);
-    msg!("earn protocol freeze_leverage: {:?}", freeze_leverage);
+    msg!("earn protocol pause flags: {:?}", pause_flags);
 
     emit!(EventProtocolSet{
-        freeze,
-        freeze_earn,
-        freeze_lend,
-        freeze_leverage,
+        pause_flags,
     });
 
     Ok(())
@@ -38,12 +31,11 @@ pub fn handle(ctx: Context<ProtocolSet>, freeze:bool, freeze_earn: bool, freeze_
 
 #[derive(Accounts)]
 pub struct ProtocolSet<'info> {
-    #[account(
-        mut,
-        seeds = [seeds::PROTOCOL, payer.key().as_ref()],
-        bump,
-    )]
+    #[account(mut)]
     pub protocol: AccountLoader<'info, Protocol>,
+    // Only needed when the payer is not the owner
+    #[account()]
+    pub roles: Option<AccountLoader<'info, Roles>>,
 
     #[account(mut)]
     pub payer: Signer<'info>,
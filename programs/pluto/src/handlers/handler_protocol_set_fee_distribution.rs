@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::error::Errors;
+use crate::event::EventProtocolSetFeeDistribution;
+use crate::state::{Protocol, SetFeeDistributionParams};
+
+pub fn handle(ctx: Context<ProtocolSetFeeDistribution>, treasury: Pubkey, insurance_fund: Pubkey, buyback: Pubkey, treasury_ratio: u32, insurance_ratio: u32, buyback_ratio: u32) -> Result<()> {
+    let protocol = &mut ctx.accounts.protocol.load_mut()?;
+    let old_treasury = protocol.treasury;
+    let old_insurance_fund = protocol.insurance_fund;
+    let old_buyback = protocol.buyback;
+    let old_treasury_ratio = protocol.treasury_ratio;
+    let old_insurance_ratio = protocol.insurance_ratio;
+    let old_buyback_ratio = protocol.buyback_ratio;
+
+    protocol.set_fee_distribution(SetFeeDistributionParams {
+        treasury,
+        insurance_fund,
+        buyback,
+        treasury_ratio,
+        insurance_ratio,
+        buyback_ratio,
+    })?;
+
+    msg!("protocol address: {:?}", ctx.accounts.protocol.key());
+    msg!("protocol treasury: {:?} ratio: {:?}", treasury, treasury_ratio);
+    msg!("protocol insurance fund: {:?} ratio: {:?}", insurance_fund, insurance_ratio);
+    msg!("protocol buyback: {:?} ratio: {:?}", buyback, buyback_ratio);
+
+    emit!(EventProtocolSetFeeDistribution {
+        protocol: ctx.accounts.protocol.key(),
+        old_treasury,
+        treasury,
+        old_insurance_fund,
+        insurance_fund,
+        old_buyback,
+        buyback,
+        old_treasury_ratio,
+        treasury_ratio,
+        old_insurance_ratio,
+        insurance_ratio,
+        old_buyback_ratio,
+        buyback_ratio,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProtocolSetFeeDistribution<'info> {
+    #[account(mut)]
+    pub protocol: AccountLoader<'info, Protocol>,
+
+    #[account(mut, address = protocol.load()?.owner @ Errors::NotOwner)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::error::Errors;
+use crate::event::EventProtocolSetTimelockDelay;
+use crate::state::{Protocol};
+
+pub fn handle(ctx: Context<ProtocolSetTimelockDelay>, timelock_delay: i64) -> Result<()> {
+    let protocol = &mut ctx.accounts.protocol.load_mut()?;
+    let old_timelock_delay = protocol.timelock_delay;
+
+    let applied = protocol.set_timelock_delay(timelock_delay)?;
+
+    msg!("protocol address: {:?}", ctx.accounts.protocol.key());
+    msg!("protocol timelock delay: {:?} applied: {:?}", timelock_delay, applied);
+    msg!("protocol pending timelock delay: {:?} after: {:?}", protocol.pending_timelock_delay, protocol.pending_timelock_delay_after);
+
+    emit!(EventProtocolSetTimelockDelay{
+        protocol: ctx.accounts.protocol.key(),
+        old_timelock_delay,
+        timelock_delay: protocol.timelock_delay,
+        pending_timelock_delay: protocol.pending_timelock_delay,
+        pending_timelock_delay_after: protocol.pending_timelock_delay_after,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProtocolSetTimelockDelay<'info> {
+    #[account(mut)]
+    pub protocol: AccountLoader<'info, Protocol>,
+
+    #[account(mut, address = protocol.load()?.owner @ Errors::NotOwner)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::error::{ErrorEarn, Errors};
+use crate::event::EventVaultEarnAccruedInterest;
+use crate::state::{EarnConfig, Protocol, VaultEarn};
+use crate::util::pause::{require_not_paused, PauseFlags};
+
+pub fn handle(ctx: Context<VaultEarnAccrueInterest>) -> Result<()> {
+    require_not_paused(&[
+        ctx.accounts.protocol.load()?.pause_flags,
+        ctx.accounts.earn_config.load()?.pause_flags,
+    ], PauseFlags::INDEX_UPDATE)?;
+
+    let earn_config = &ctx.accounts.earn_config.load()?;
+    let vault = &mut ctx.accounts.vault.load_mut()?;
+
+    require!(earn_config.has_rate_model(), ErrorEarn::RateModelNotConfigured);
+
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("vault config address: {:?}", ctx.accounts.earn_config.key());
+
+    let old_index = vault.index;
+    let utilization_rate = vault.utilization_rate()?;
+    let borrow_rate = earn_config.borrow_rate(utilization_rate)?;
+    let supply_rate = earn_config.supply_rate(utilization_rate, borrow_rate)?;
+
+    vault.accrue_interest(earn_config)?;
+
+    msg!("utilization_rate: {:?} borrow_rate: {:?} supply_rate: {:?}", utilization_rate, borrow_rate, supply_rate);
+    msg!("old index: {:?} index: {:?}", old_index, vault.index);
+
+    emit!(EventVaultEarnAccruedInterest{
+        vault: ctx.accounts.vault.key(),
+        utilization_rate,
+        borrow_rate,
+        supply_rate,
+        old_index,
+        index: vault.index,
+        last_index_updated: vault.last_index_updated,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VaultEarnAccrueInterest<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account(
+        has_one = protocol @ Errors::InvalidProtocol,
+    )]
+    pub earn_config: AccountLoader<'info, EarnConfig>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+        has_one = earn_config @ Errors::InvalidConfig,
+    )]
+    pub vault: AccountLoader<'info, VaultEarn>,
+
+    pub user: Signer<'info>,
+}
\ No newline at end of file
@@ -1,28 +1,42 @@
 use anchor_lang::prelude::*;
-use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 use crate::error::Errors;
 use crate::event::{EventVaultEarnChangedPriceOracle};
 use crate::state::{Protocol, VaultEarn};
+use crate::util::oracle;
+use crate::util::oracle_source::OracleSource;
 
-pub fn handle(ctx: Context<VaultEarnChangePriceOracle>, price_feed: [u8; 64]) -> Result<()> {
+pub fn handle(ctx: Context<VaultEarnChangePriceOracle>, price_source: OracleSource, price_feed: [u8; 64], fixed_price: u64) -> Result<()> {
     let vault = &mut ctx.accounts.vault.load_mut()?;
     let owner = &mut ctx.accounts.owner;
+    let old_price_source = vault.price_source;
     let old_price_oracle = vault.price_oracle;
     let old_price_feed = vault.price_feed;
-    vault.change_price_oracle(ctx.accounts.price_oracle.key(), price_feed)?;
+    let old_fixed_price = vault.fixed_price;
+
+    oracle::validate_source(price_source, &ctx.accounts.price_oracle, &price_feed, fixed_price)?;
+
+    vault.change_price_oracle(price_source, ctx.accounts.price_oracle.key(), price_feed, fixed_price)?;
 
     msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("old price source: {:?}", old_price_source);
+    msg!("new price source: {:?}", price_source);
     msg!("old price oracle address: {:?}", old_price_oracle);
     msg!("new price oracle address: {:?}", ctx.accounts.price_oracle.key());
     msg!("old price feed: {:?}", old_price_feed);
     msg!("new price feed: {:?}", price_feed);
+    msg!("old fixed price: {:?}", old_fixed_price);
+    msg!("new fixed price: {:?}", fixed_price);
 
     emit!(EventVaultEarnChangedPriceOracle {
         vault: ctx.accounts.vault.key(),
+        old_price_source,
+        new_price_source: price_source,
         old_price_oracle,
         new_price_oracle: ctx.accounts.price_oracle.key(),
         old_price_feed,
         new_price_feed: price_feed,
+        old_fixed_price,
+        new_fixed_price: fixed_price,
     });
 
     Ok(())
@@ -35,11 +49,11 @@ pub struct VaultEarnChangePriceOracle<'info> {
     #[account(
         mut,
         has_one = protocol @ Errors::InvalidProtocol,
-        has_one = price_oracle,
     )]
     pub vault: AccountLoader<'info, VaultEarn>,
 
-    pub price_oracle: Box<Account<'info, PriceUpdateV2>>,
+    /// CHECK: validated against the price source
+    pub price_oracle: UncheckedAccount<'info>,
 
     #[account(mut, address = protocol.load()?.owner @ Errors::NotOwner)]
     pub owner: Signer<'info>,
@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{TokenInterface, Mint, TokenAccount};
+use crate::error::{ErrorEarn, Errors};
+use crate::event::{EventEarnWithdrawClaimed};
+use crate::state::{EarnConfig, Protocol, WithdrawRequest};
+use crate::state::vault_earn::VaultEarn;
+use crate::util::{seeds, transfer_token::transfer_token_with_signer};
+use crate::util::pause::{require_not_paused, PauseFlags};
+
+pub fn handle(ctx: Context<VaultEarnClaimWithdraw>) -> Result<()> {
+    check_pause(&ctx)?;
+
+    let vault = &mut ctx.accounts.vault.load_mut()?;
+    let withdraw_request = &ctx.accounts.withdraw_request.load()?;
+
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("withdraw request address: {:?}", ctx.accounts.withdraw_request.key());
+    msg!("ticket: {:?} amount: {:?} queued_before: {:?}", withdraw_request.ticket, withdraw_request.amount, withdraw_request.queued_before);
+
+    // Liquidity that arrived outside repayments, such as deposits, fills the queue here
+    vault.fill_withdraw_queue_from_liquidity(ctx.accounts.vault_liquidity.amount)?;
+
+    msg!("withdraw_filled_amount: {:?} withdraw_claimed_amount: {:?}", vault.withdraw_filled_amount, vault.withdraw_claimed_amount);
+
+    vault.claim_withdraw(withdraw_request.queued_before, withdraw_request.amount)?;
+
+    let fee_amount = withdraw_request.fee_amount.checked_add(withdraw_request.protocol_fee_amount).ok_or(ErrorEarn::InsufficientFund)?;
+    let amount_after_fee = withdraw_request.amount.checked_sub(fee_amount).ok_or(ErrorEarn::InsufficientFund)?;
+
+    msg!("fee_amount: {:?} amount_after_fee: {:?}", fee_amount, amount_after_fee);
+
+    let vault_key = ctx.accounts.vault.key();
+    let seeds = &[
+        seeds::VAULT_EARN_AUTH,
+        vault_key.as_ref(),
+        &[ctx.bumps.vault_authority],
+    ];
+
+    let signer_seeds = &[&seeds[..]];
+
+    if fee_amount > 0 {
+        transfer_token_with_signer(
+            ctx.accounts.vault_liquidity.to_account_info(),
+            ctx.accounts.earn_fee_vault.to_account_info(),
+            ctx.accounts.vault_authority.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.token_mint.to_account_info(),
+            fee_amount,
+            ctx.accounts.token_mint.decimals,
+            signer_seeds,
+        )?;
+    }
+
+    transfer_token_with_signer(
+        ctx.accounts.vault_liquidity.to_account_info(),
+        ctx.accounts.user_ata.to_account_info(),
+        ctx.accounts.vault_authority.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.token_mint.to_account_info(),
+        amount_after_fee,
+        ctx.accounts.token_mint.decimals,
+        signer_seeds,
+    )?;
+
+    emit!(EventEarnWithdrawClaimed {
+        vault: ctx.accounts.vault.key(),
+        user: ctx.accounts.user.key(),
+        withdraw_request: ctx.accounts.withdraw_request.key(),
+        ticket: withdraw_request.ticket,
+        amount: withdraw_request.amount,
+        fee_amount: withdraw_request.fee_amount,
+        protocol_fee_amount: withdraw_request.protocol_fee_amount,
+        withdraw_filled_amount: vault.withdraw_filled_amount,
+        withdraw_claimed_amount: vault.withdraw_claimed_amount,
+    });
+
+    Ok(())
+}
+
+fn check_pause(ctx: &Context<VaultEarnClaimWithdraw>) -> Result<()> {
+    require_not_paused(&[
+        ctx.accounts.protocol.load()?.pause_flags,
+        ctx.accounts.earn_config.load()?.pause_flags,
+    ], PauseFlags::WITHDRAW)
+}
+
+#[derive(Accounts)]
+pub struct VaultEarnClaimWithdraw<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account(
+        has_one = earn_fee_vault,
+    )]
+    pub earn_config: AccountLoader<'info, EarnConfig>,
+    /// CHECK Safe
+    #[account(mut)]
+    pub earn_fee_vault: AccountInfo<'info>,
+    /// CHECK VAULT EARN AUTHORITY
+    #[account(
+        seeds = [seeds::VAULT_EARN_AUTH, vault.key().as_ref()],
+        bump,
+    )]
+    pub vault_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+        has_one = earn_config @ Errors::InvalidConfig,
+        has_one = token_program,
+        has_one = token_mint,
+    )]
+    pub vault: AccountLoader<'info, VaultEarn>,
+
+    #[account(
+        mut,
+        has_one = vault,
+        constraint = withdraw_request.load()?.owner == user.key() @ ErrorEarn::InvalidOwner,
+        close = user,
+    )]
+    pub withdraw_request: AccountLoader<'info, WithdrawRequest>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        associated_token::token_program = token_program,
+        associated_token::mint = token_mint,
+        associated_token::authority = vault_authority,
+    )]
+    pub vault_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::token_program = token_program,
+        associated_token::mint = token_mint,
+        associated_token::authority = user
+    )]
+    pub user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
\ No newline at end of file
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::Mint;
+use crate::error::Errors;
+use crate::event::{EventVaultEarnCreatedShareMint};
+use crate::state::{Protocol, VaultEarn};
+use crate::util::seeds;
+use crate::util::constant::UNIT_DECIMALS;
+
+pub fn handle(ctx: Context<VaultEarnCreateShareMint>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault.load_mut()?;
+    vault.set_share_mint(ctx.accounts.share_mint.key())?;
+
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("share mint address: {:?}", ctx.accounts.share_mint.key());
+    msg!("unit supply: {:?}", vault.unit_supply);
+
+    emit!(EventVaultEarnCreatedShareMint {
+        vault: ctx.accounts.vault.key(),
+        share_mint: ctx.accounts.share_mint.key(),
+        decimals: UNIT_DECIMALS,
+        unit_supply: vault.unit_supply,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VaultEarnCreateShareMint<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    /// CHECK VAULT EARN AUTHORITY
+    #[account(
+        seeds = [seeds::VAULT_EARN_AUTH, vault.key().as_ref()],
+        bump,
+    )]
+    pub vault_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+    )]
+    pub vault: AccountLoader<'info, VaultEarn>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [seeds::LENDER_MINT, vault.key().as_ref()],
+        bump,
+        mint::decimals = UNIT_DECIMALS,
+        mint::authority = vault_authority,
+        mint::token_program = share_token_program,
+    )]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, address = protocol.load()?.owner @ Errors::NotOwner)]
+    pub owner: Signer<'info>,
+    pub share_token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -0,0 +1,166 @@
+use anchor_lang::Discriminator;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{TokenInterface, Mint, TokenAccount};
+use crate::error::{ErrorEarn, Errors};
+use crate::event::{EventEarnFlashBorrow};
+use crate::handlers::FLASH_REPAY_VAULT_ACCOUNT_INDEX;
+use crate::state::{EarnConfig, Protocol};
+use crate::state::vault_earn::VaultEarn;
+use crate::util::{seeds, transfer_token::transfer_token_with_signer};
+use crate::util::pause::{require_not_paused, PauseFlags};
+
+pub fn handle(ctx: Context<VaultEarnFlashBorrow>, amount: u64) -> Result<()> {
+    let repay_instruction_index = verify_next_ixs(&ctx, amount)?;
+    check_pause(&ctx)?;
+
+    let vault = &mut ctx.accounts.vault.load_mut()?;
+
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("amount: {:?} vault liquidity: {:?}", amount, ctx.accounts.vault_liquidity.amount);
+    msg!("repay instruction index: {:?}", repay_instruction_index);
+
+    require_gte!(ctx.accounts.vault_liquidity.amount, amount, ErrorEarn::InsufficientLiquidityInPool);
+
+    vault.flash_borrow(amount)?;
+
+    let vault_key = ctx.accounts.vault.key();
+    let seeds = &[
+        seeds::VAULT_EARN_AUTH,
+        vault_key.as_ref(),
+        &[ctx.bumps.vault_authority],
+    ];
+
+    let signer_seeds = &[&seeds[..]];
+
+    transfer_token_with_signer(
+        ctx.accounts.vault_liquidity.to_account_info(),
+        ctx.accounts.user_ata.to_account_info(),
+        ctx.accounts.vault_authority.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.token_mint.to_account_info(),
+        amount,
+        ctx.accounts.token_mint.decimals,
+        signer_seeds,
+    )?;
+
+    emit!(EventEarnFlashBorrow {
+        vault: ctx.accounts.vault.key(),
+        user: ctx.accounts.user.key(),
+        amount,
+        repay_instruction_index,
+    });
+
+    Ok(())
+}
+
+#[inline(never)]
+fn verify_next_ixs(ctx: &Context<VaultEarnFlashBorrow>, amount: u64) -> Result<u16> {
+    let ixs = ctx.accounts.instructions.to_account_info();
+
+    // make sure this isnt a cpi call
+    let current_index = load_current_index_checked(&ixs)? as usize;
+    let current_ix = load_instruction_at_checked(current_index, &ixs)?;
+    if current_ix.program_id != *ctx.program_id {
+        return Err(Errors::InvalidProgram.into());
+    }
+
+    // a later instruction of this program must repay the same amount to the same vault
+    let mut index = current_index + 1;
+    loop {
+        if let Ok(ix) = load_instruction_at_checked(index, &ixs) {
+            if ix.program_id == crate::id() {
+                let ix_discriminator: [u8; 8] = ix.data[0..8]
+                    .try_into()
+                    .map_err(|_| Errors::UnknownInstruction)?;
+
+                if ix_discriminator == crate::instruction::EarnVaultFlashBorrow::discriminator() {
+                    return Err(ErrorEarn::FlashLoanInProgress.into());
+                }
+
+                if ix_discriminator == crate::instruction::EarnVaultFlashRepay::discriminator() {
+                    let repay_vault = ix.accounts.get(FLASH_REPAY_VAULT_ACCOUNT_INDEX).map(|account| account.pubkey);
+                    let repay_amount: [u8; 8] = ix.data.get(8..16)
+                        .and_then(|data| data.try_into().ok())
+                        .ok_or(ErrorEarn::InvalidFlashRepay)?;
+
+                    if repay_vault == Some(ctx.accounts.vault.key()) {
+                        require_eq!(u64::from_le_bytes(repay_amount), amount, ErrorEarn::InvalidFlashRepay);
+                        break;
+                    }
+                }
+            }
+        } else {
+            return Err(ErrorEarn::MissingFlashRepay.into());
+        }
+
+        index += 1
+    }
+
+    Ok(index as u16)
+}
+
+#[inline(never)]
+fn check_pause(ctx: &Context<VaultEarnFlashBorrow>) -> Result<()> {
+    require_not_paused(&[
+        ctx.accounts.protocol.load()?.pause_flags,
+        ctx.accounts.earn_config.load()?.pause_flags,
+    ], PauseFlags::FLASH)
+}
+
+#[derive(Accounts)]
+pub struct VaultEarnFlashBorrow<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account()]
+    pub earn_config: AccountLoader<'info, EarnConfig>,
+    /// CHECK VAULT EARN AUTHORITY
+    #[account(
+        seeds = [seeds::VAULT_EARN_AUTH, vault.key().as_ref()],
+        bump,
+    )]
+    pub vault_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+        has_one = earn_config @ Errors::InvalidConfig,
+        has_one = token_program,
+        has_one = token_mint,
+    )]
+    pub vault: AccountLoader<'info, VaultEarn>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        associated_token::token_program = token_program,
+        associated_token::mint = token_mint,
+        associated_token::authority = vault_authority,
+    )]
+    pub vault_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::token_program = token_program,
+        associated_token::mint = token_mint,
+        associated_token::authority = user
+    )]
+    pub user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: check instructions account
+    #[account(address = sysvar::instructions::ID @Errors::InvalidAddress)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
\ No newline at end of file
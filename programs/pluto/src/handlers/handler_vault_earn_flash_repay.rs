@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_spl::token_interface::{TokenInterface, Mint, TokenAccount};
+use crate::error::{ErrorEarn, Errors};
+use crate::error::ErrorMath::MathOverflow;
+use crate::event::{EventEarnFlashRepay};
+use crate::state::{EarnConfig, Protocol};
+use crate::state::vault_earn::VaultEarn;
+use crate::util::{seeds, transfer_token::transfer_token};
+
+// Position of the vault in the flash repay accounts, read by the flash borrow introspection
+pub const FLASH_REPAY_VAULT_ACCOUNT_INDEX: usize = 3;
+
+pub fn handle(ctx: Context<VaultEarnFlashRepay>, amount: u64) -> Result<()> {
+    verify_ixs(&ctx)?;
+
+    let earn_config = &ctx.accounts.earn_config.load()?;
+    let vault = &mut ctx.accounts.vault.load_mut()?;
+
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("vault config address: {:?}", ctx.accounts.earn_config.key());
+
+    // Interest up to now is compounded before the fee lands on the index
+    vault.accrue_interest(earn_config)?;
+
+    let fee_amount = earn_config.flash_fee_amount(amount)?;
+
+    msg!("amount: {:?} flash_fee: {:?} fee_amount: {:?}", amount, earn_config.flash_fee, fee_amount);
+
+    transfer_token(
+        ctx.accounts.user_ata.to_account_info(),
+        ctx.accounts.vault_liquidity.to_account_info(),
+        ctx.accounts.user.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.token_mint.to_account_info(),
+        amount.checked_add(fee_amount).ok_or(MathOverflow)?,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    vault.flash_repay(amount, fee_amount)?;
+
+    msg!("vault index: {:?}", vault.index);
+
+    emit!(EventEarnFlashRepay {
+        vault: ctx.accounts.vault.key(),
+        user: ctx.accounts.user.key(),
+        amount,
+        flash_fee: earn_config.flash_fee,
+        fee_amount,
+        index: vault.index,
+    });
+
+    Ok(())
+}
+
+#[inline(never)]
+fn verify_ixs(ctx: &Context<VaultEarnFlashRepay>) -> Result<()> {
+    let ixs = ctx.accounts.instructions.to_account_info();
+
+    // make sure this isnt a cpi call
+    let current_index = load_current_index_checked(&ixs)? as usize;
+    let current_ix = load_instruction_at_checked(current_index, &ixs)?;
+    if current_ix.program_id != *ctx.program_id {
+        return Err(Errors::InvalidProgram.into());
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VaultEarnFlashRepay<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account()]
+    pub earn_config: AccountLoader<'info, EarnConfig>,
+    /// CHECK VAULT EARN AUTHORITY
+    #[account(
+        seeds = [seeds::VAULT_EARN_AUTH, vault.key().as_ref()],
+        bump,
+    )]
+    pub vault_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+        has_one = earn_config @ Errors::InvalidConfig,
+        has_one = token_program,
+        has_one = token_mint,
+    )]
+    pub vault: AccountLoader<'info, VaultEarn>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        associated_token::token_program = token_program,
+        associated_token::mint = token_mint,
+        associated_token::authority = vault_authority,
+    )]
+    pub vault_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::token_program = token_program,
+        associated_token::mint = token_mint,
+        associated_token::authority = user
+    )]
+    pub user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: check instructions account
+    #[account(address = sysvar::instructions::ID @Errors::InvalidAddress)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
\ No newline at end of file
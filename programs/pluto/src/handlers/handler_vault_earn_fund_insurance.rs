@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{TokenInterface, Mint, TokenAccount};
+use crate::error::Errors;
+use crate::event::{EventEarnInsuranceFunded};
+use crate::state::{Protocol};
+use crate::state::vault_earn::VaultEarn;
+use crate::util::{seeds, transfer_token::transfer_token};
+
+// Tops up the insurance fund of a vault, the tokens are not lent out and only cover bad debt
+pub fn handle(ctx: Context<VaultEarnFundInsurance>, amount: u64) -> Result<()> {
+    require!(amount > 0, Errors::InvalidAmountZero);
+
+    let vault = &mut ctx.accounts.vault.load_mut()?;
+
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("amount: {:?}", amount);
+
+    transfer_token(
+        ctx.accounts.user_ata.to_account_info(),
+        ctx.accounts.vault_liquidity.to_account_info(),
+        ctx.accounts.user.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.token_mint.to_account_info(),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    vault.fund_insurance(amount)?;
+
+    msg!("insurance_fund_amount: {:?}", vault.insurance_fund_amount);
+
+    emit!(EventEarnInsuranceFunded {
+        vault: ctx.accounts.vault.key(),
+        user: ctx.accounts.user.key(),
+        amount,
+        insurance_fund_amount: vault.insurance_fund_amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VaultEarnFundInsurance<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    /// CHECK VAULT EARN AUTHORITY
+    #[account(
+        seeds = [seeds::VAULT_EARN_AUTH, vault.key().as_ref()],
+        bump,
+    )]
+    pub vault_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+        has_one = token_program,
+        has_one = token_mint,
+    )]
+    pub vault: AccountLoader<'info, VaultEarn>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        associated_token::token_program = token_program,
+        associated_token::mint = token_mint,
+        associated_token::authority = vault_authority,
+    )]
+    pub vault_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::token_program = token_program,
+        associated_token::mint = token_mint,
+        associated_token::authority = user
+    )]
+    pub user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
\ No newline at end of file
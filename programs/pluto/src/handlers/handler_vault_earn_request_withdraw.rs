@@ -0,0 +1,296 @@
+use anchor_lang::{Accounts, system_program};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{TokenInterface, Mint, TokenAccount};
+use crate::error::{ErrorEarn, Errors};
+use crate::event::{EventEarnWithdrawQueued};
+use crate::state::{EarnConfig, InitWithdrawRequestParams, Lender, Protocol, Stats, WithdrawRequest};
+use crate::state::vault_earn::VaultEarn;
+use crate::util::{decimals, seeds};
+use crate::util::share_token::{burn_share_token, mint_share_token_with_signer};
+use crate::util::constant::{PERCENT_DECIMALS, UNIT_DECIMALS, INDEX_DECIMALS};
+use crate::util::pause::{require_not_paused, PauseFlags};
+
+pub fn handle(ctx: Context<VaultEarnRequestWithdraw>, unit: u64) -> Result<()> {
+    verify_ixs(&ctx)?;
+    check_pause(&ctx)?;
+    let lender_key = ctx.accounts.lender.as_ref().map_or(Pubkey::default(), |lender| lender.key());
+    let remaining_share = {
+        let earn_config = &ctx.accounts.earn_config.load()?;
+        let vault = &mut ctx.accounts.vault.load_mut()?;
+        let withdraw_request = &mut ctx.accounts.withdraw_request.load_init()?;
+
+        msg!("vault address: {:?}", ctx.accounts.vault.key());
+        msg!("vault config address: {:?}", ctx.accounts.earn_config.key());
+        msg!("lender address: {:?}", lender_key);
+
+        vault.accrue_interest(earn_config)?;
+
+        msg!("vault index: {:?}", vault.index);
+        msg!("vault share index: {:?}", vault.share_index);
+
+        let amount = vault.unit_to_amount(unit as u128)? as u64;
+        msg!("unit: {:?}, amount: {:?}", unit, amount);
+
+        require_gte!(amount as u128, earn_config.min_withdraw_limit as u128, ErrorEarn::WithdrawMinLimitNotMet);
+        require_gte!(earn_config.max_withdraw_limit as u128, amount as u128, ErrorEarn::WithdrawMaxLimitExceeded);
+
+        // Only withdrawals the free liquidity cannot cover wait in the queue
+        let free_amount = vault.withdraw_free_amount(ctx.accounts.vault_liquidity.amount);
+        msg!("free_amount: {:?} withdraw_reserved_amount: {:?}", free_amount, vault.withdraw_reserved_amount());
+
+        if free_amount >= amount {
+            return Err(ErrorEarn::WithdrawQueueNotNeeded.into());
+        }
+
+        // Shares are the only claim redeemed, units of the user lender not minted yet are migrated first
+        let minted_unit = match &ctx.accounts.lender {
+            Some(lender) => {
+                let lender = &mut lender.load_mut()?;
+
+                if !lender.is_initialized {
+                    return Err(ErrorEarn::InvalidFund.into());
+                }
+
+                if lender.owner != *ctx.accounts.user.key {
+                    return Err(ErrorEarn::InvalidOwner.into());
+                }
+
+                msg!("lender unit: {:?}", lender.unit);
+                msg!("lender index: {:?}", lender.index);
+
+                let minted_unit = lender.tokenize()?;
+                vault.mint_share(ctx.accounts.share_mint.supply, minted_unit, lender.index)?;
+                minted_unit
+            }
+            None => 0,
+        };
+        let share_balance = ctx.accounts.user_share_ata.amount.checked_add(minted_unit).ok_or(ErrorEarn::InsufficientShare)?;
+
+        msg!("minted_unit: {:?} share_balance: {:?}", minted_unit, share_balance);
+
+        if share_balance < unit {
+            return Err(ErrorEarn::InsufficientShare.into());
+        }
+
+        let vault_key = ctx.accounts.vault.key();
+        let seeds = &[
+            seeds::VAULT_EARN_AUTH,
+            vault_key.as_ref(),
+            &[ctx.bumps.vault_authority],
+        ];
+
+        let signer_seeds = &[&seeds[..]];
+
+        if minted_unit > 0 {
+            mint_share_token_with_signer(
+                ctx.accounts.share_mint.to_account_info(),
+                ctx.accounts.user_share_ata.to_account_info(),
+                ctx.accounts.vault_authority.to_account_info(),
+                ctx.accounts.share_token_program.to_account_info(),
+                minted_unit,
+                signer_seeds,
+            )?;
+        }
+
+        // Units leave the vault now, the amount owed is locked at the current index
+        burn_share_token(
+            ctx.accounts.share_mint.to_account_info(),
+            ctx.accounts.user_share_ata.to_account_info(),
+            ctx.accounts.user.to_account_info(),
+            ctx.accounts.share_token_program.to_account_info(),
+            unit,
+        )?;
+
+        let mut fee_amount = 0u64;
+
+        if earn_config.withdraw_fee > 0 {
+            let fee = decimals::mul_ceil(vault.token_decimal, amount as u128, vault.token_decimal, earn_config.withdraw_fee as u128, PERCENT_DECIMALS)?;
+            fee_amount = decimals::div_ceil(vault.token_decimal, fee, vault.token_decimal, 100, 0)? as u64;
+        }
+
+        // Shares carry no holder cost basis, the profit is taken from the average index the shares were minted at
+        let utilization_rate = vault.utilization_rate()?;
+        let protocol_fee_factor = vault.protocol_fee_factor(earn_config.protocol_fee, utilization_rate, vault.share_index, vault.index)?;
+
+        let protocol_fee_amount = (decimals::mul_ceil(
+            vault.token_decimal, unit as u128, UNIT_DECIMALS,
+            protocol_fee_factor, INDEX_DECIMALS
+        )? as u64).saturating_div(100);
+
+        msg!("fee_amount: {:?} protocol_fee_amount: {:?}", fee_amount, protocol_fee_amount);
+
+        require_gte!(amount, fee_amount.checked_add(protocol_fee_amount).ok_or(ErrorEarn::InsufficientFund)?, ErrorEarn::InsufficientFund);
+
+        vault.burn(earn_config, unit)?;
+
+        let (ticket, queued_before) = vault.enqueue_withdraw(amount)?;
+        vault.fill_withdraw_queue_from_liquidity(ctx.accounts.vault_liquidity.amount)?;
+
+        withdraw_request.init(InitWithdrawRequestParams {
+            bump: ctx.bumps.withdraw_request,
+            owner: ctx.accounts.user.key(),
+            vault: ctx.accounts.vault.key(),
+            lender: lender_key,
+            ticket,
+            unit,
+            index: vault.index,
+            amount,
+            fee_amount,
+            protocol_fee_amount,
+            queued_before,
+        })?;
+
+        let queued_ahead_amount = queued_before.saturating_sub(vault.withdraw_filled_amount);
+
+        msg!("ticket: {:?} queued_ahead_amount: {:?}", ticket, queued_ahead_amount);
+
+        emit!(EventEarnWithdrawQueued {
+            vault: ctx.accounts.vault.key(),
+            user: ctx.accounts.user.key(),
+            lender: lender_key,
+            withdraw_request: ctx.accounts.withdraw_request.key(),
+            ticket,
+            unit,
+            index: vault.index,
+            amount,
+            fee_amount,
+            protocol_fee_amount,
+            queued_ahead_amount,
+            unit_supply: vault.unit_supply,
+        });
+
+        share_balance.saturating_sub(unit)
+    };
+
+    // The lender only holds migrated units, it is closed once the user redeemed all of the shares
+    if let Some(lender) = &ctx.accounts.lender {
+        if remaining_share == 0 {
+            msg!("lender closed");
+            close_lender(&lender.to_account_info(), &ctx.accounts.user.to_account_info())?;
+
+            let stats = &mut ctx.accounts.earn_stats.load_mut()?;
+            stats.remove_user()?;
+        }
+    }
+
+    Ok(())
+}
+
+fn verify_ixs(ctx: &Context<VaultEarnRequestWithdraw>) -> Result<()> {
+    let ixs = ctx.accounts.instructions.to_account_info();
+
+    // make sure this isnt a cpi call
+    let current_index = load_current_index_checked(&ixs)? as usize;
+    let current_ix = load_instruction_at_checked(current_index, &ixs)?;
+    if current_ix.program_id != *ctx.program_id {
+        return Err(Errors::InvalidProgram.into());
+    }
+
+    Ok(())
+}
+
+fn check_pause(ctx: &Context<VaultEarnRequestWithdraw>) -> Result<()> {
+    require_not_paused(&[
+        ctx.accounts.protocol.load()?.pause_flags,
+        ctx.accounts.earn_config.load()?.pause_flags,
+    ], PauseFlags::WITHDRAW)
+}
+
+fn close_lender<'info>(lender: &AccountInfo<'info>, user: &AccountInfo<'info>) -> Result<()> {
+    let dest_lamports = user.lamports();
+    let close_lamports = lender.lamports();
+
+    **lender.try_borrow_mut_lamports()? = 0;
+    **user.try_borrow_mut_lamports()? = dest_lamports.checked_add(close_lamports).unwrap();
+
+    lender.assign(&system_program::ID);
+    lender.realloc(0, false)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VaultEarnRequestWithdraw<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account()]
+    pub earn_config: AccountLoader<'info, EarnConfig>,
+    /// CHECK VAULT EARN AUTHORITY
+    #[account(
+        seeds = [seeds::VAULT_EARN_AUTH, vault.key().as_ref()],
+        bump,
+    )]
+    pub vault_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+        has_one = earn_config @ Errors::InvalidConfig,
+        has_one = earn_stats,
+        has_one = token_program,
+        has_one = token_mint,
+        has_one = share_mint,
+    )]
+    pub vault: AccountLoader<'info, VaultEarn>,
+    #[account(mut)]
+    pub earn_stats: AccountLoader<'info, Stats>,
+
+    // Only needed to migrate units not minted as shares yet
+    #[account(
+        mut,
+        seeds = [seeds::LENDER, vault.key().as_ref(), token_mint.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub lender: Option<AccountLoader<'info, Lender>>,
+
+    #[account(
+        init,
+        seeds = [seeds::WITHDRAW_REQUEST, vault.key().as_ref(), &vault.load()?.withdraw_queue_tail.to_le_bytes()],
+        bump,
+        payer = user,
+        space = WithdrawRequest::INIT_SPACE+(1*8),
+    )]
+    pub withdraw_request: AccountLoader<'info, WithdrawRequest>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        associated_token::token_program = token_program,
+        associated_token::mint = token_mint,
+        associated_token::authority = vault_authority,
+    )]
+    pub vault_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        mint::token_program = share_token_program,
+    )]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::token_program = share_token_program,
+        associated_token::mint = share_mint,
+        associated_token::authority = user
+    )]
+    pub user_share_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: check instructions account
+    #[account(address = sysvar::instructions::ID @Errors::InvalidAddress)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub share_token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
\ No newline at end of file
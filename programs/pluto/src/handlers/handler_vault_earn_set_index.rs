@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::error::Errors;
+use crate::event::EventVaultEarnSetIndex;
+use crate::state::{EarnConfig, Protocol, VaultEarn};
+use crate::util::pause::{require_not_paused, PauseFlags};
+
+pub fn handle(ctx: Context<VaultEarnSetIndex>, index: u128, apy: u32) -> Result<()> {
+    require_not_paused(&[
+        ctx.accounts.protocol.load()?.pause_flags,
+        ctx.accounts.earn_config.load()?.pause_flags,
+    ], PauseFlags::INDEX_UPDATE)?;
+
+    let vault = &mut ctx.accounts.vault.load_mut()?;
+    let old_index = vault.index;
+    vault.set_index(index, apy)?;
+
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("old index: {:?} index: {:?} apy: {:?}", old_index, index, apy);
+
+    emit!(EventVaultEarnSetIndex{
+        vault: ctx.accounts.vault.key(),
+        indexer: ctx.accounts.indexer.key(),
+        old_index,
+        index,
+        apy,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VaultEarnSetIndex<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account(
+        has_one = protocol @ Errors::InvalidProtocol,
+    )]
+    pub earn_config: AccountLoader<'info, EarnConfig>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+        has_one = earn_config @ Errors::InvalidConfig,
+    )]
+    pub vault: AccountLoader<'info, VaultEarn>,
+
+    #[account(address = earn_config.load()?.indexer @ Errors::NotIndexer)]
+    pub indexer: Signer<'info>,
+}
\ No newline at end of file
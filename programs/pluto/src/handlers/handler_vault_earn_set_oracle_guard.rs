@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::error::Errors;
+use crate::event::{EventVaultEarnSetOracleGuard};
+use crate::state::{Protocol, VaultEarn};
+
+pub fn handle(ctx: Context<VaultEarnSetOracleGuard>, max_age: u64, max_confidence_rate: u32, max_ema_deviation_rate: u32) -> Result<()> {
+    let vault = &mut ctx.accounts.vault.load_mut()?;
+    let old_max_age = vault.oracle_guard.max_age;
+    let old_max_confidence_rate = vault.oracle_guard.max_confidence_rate;
+    let old_max_ema_deviation_rate = vault.oracle_guard.max_ema_deviation_rate;
+
+    vault.oracle_guard.set(max_age, max_confidence_rate, max_ema_deviation_rate)?;
+
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("old max age: {:?}", old_max_age);
+    msg!("new max age: {:?}", max_age);
+    msg!("old max confidence rate: {:?}", old_max_confidence_rate);
+    msg!("new max confidence rate: {:?}", max_confidence_rate);
+    msg!("old max ema deviation rate: {:?}", old_max_ema_deviation_rate);
+    msg!("new max ema deviation rate: {:?}", max_ema_deviation_rate);
+
+    emit!(EventVaultEarnSetOracleGuard {
+        vault: ctx.accounts.vault.key(),
+        old_max_age,
+        new_max_age: max_age,
+        old_max_confidence_rate,
+        new_max_confidence_rate: max_confidence_rate,
+        old_max_ema_deviation_rate,
+        new_max_ema_deviation_rate: max_ema_deviation_rate,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VaultEarnSetOracleGuard<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+    )]
+    pub vault: AccountLoader<'info, VaultEarn>,
+
+    #[account(mut, address = protocol.load()?.owner @ Errors::NotOwner)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{TokenInterface, Mint, TokenAccount};
+use crate::error::{ErrorEarn, Errors};
+use crate::event::{EventEarnLenderTokenized};
+use crate::state::{Lender, Protocol};
+use crate::state::vault_earn::VaultEarn;
+use crate::util::{seeds, share_token::mint_share_token_with_signer};
+
+pub fn handle(ctx: Context<VaultEarnTokenizeLender>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault.load_mut()?;
+    let lender = &mut ctx.accounts.lender.load_mut()?;
+
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("lender address: {:?}", ctx.accounts.lender.key());
+
+    if !lender.is_initialized {
+        return Err(ErrorEarn::InvalidFund.into());
+    }
+
+    if lender.owner != *ctx.accounts.user.key {
+        return Err(ErrorEarn::InvalidOwner.into());
+    }
+
+    // Mint the units deposited before the share mint existed, or not yet minted
+    let minted_unit = lender.tokenize()?;
+    vault.mint_share(ctx.accounts.share_mint.supply, minted_unit, lender.index)?;
+
+    msg!("lender unit: {:?} minted unit: {:?}", lender.unit, minted_unit);
+
+    if minted_unit > 0 {
+        let vault_key = ctx.accounts.vault.key();
+        let seeds = &[
+            seeds::VAULT_EARN_AUTH,
+            vault_key.as_ref(),
+            &[ctx.bumps.vault_authority],
+        ];
+
+        let signer_seeds = &[&seeds[..]];
+
+        mint_share_token_with_signer(
+            ctx.accounts.share_mint.to_account_info(),
+            ctx.accounts.user_share_ata.to_account_info(),
+            ctx.accounts.vault_authority.to_account_info(),
+            ctx.accounts.share_token_program.to_account_info(),
+            minted_unit,
+            signer_seeds,
+        )?;
+    }
+
+    emit!(EventEarnLenderTokenized {
+        vault: ctx.accounts.vault.key(),
+        user: ctx.accounts.user.key(),
+        lender: ctx.accounts.lender.key(),
+        share_mint: ctx.accounts.share_mint.key(),
+        unit: lender.unit,
+        share_unit: lender.share_unit,
+        minted_unit,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VaultEarnTokenizeLender<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    /// CHECK VAULT EARN AUTHORITY
+    #[account(
+        seeds = [seeds::VAULT_EARN_AUTH, vault.key().as_ref()],
+        bump,
+    )]
+    pub vault_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+        has_one = share_mint,
+        has_one = token_program,
+        has_one = token_mint,
+    )]
+    pub vault: AccountLoader<'info, VaultEarn>,
+
+    #[account(
+        mut,
+        seeds = [seeds::LENDER, vault.key().as_ref(), token_mint.key().as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub lender: AccountLoader<'info, Lender>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        mint::token_program = share_token_program,
+    )]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::token_program = share_token_program,
+        associated_token::mint = share_mint,
+        associated_token::authority = user
+    )]
+    pub user_share_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mint::token_program = token_program,
+    )]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub share_token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
\ No newline at end of file
@@ -3,6 +3,7 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::sysvar;
 use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
 use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::Token2022;
 use anchor_spl::token_interface::{close_account, CloseAccount, TokenInterface, Mint, TokenAccount};
 use crate::error::{ErrorEarn, Errors};
 use crate::event::{EventEarnWithdraw, EventEarnWithdrawn};
@@ -10,29 +11,31 @@ use crate::handlers::VaultEarnDeposit;
 use crate::state::{EarnConfig, Lender, Protocol, Stats};
 use crate::state::vault_earn::VaultEarn;
 use crate::util::{decimals, seeds, transfer_token::{transfer_token, transfer_token_with_signer}};
+use crate::util::share_token::{burn_share_token, mint_share_token_with_signer};
 use crate::util::constant::{PERCENT_DECIMALS, UNIT_DECIMALS, INDEX_DECIMALS};
+use crate::util::pause::{require_not_paused, PauseFlags};
 
 pub fn handle(ctx: Context<VaultEarnWithdraw>, unit: u64, min_output_amount: u64) -> Result<()> {
     verify_ixs(&ctx)?;
-    check_freeze(&ctx)?;
-    {
+    check_pause(&ctx)?;
+    let lender_key = ctx.accounts.lender.as_ref().map_or(Pubkey::default(), |lender| lender.key());
+    let remaining_share = {
         let fee_vault = &ctx.accounts.earn_fee_vault;
         let earn_config = &ctx.accounts.earn_config.load()?;
         let vault = &mut ctx.accounts.vault.load_mut()?;
-        let lender = &mut ctx.accounts.lender.load_mut()?;
 
         msg!("vault address: {:?}", ctx.accounts.vault.key());
         msg!("vault config address: {:?}", ctx.accounts.earn_config.key());
-        msg!("lender address: {:?}", ctx.accounts.lender.key());
+        msg!("lender address: {:?}", lender_key);
+
+        vault.accrue_interest(earn_config)?;
 
         msg!("vault index: {:?}", vault.index);
+        msg!("vault share index: {:?}", vault.share_index);
         msg!("min_withdraw_limit: {:?}", earn_config.min_withdraw_limit);
         msg!("max_withdraw_limit: {:?}", earn_config.max_withdraw_limit);
         msg!("withdraw_fee: {:?}", earn_config.withdraw_fee);
 
-        msg!("lender unit: {:?}", lender.unit);
-        msg!("lender index: {:?}", lender.index);
-
         let amount = vault.unit_to_amount(unit as u128)? as u64;
         msg!("unit: {:?}, min_output_amount: {:?}, amount: {:?}", unit, min_output_amount, amount);
 
@@ -41,35 +44,47 @@ pub fn handle(ctx: Context<VaultEarnWithdraw>, unit: u64, min_output_amount: u64
 
         let vault_liquidity = &ctx.accounts.vault_liquidity;
 
-        let user = &mut ctx.accounts.user;
         let user_ata = &ctx.accounts.user_ata;
 
-        if !lender.is_initialized {
-            return Err(ErrorEarn::InvalidFund.into());
-        }
+        // Shares are the only claim redeemed, units of the user lender not minted yet are migrated first
+        let minted_unit = match &ctx.accounts.lender {
+            Some(lender) => {
+                let lender = &mut lender.load_mut()?;
 
-        if lender.owner != *ctx.accounts.user.key {
-            return Err(ErrorEarn::InvalidOwner.into());
-        }
+                if !lender.is_initialized {
+                    return Err(ErrorEarn::InvalidFund.into());
+                }
 
-        if lender.unit < unit {
-            return Err(ErrorEarn::InsufficientFund.into());
-        }
+                if lender.owner != *ctx.accounts.user.key {
+                    return Err(ErrorEarn::InvalidOwner.into());
+                }
 
-        if vault_liquidity.amount < min_output_amount {
-            return Err(ErrorEarn::InsufficientLiquidityInPool.into());
+                msg!("lender unit: {:?}", lender.unit);
+                msg!("lender index: {:?}", lender.index);
+
+                let minted_unit = lender.tokenize()?;
+                vault.mint_share(ctx.accounts.share_mint.supply, minted_unit, lender.index)?;
+                minted_unit
+            }
+            None => 0,
+        };
+        let share_balance = ctx.accounts.user_share_ata.amount.checked_add(minted_unit).ok_or(ErrorEarn::InsufficientShare)?;
+
+        msg!("minted_unit: {:?} share_balance: {:?}", minted_unit, share_balance);
+
+        if share_balance < unit {
+            return Err(ErrorEarn::InsufficientShare.into());
         }
 
-        if lender.valuation_amount(vault.index, vault.token_decimal)? < min_output_amount {
-            return Err(ErrorEarn::InsufficientFund.into());
+        // Liquidity owed to queued withdrawals and the insurance fund is not available here, short withdrawals go through the queue
+        if vault.withdraw_free_amount(vault_liquidity.amount) < amount {
+            return Err(ErrorEarn::InsufficientLiquidityInPool.into());
         }
 
         if amount < min_output_amount {
             return Err(ErrorEarn::OutputTooSmall.into());
         }
 
-        lender.withdraw(amount, unit, vault.index)?;
-
         let vault_key = ctx.accounts.vault.key();
         let seeds = &[
             seeds::VAULT_EARN_AUTH,
@@ -79,18 +94,38 @@ pub fn handle(ctx: Context<VaultEarnWithdraw>, unit: u64, min_output_amount: u64
 
         let signer_seeds = &[&seeds[..]];
 
+        if minted_unit > 0 {
+            mint_share_token_with_signer(
+                ctx.accounts.share_mint.to_account_info(),
+                ctx.accounts.user_share_ata.to_account_info(),
+                ctx.accounts.vault_authority.to_account_info(),
+                ctx.accounts.share_token_program.to_account_info(),
+                minted_unit,
+                signer_seeds,
+            )?;
+        }
+
+        burn_share_token(
+            ctx.accounts.share_mint.to_account_info(),
+            ctx.accounts.user_share_ata.to_account_info(),
+            ctx.accounts.user.to_account_info(),
+            ctx.accounts.share_token_program.to_account_info(),
+            unit,
+        )?;
+
         let mut fee_amount = 0u64;
 
         if earn_config.withdraw_fee > 0 {
-            let fee = decimals::mul_ceil(vault.token_decimal, lender.pending_withdraw_amount as u128, vault.token_decimal, earn_config.withdraw_fee as u128, PERCENT_DECIMALS)?;
+            let fee = decimals::mul_ceil(vault.token_decimal, amount as u128, vault.token_decimal, earn_config.withdraw_fee as u128, PERCENT_DECIMALS)?;
             fee_amount = decimals::div_ceil(vault.token_decimal, fee, vault.token_decimal, 100, 0)? as u64;
         }
 
         msg!("fee_amount: {:?}", fee_amount);
 
+        // Shares carry no holder cost basis, the profit is taken from the average index the shares were minted at
         let utilization_rate = vault.utilization_rate()?;
         msg!("utilization_rate: {:?}", utilization_rate);
-        let protocol_fee_factor = vault.protocol_fee_factor(earn_config.protocol_fee, utilization_rate, lender.index, vault.index)?;
+        let protocol_fee_factor = vault.protocol_fee_factor(earn_config.protocol_fee, utilization_rate, vault.share_index, vault.index)?;
         msg!("protocol_fee_factor: {:?}", protocol_fee_factor);
 
         let protocol_fee_amount = (decimals::mul_ceil(
@@ -128,22 +163,22 @@ pub fn handle(ctx: Context<VaultEarnWithdraw>, unit: u64, min_output_amount: u64
             signer_seeds,
         )?;
 
-        msg!("confirm_withdraw");
-        lender.confirm_withdraw()?;
         msg!("burn unit");
         vault.burn(earn_config, unit)?;
 
+        let remaining_share = share_balance.saturating_sub(unit);
+
         emit!(EventEarnWithdraw{
             vault: ctx.accounts.vault.key(),
             user: ctx.accounts.user.key(),
-            lender: ctx.accounts.lender.key(),
+            lender: lender_key,
             token_mint: ctx.accounts.token_mint.key(),
             amount,
-            unit: lender.unit,
-            index: lender.index,
-            pending_amount: lender.pending_withdraw_amount,
-            pending_unit: lender.pending_withdraw_unit,
-            pending_index: lender.pending_withdraw_index,
+            unit: remaining_share,
+            index: vault.share_index,
+            pending_amount: 0,
+            pending_unit: 0,
+            pending_index: 0,
             unit_supply: vault.unit_supply,
             vault_index: vault.index,
             fee_amount,
@@ -152,14 +187,14 @@ pub fn handle(ctx: Context<VaultEarnWithdraw>, unit: u64, min_output_amount: u64
         emit!(EventEarnWithdrawn{
             vault: ctx.accounts.vault.key(),
             user: ctx.accounts.user.key(),
-            lender: ctx.accounts.lender.key(),
+            lender: lender_key,
             token_mint: ctx.accounts.token_mint.key(),
             amount,
-            unit: lender.unit,
-            index: lender.index,
-            pending_amount: lender.pending_withdraw_amount,
-            pending_unit: lender.pending_withdraw_unit,
-            pending_index: lender.pending_withdraw_index,
+            unit: remaining_share,
+            index: vault.share_index,
+            pending_amount: 0,
+            pending_unit: 0,
+            pending_index: 0,
             unit_supply: vault.unit_supply,
             vault_index: vault.index,
             protocol_fee: earn_config.protocol_fee,
@@ -168,26 +203,19 @@ pub fn handle(ctx: Context<VaultEarnWithdraw>, unit: u64, min_output_amount: u64
             fee_amount,
             padding: [0;32],
         });
-    }
 
-    let mut need_close = false;
+        remaining_share
+    };
 
-    if (UNIT_DECIMALS as i8) > (ctx.accounts.vault.load_mut()?.token_decimal as i8) {
-        if ctx.accounts.lender.load_mut()?.unit <= 10u64.pow((UNIT_DECIMALS - ctx.accounts.vault.load()?.token_decimal) as u32) {
-            need_close = true;
-        }
-    } else {
-        if ctx.accounts.lender.load_mut()?.unit == 0 {
-            need_close = true;
-        }
-    }
+    // The lender only holds migrated units, it is closed once the user redeemed all of the shares
+    if let Some(lender) = &ctx.accounts.lender {
+        if remaining_share == 0 {
+            msg!("lender closed");
+            close_lender(&lender.to_account_info(), &ctx.accounts.user.to_account_info())?;
 
-    if need_close {
-        msg!("lender closed");
-        close_lender(&ctx)?;
-
-        let stats = &mut ctx.accounts.earn_stats.load_mut()?;
-        stats.remove_user()?;
+            let stats = &mut ctx.accounts.earn_stats.load_mut()?;
+            stats.remove_user()?;
+        }
     }
 
     Ok(())
@@ -206,21 +234,22 @@ fn verify_ixs(ctx: &Context<VaultEarnWithdraw>) -> Result<()> {
     Ok(())
 }
 
-fn check_freeze(ctx: &Context<VaultEarnWithdraw>) -> Result<()> {
-    require!(!(ctx.accounts.protocol.load()?.freeze && !ctx.accounts.protocol.load()?.freeze_earn), ErrorEarn::VaultFrozen);
-    require!(!ctx.accounts.earn_config.load()?.freeze, ErrorEarn::VaultFrozen);
-    Ok(())
+fn check_pause(ctx: &Context<VaultEarnWithdraw>) -> Result<()> {
+    require_not_paused(&[
+        ctx.accounts.protocol.load()?.pause_flags,
+        ctx.accounts.earn_config.load()?.pause_flags,
+    ], PauseFlags::WITHDRAW)
 }
 
-fn close_lender(ctx: &Context<VaultEarnWithdraw>) -> Result<()> {
-    let dest_lamports = ctx.accounts.user.to_account_info().lamports();
-    let close_lamports = ctx.accounts.lender.to_account_info().lamports();
+fn close_lender<'info>(lender: &AccountInfo<'info>, user: &AccountInfo<'info>) -> Result<()> {
+    let dest_lamports = user.lamports();
+    let close_lamports = lender.lamports();
 
-    **ctx.accounts.lender.to_account_info().try_borrow_mut_lamports()? = 0;
-    **ctx.accounts.user.to_account_info().try_borrow_mut_lamports()? = dest_lamports.checked_add(close_lamports).unwrap();
+    **lender.try_borrow_mut_lamports()? = 0;
+    **user.try_borrow_mut_lamports()? = dest_lamports.checked_add(close_lamports).unwrap();
 
-    ctx.accounts.lender.to_account_info().assign(&system_program::ID);
-    ctx.accounts.lender.to_account_info().realloc(0, false)?;
+    lender.assign(&system_program::ID);
+    lender.realloc(0, false)?;
 
     Ok(())
 }
@@ -249,17 +278,19 @@ pub struct VaultEarnWithdraw<'info> {
         has_one = earn_stats,
         has_one = token_program,
         has_one = token_mint,
+        has_one = share_mint,
     )]
     pub vault: AccountLoader<'info, VaultEarn>,
     #[account(mut)]
     pub earn_stats: AccountLoader<'info, Stats>,
 
+    // Only needed to migrate units not minted as shares yet
     #[account(
         mut,
         seeds = [seeds::LENDER, vault.key().as_ref(), token_mint.key().as_ref(), user.key().as_ref()],
         bump,
     )]
-    pub lender: AccountLoader<'info, Lender>,
+    pub lender: Option<AccountLoader<'info, Lender>>,
 
     #[account(mut)]
     pub user: Signer<'info>,
@@ -286,11 +317,26 @@ pub struct VaultEarnWithdraw<'info> {
     )]
     pub token_mint: Box<InterfaceAccount<'info, Mint>>,
 
+    #[account(
+        mut,
+        mint::token_program = share_token_program,
+    )]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::token_program = share_token_program,
+        associated_token::mint = share_mint,
+        associated_token::authority = user
+    )]
+    pub user_share_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
     /// CHECK: check instructions account
     #[account(address = sysvar::instructions::ID @Errors::InvalidAddress)]
     pub instructions: UncheckedAccount<'info>,
 
     pub token_program: Interface<'info, TokenInterface>,
+    pub share_token_program: Program<'info, Token2022>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
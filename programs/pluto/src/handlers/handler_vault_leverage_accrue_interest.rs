@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::error::{ErrorEarn, Errors};
+use crate::event::EventVaultLeverageAccruedInterest;
+use crate::state::{EarnConfig, LeverageConfig, Protocol, VaultEarn, VaultLeverage};
+use crate::util::pause::{require_not_paused, PauseFlags};
+
+pub fn handle(ctx: Context<VaultLeverageAccrueInterest>) -> Result<()> {
+    require_not_paused(&[
+        ctx.accounts.protocol.load()?.pause_flags,
+        ctx.accounts.leverage_config.load()?.pause_flags,
+    ], PauseFlags::INDEX_UPDATE)?;
+
+    let earn_config = &ctx.accounts.earn_config.load()?;
+    let borrow_vault = &mut ctx.accounts.borrow_vault.load_mut()?;
+    let vault = &mut ctx.accounts.vault.load_mut()?;
+
+    require!(earn_config.has_rate_model(), ErrorEarn::RateModelNotConfigured);
+
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("borrow_vault address: {:?}", ctx.accounts.borrow_vault.key());
+
+    // Lender side first so both indexes use the same utilization
+    borrow_vault.accrue_interest(earn_config)?;
+
+    let old_borrowing_index = vault.borrowing_index;
+    let utilization_rate = borrow_vault.utilization_rate()?;
+    let borrow_rate = earn_config.borrow_rate(utilization_rate)?;
+
+    vault.accrue_interest(borrow_rate)?;
+
+    msg!("utilization_rate: {:?} borrow_rate: {:?}", utilization_rate, borrow_rate);
+    msg!("old borrowing index: {:?} borrowing index: {:?}", old_borrowing_index, vault.borrowing_index);
+
+    emit!(EventVaultLeverageAccruedInterest{
+        vault: ctx.accounts.vault.key(),
+        borrow_vault: ctx.accounts.borrow_vault.key(),
+        utilization_rate,
+        borrow_rate,
+        old_borrowing_index,
+        borrowing_index: vault.borrowing_index,
+        borrow_vault_index: borrow_vault.index,
+        last_index_updated: vault.last_index_updated,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VaultLeverageAccrueInterest<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account(
+        has_one = protocol @ Errors::InvalidProtocol,
+    )]
+    pub leverage_config: AccountLoader<'info, LeverageConfig>,
+    #[account(
+        has_one = protocol @ Errors::InvalidProtocol,
+    )]
+    pub earn_config: AccountLoader<'info, EarnConfig>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+        has_one = earn_config @ Errors::InvalidConfig,
+    )]
+    pub borrow_vault: AccountLoader<'info, VaultEarn>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+        has_one = leverage_config @ Errors::InvalidConfig,
+        has_one = borrow_vault @ Errors::InvalidBorrowingConfig,
+    )]
+    pub vault: AccountLoader<'info, VaultLeverage>,
+
+    pub user: Signer<'info>,
+}
\ No newline at end of file
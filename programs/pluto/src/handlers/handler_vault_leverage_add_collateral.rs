@@ -0,0 +1,179 @@
+use anchor_lang::Discriminator;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_spl::token_interface::{TokenInterface, Mint, TokenAccount};
+use crate::error::{ErrorLeverage, Errors};
+use crate::error::ErrorMath::MathOverflow;
+use crate::event::{EventLeverageAddCollateral};
+use crate::state::{LeverageConfig, Obligation, Protocol, VaultLeverage};
+use crate::util::{oracle, seeds};
+use crate::util::action::LeverageAction;
+use crate::util::constant::{PERCENT_MAX};
+use crate::util::pause::{require_not_paused, PauseFlags};
+
+pub fn handle(ctx: Context<VaultLeverageAddCollateral>, number: u8, amount: u64) -> Result<()> {
+    verify_next_ixs(&ctx)?;
+    check_pause(&ctx)?;
+
+    let config = &ctx.accounts.leverage_config.load()?;
+    let vault = &ctx.accounts.vault.load()?;
+    let obligation = &mut Obligation::load_with_positions_mut(&ctx.accounts.obligation)?;
+
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("obligation address: {:?}", ctx.accounts.obligation.key());
+
+    require_gt!(amount, 0, ErrorLeverage::InvalidAmount);
+    require_gte!(amount, config.min_leverage_limit, ErrorLeverage::InvalidAmount);
+    require_gte!(config.max_leverage_limit, amount, ErrorLeverage::InvalidAmount);
+
+    let position = obligation.position_mut(number)?;
+
+    require_gt!(position.unit, 0, ErrorLeverage::NoPositionFound);
+    position.require_holder(ctx.accounts.owner.key(), ctx.accounts.user.key(), &ctx.accounts.user_nft_account)?;
+
+    // Position must not be in the middle of another process
+    position.halt_on_leveraging()?;
+    position.halt_on_deleveraging()?;
+
+    let (token_collateral_price, token_collateral_price_exponent, token_collateral_price_sources) = vault.token_collateral_price(&ctx.accounts.token_collateral_price_oracle, ctx.remaining_accounts)?;
+    let (native_collateral_price, native_collateral_price_exponent, native_collateral_price_sources) = vault.native_collateral_price(&ctx.accounts.native_collateral_price_oracle, ctx.remaining_accounts)?;
+
+    // Expected native collateral out of the swap, minus the allowed slippage
+    let fund_value = oracle::value(amount, vault.token_collateral_token_decimal, token_collateral_price, token_collateral_price_exponent)?;
+    let expected_output = oracle::amount(fund_value, vault.native_collateral_token_decimal, native_collateral_price, native_collateral_price_exponent)?;
+    let min_native_collateral_output = (expected_output as u128)
+        .checked_mul(PERCENT_MAX.checked_sub(config.slippage_rate).ok_or(MathOverflow)? as u128).ok_or(MathOverflow)?
+        .checked_div(PERCENT_MAX as u128).ok_or(MathOverflow)? as u64;
+
+    require_gt!(min_native_collateral_output, 0, ErrorLeverage::InvalidAmount);
+
+    msg!("fund_amount: {:?} min_native_collateral_output: {:?}", amount, min_native_collateral_output);
+
+    position.set_action(LeverageAction::AddCollateral)?;
+    position.set_config(config)?;
+    position.set_oracle(
+        vault.token_collateral_price_oracle, vault.token_collateral_price_feed, token_collateral_price, token_collateral_price_exponent,
+        vault.native_collateral_price_oracle, vault.native_collateral_price_feed, native_collateral_price, native_collateral_price_exponent,
+    )?;
+    position.add_collateral(amount, min_native_collateral_output)?;
+
+    emit!(EventLeverageAddCollateral {
+        vault: ctx.accounts.vault.key(),
+        user: ctx.accounts.user.key(),
+        obligation: ctx.accounts.obligation.key(),
+        position_id: position.id,
+        position_number: number,
+        token_collateral_price,
+        token_collateral_price_exponent,
+        native_collateral_price,
+        native_collateral_price_exponent,
+        token_collateral_price_sources,
+        native_collateral_price_sources,
+        fund_amount: amount,
+        min_native_collateral_output,
+    });
+
+    Ok(())
+}
+
+#[inline(never)]
+fn verify_next_ixs(ctx: &Context<VaultLeverageAddCollateral>) -> Result<()> {
+    let ixs = ctx.accounts.instructions.to_account_info();
+
+    // make sure this isnt a cpi call
+    let current_index = load_current_index_checked(&ixs)? as usize;
+    let current_ix = load_instruction_at_checked(current_index, &ixs)?;
+    if current_ix.program_id != *ctx.program_id {
+        return Err(Errors::InvalidProgram.into());
+    }
+
+    // next instruction of this program must be the confiscate taking the swap output
+    let mut index = current_index + 1;
+    loop {
+        if let Ok(ix) = load_instruction_at_checked(index, &ixs) {
+            if ix.program_id == crate::id() {
+                let ix_discriminator: [u8; 8] = ix.data[0..8]
+                    .try_into()
+                    .map_err(|_| Errors::UnknownInstruction)?;
+
+                if ix_discriminator == crate::instruction::LeverageVaultConfiscate::discriminator() {
+                    break;
+                } else {
+                    return Err(ErrorLeverage::NextInstructionMustBeConfiscate.into());
+                }
+            }
+        } else {
+            return Err(ErrorLeverage::MissingConfiscate.into());
+        }
+
+        index += 1
+    }
+
+    Ok(())
+}
+
+#[inline(never)]
+fn check_pause(ctx: &Context<VaultLeverageAddCollateral>) -> Result<()> {
+    require_not_paused(&[
+        ctx.accounts.protocol.load()?.pause_flags,
+        ctx.accounts.leverage_config.load()?.pause_flags,
+    ], PauseFlags::DEPOSIT)
+}
+
+#[derive(Accounts)]
+pub struct VaultLeverageAddCollateral<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account()]
+    pub leverage_config: AccountLoader<'info, LeverageConfig>,
+    #[account(
+        has_one = protocol @ Errors::InvalidProtocol,
+        has_one = leverage_config @ Errors::InvalidConfig,
+        has_one = token_collateral_token_program,
+        has_one = token_collateral_token_mint,
+        has_one = native_collateral_token_program,
+        has_one = native_collateral_token_mint,
+    )]
+    pub vault: AccountLoader<'info, VaultLeverage>,
+
+    #[account(
+        mut,
+        seeds = [seeds::OBLIGATION, vault.key().as_ref(), token_collateral_token_mint.key().as_ref(), native_collateral_token_mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub obligation: AccountLoader<'info, Obligation>,
+    /// CHECK: owner of the obligation, validated by the obligation seeds
+    pub owner: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub user_nft_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: price oracle of the vault, read according to its oracle source
+    #[account(
+        address = vault.load()?.token_collateral_price_oracle @ Errors::InvalidPriceOracle,
+    )]
+    pub token_collateral_price_oracle: UncheckedAccount<'info>,
+    /// CHECK: price oracle of the vault, read according to its oracle source
+    #[account(
+        address = vault.load()?.native_collateral_price_oracle @ Errors::InvalidPriceOracle,
+    )]
+    pub native_collateral_price_oracle: UncheckedAccount<'info>,
+
+    #[account(
+        mint::token_program = token_collateral_token_program,
+    )]
+    pub token_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mint::token_program = native_collateral_token_program,
+    )]
+    pub native_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: check instructions account
+    #[account(address = sysvar::instructions::ID @Errors::InvalidAddress)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_collateral_token_program: Interface<'info, TokenInterface>,
+    pub native_collateral_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
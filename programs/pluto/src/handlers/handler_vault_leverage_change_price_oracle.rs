@@ -1,40 +1,75 @@
 use anchor_lang::prelude::*;
-use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 use crate::error::Errors;
 use crate::event::{EventVaultLeverageChangedPriceOracle};
 use crate::state::{Protocol, VaultLeverage};
+use crate::util::oracle;
+use crate::util::oracle_source::OracleSource;
 
-pub fn handle(ctx: Context<VaultLeverageChangePriceOracle>, token_collateral_price_feed: [u8; 64], native_collateral_price_feed: [u8; 64]) -> Result<()> {
+pub fn handle(
+    ctx: Context<VaultLeverageChangePriceOracle>,
+    token_collateral_price_source: OracleSource,
+    token_collateral_price_feed: [u8; 64],
+    token_collateral_fixed_price: u64,
+    native_collateral_price_source: OracleSource,
+    native_collateral_price_feed: [u8; 64],
+    native_collateral_fixed_price: u64,
+) -> Result<()> {
     let vault = &mut ctx.accounts.vault.load_mut()?;
     let owner = &mut ctx.accounts.owner;
+    let old_token_collateral_price_source = vault.token_collateral_price_source;
     let old_token_collateral_price_oracle = vault.token_collateral_price_oracle;
     let old_token_collateral_price_feed = vault.token_collateral_price_feed;
+    let old_token_collateral_fixed_price = vault.token_collateral_fixed_price;
+    let old_native_collateral_price_source = vault.native_collateral_price_source;
     let old_native_collateral_price_oracle = vault.native_collateral_price_oracle;
     let old_native_collateral_price_feed = vault.native_collateral_price_feed;
+    let old_native_collateral_fixed_price = vault.native_collateral_fixed_price;
 
     msg!("protocol address: {:?}", ctx.accounts.protocol.key());
     msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("old principal price source: {:?}", old_token_collateral_price_source);
+    msg!("new principal price source: {:?}", token_collateral_price_source);
     msg!("old principal price oracle address: {:?}", old_token_collateral_price_oracle);
     msg!("new principal price oracle address: {:?}", ctx.accounts.token_collateral_price_oracle.key());
     msg!("old principal price feed: {:?}", old_token_collateral_price_feed);
     msg!("new principal price feed: {:?}", token_collateral_price_feed);
+    msg!("old principal fixed price: {:?}", old_token_collateral_fixed_price);
+    msg!("new principal fixed price: {:?}", token_collateral_fixed_price);
+    msg!("old collateral price source: {:?}", old_native_collateral_price_source);
+    msg!("new collateral price source: {:?}", native_collateral_price_source);
     msg!("old collateral price oracle address: {:?}", old_native_collateral_price_oracle);
     msg!("new collateral price oracle address: {:?}", ctx.accounts.native_collateral_price_oracle.key());
     msg!("old collateral price feed: {:?}", old_native_collateral_price_feed);
     msg!("new collateral price feed: {:?}", native_collateral_price_feed);
+    msg!("old collateral fixed price: {:?}", old_native_collateral_fixed_price);
+    msg!("new collateral fixed price: {:?}", native_collateral_fixed_price);
 
-    vault.change_price_oracle(ctx.accounts.token_collateral_price_oracle.key(), token_collateral_price_feed, ctx.accounts.native_collateral_price_oracle.key(), native_collateral_price_feed)?;
+    oracle::validate_source(token_collateral_price_source, &ctx.accounts.token_collateral_price_oracle, &token_collateral_price_feed, token_collateral_fixed_price)?;
+    oracle::validate_source(native_collateral_price_source, &ctx.accounts.native_collateral_price_oracle, &native_collateral_price_feed, native_collateral_fixed_price)?;
+
+    vault.change_price_oracle(
+        token_collateral_price_source, ctx.accounts.token_collateral_price_oracle.key(), token_collateral_price_feed, token_collateral_fixed_price,
+        native_collateral_price_source, ctx.accounts.native_collateral_price_oracle.key(), native_collateral_price_feed, native_collateral_fixed_price,
+    )?;
 
     emit!(EventVaultLeverageChangedPriceOracle {
         vault: ctx.accounts.vault.key(),
+        old_token_collateral_price_source,
+        new_token_collateral_price_source: token_collateral_price_source,
         old_token_collateral_price_oracle,
         new_token_collateral_price_oracle: ctx.accounts.token_collateral_price_oracle.key(),
         old_token_collateral_price_feed,
         new_token_collateral_price_feed: token_collateral_price_feed,
+        old_token_collateral_fixed_price,
+        new_token_collateral_fixed_price: token_collateral_fixed_price,
+        old_native_collateral_price_source,
+        new_native_collateral_price_source: native_collateral_price_source,
         old_native_collateral_price_oracle,
         new_native_collateral_price_oracle: ctx.accounts.native_collateral_price_oracle.key(),
         old_native_collateral_price_feed,
         new_native_collateral_price_feed: native_collateral_price_feed,
+        old_native_collateral_fixed_price,
+        new_native_collateral_fixed_price: native_collateral_fixed_price,
     });
 
     Ok(())
@@ -50,8 +85,10 @@ pub struct VaultLeverageChangePriceOracle<'info> {
     )]
     pub vault: AccountLoader<'info, VaultLeverage>,
 
-    pub token_collateral_price_oracle: Box<Account<'info, PriceUpdateV2>>,
-    pub native_collateral_price_oracle: Box<Account<'info, PriceUpdateV2>>,
+    /// CHECK: validated against the token collateral price source
+    pub token_collateral_price_oracle: UncheckedAccount<'info>,
+    /// CHECK: validated against the native collateral price source
+    pub native_collateral_price_oracle: UncheckedAccount<'info>,
 
     #[account(mut, address = protocol.load()?.owner @ Errors::NotOwner)]
     pub owner: Signer<'info>,
@@ -0,0 +1,347 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{AssociatedToken};
+use anchor_spl::token_interface::{TokenInterface, Mint, TokenAccount};
+use crate::error::{ErrorLeverage, Errors};
+use crate::error::ErrorMath::MathOverflow;
+use crate::event::{EventLeverageCloseSwap};
+use crate::state::{EarnConfig, LeverageConfig, Obligation, Protocol, VaultEarn, VaultLeverage};
+use crate::util::{decimals, oracle, seeds, swap, transfer_token::{transfer_token, transfer_token_with_signer}};
+use crate::util::action::LeverageAction;
+use crate::util::insurance::pay_fee_with_insurance;
+use crate::util::constant::{INDEX_DECIMALS, PERCENT_MAX};
+use crate::util::pause::{require_not_paused, PauseFlags};
+
+// Full close with the swap done by cpi in the same instruction, callable by other programs
+pub fn handle<'info>(
+    ctx: Context<'_, '_, 'info, 'info, VaultLeverageCloseSwap<'info>>,
+    number: u8,
+    route_accounts_len: u8,
+    route_data: Vec<u8>,
+) -> Result<()> {
+    check_pause(&ctx)?;
+
+    let config = &ctx.accounts.leverage_config.load()?;
+    let vault = &mut ctx.accounts.vault.load_mut()?;
+    let earn_config = &ctx.accounts.earn_config.load()?;
+    let borrow_vault = &mut ctx.accounts.borrow_vault.load_mut()?;
+    let obligation = &mut Obligation::load_with_positions_mut(&ctx.accounts.obligation)?;
+
+    // Debt is priced on a borrowing index accrued up to now
+    vault.accrue_interest_from(borrow_vault, earn_config)?;
+
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("borrow_vault address: {:?}", ctx.accounts.borrow_vault.key());
+    msg!("obligation address: {:?}", ctx.accounts.obligation.key());
+
+    let route_accounts = swap::route_accounts(ctx.remaining_accounts, route_accounts_len)?;
+
+    let position = obligation.position_mut(number)?;
+
+    require_gt!(position.unit, 0, ErrorLeverage::NoPositionFound);
+    position.require_holder(ctx.accounts.owner.key(), ctx.accounts.user.key(), &ctx.accounts.user_nft_account)?;
+
+    // Position must not be in the middle of another process
+    position.halt_on_leveraging()?;
+    position.halt_on_deleveraging()?;
+
+    let (token_collateral_price, token_collateral_price_exponent, token_collateral_price_sources) = vault.token_collateral_price(&ctx.accounts.token_collateral_price_oracle, ctx.remaining_accounts)?;
+    let (native_collateral_price, native_collateral_price_exponent, native_collateral_price_sources) = vault.native_collateral_price(&ctx.accounts.native_collateral_price_oracle, ctx.remaining_accounts)?;
+
+    msg!("token_collateral_price: {:?} exponent: {:?}", token_collateral_price, token_collateral_price_exponent);
+    msg!("native_collateral_price: {:?} exponent: {:?}", native_collateral_price, native_collateral_price_exponent);
+
+    let release_amount = position.collateral_amount(vault.native_collateral_token_decimal, vault.index)?;
+    let repay_amount = position.borrowing_amount(vault.token_collateral_token_decimal, vault.borrowing_index)?;
+
+    let native_collateral_value = oracle::value(release_amount, vault.native_collateral_token_decimal, native_collateral_price, native_collateral_price_exponent)?;
+
+    // Expected token collateral out of the swap, minus the allowed slippage
+    let expected_output = oracle::amount(native_collateral_value, vault.token_collateral_token_decimal, token_collateral_price, token_collateral_price_exponent)?;
+    let release_min_output = (expected_output as u128)
+        .checked_mul(PERCENT_MAX.checked_sub(config.slippage_rate).ok_or(MathOverflow)? as u128).ok_or(MathOverflow)?
+        .checked_div(PERCENT_MAX as u128).ok_or(MathOverflow)? as u64;
+
+    require_gt!(release_min_output, 0, ErrorLeverage::InvalidAmount);
+
+    msg!("release_amount: {:?} repay_amount: {:?} release_min_output: {:?}", release_amount, repay_amount, release_min_output);
+
+    position.set_action(LeverageAction::Close)?;
+    position.set_config(config)?;
+    position.set_oracle(
+        vault.token_collateral_price_oracle, vault.token_collateral_price_feed, token_collateral_price, token_collateral_price_exponent,
+        vault.native_collateral_price_oracle, vault.native_collateral_price_feed, native_collateral_price, native_collateral_price_exponent,
+    )?;
+    position.release(
+        release_amount, position.unit, vault.index,
+        PERCENT_MAX,
+        repay_amount, position.borrowing_unit, vault.borrowing_index,
+        release_min_output,
+    )?;
+
+    // RELEASE TO THE USER FOR THE SWAP
+    let vault_key = ctx.accounts.vault.key();
+    let vault_seeds = &[
+        seeds::VAULT_LEVERAGE_AUTH,
+        vault_key.as_ref(),
+        &[ctx.bumps.vault_authority],
+    ];
+
+    let vault_signer_seeds = &[&vault_seeds[..]];
+
+    transfer_token_with_signer(
+        ctx.accounts.native_collateral_vault_liquidity.to_account_info(),
+        ctx.accounts.user_native_ata.to_account_info(),
+        ctx.accounts.vault_authority.to_account_info(),
+        ctx.accounts.native_collateral_token_program.to_account_info(),
+        ctx.accounts.native_collateral_token_mint.to_account_info(),
+        release_amount,
+        ctx.accounts.native_collateral_token_mint.decimals,
+        vault_signer_seeds,
+    )?;
+
+    // SWAP, THE ROUTE MAY ONLY SPEND THE RELEASED COLLATERAL
+    let native_balance_before = swap::token_balance(&ctx.accounts.user_native_ata.to_account_info())?;
+    let token_balance_before = swap::token_balance(&ctx.accounts.user_ata.to_account_info())?;
+
+    swap::swap(&ctx.accounts.swap_routers.to_account_info(), &ctx.accounts.swap_program.to_account_info(), route_accounts, route_data)?;
+
+    let native_balance_after = swap::token_balance(&ctx.accounts.user_native_ata.to_account_info())?;
+    let token_balance_after = swap::token_balance(&ctx.accounts.user_ata.to_account_info())?;
+    let swap_input = native_balance_before.saturating_sub(native_balance_after);
+    let release_output = token_balance_after.saturating_sub(token_balance_before);
+    let swap_router = ctx.accounts.swap_program.key();
+
+    msg!("swap router: {:?} swap_input: {:?} release_output: {:?}", swap_router, swap_input, release_output);
+
+    require_gte!(release_amount, swap_input, ErrorLeverage::SwapInputTooHigh);
+    require_gte!(release_output, release_min_output, ErrorLeverage::SlippageReached);
+
+    position.set_swap_router(swap_router)?;
+
+    // REPAY BORROW
+    let utilization_rate = borrow_vault.utilization_rate()?;
+
+    if repay_amount > 0 {
+        transfer_token(
+            ctx.accounts.user_ata.to_account_info(),
+            ctx.accounts.borrow_vault_liquidity.to_account_info(),
+            ctx.accounts.user.to_account_info(),
+            ctx.accounts.token_collateral_token_program.to_account_info(),
+            ctx.accounts.token_collateral_token_mint.to_account_info(),
+            repay_amount,
+            ctx.accounts.token_collateral_token_mint.decimals,
+        )?;
+    }
+
+    position.repay_borrow(repay_amount)?;
+    if position.state.repay_unit > 0 {
+        borrow_vault.deleverage(position.state.repay_unit)?;
+    }
+
+    // PAY PROTOCOL
+    let protocol_fee_factor = vault.protocol_fee_factor(config.protocol_fee, utilization_rate, position.avg_borrowing_index, vault.borrowing_index)?;
+
+    msg!("utilization_rate: {:?}", utilization_rate);
+    msg!("protocol_fee_factor: {:?}", protocol_fee_factor);
+
+    let protocol_fee_amount = (decimals::mul_ceil(
+        vault.token_collateral_token_decimal, release_min_output as u128, vault.token_collateral_token_decimal,
+        protocol_fee_factor, INDEX_DECIMALS
+    )? as u64).saturating_div(100);
+
+    let insurance_amount = config.insurance_amount(protocol_fee_amount)?;
+
+    pay_fee_with_insurance(
+        ctx.accounts.user_ata.to_account_info(),
+        ctx.accounts.leverage_fee_vault.to_account_info(),
+        ctx.accounts.borrow_vault_liquidity.to_account_info(),
+        ctx.accounts.user.to_account_info(),
+        ctx.accounts.token_collateral_token_program.to_account_info(),
+        ctx.accounts.token_collateral_token_mint.to_account_info(),
+        ctx.accounts.token_collateral_token_mint.decimals,
+        protocol_fee_amount,
+        insurance_amount,
+    )?;
+    borrow_vault.fund_insurance(insurance_amount)?;
+
+    msg!("protocol_fee_amount: {:?} insurance_amount: {:?}", protocol_fee_amount, insurance_amount);
+
+    position.pay_protocol_fee(utilization_rate, protocol_fee_factor, protocol_fee_amount)?;
+
+    // CLOSING, THE REMAINING SWAP OUTPUT STAYS WITH THE USER
+    let remaining_amount = release_output
+        .saturating_sub(repay_amount)
+        .saturating_sub(protocol_fee_amount);
+    let release_unit = position.state.release_unit;
+    let release_index = position.state.release_index;
+    let repay_unit = position.state.repay_unit;
+    let repay_index = position.state.repay_index;
+
+    msg!("remaining_amount: {:?}", remaining_amount);
+
+    position.closing()?;
+    vault.burn(release_unit)?;
+    if repay_unit > 0 {
+        vault.burn_borrow(repay_unit)?;
+    }
+
+    let position_id = position.id;
+    obligation.close_position(position_id)?;
+
+    emit!(EventLeverageCloseSwap {
+        borrow_vault: ctx.accounts.borrow_vault.key(),
+        vault: ctx.accounts.vault.key(),
+        user: ctx.accounts.user.key(),
+        obligation: ctx.accounts.obligation.key(),
+        position_id,
+        position_number: number,
+        token_collateral_price,
+        token_collateral_price_exponent,
+        native_collateral_price,
+        native_collateral_price_exponent,
+        token_collateral_price_sources,
+        native_collateral_price_sources,
+        release_amount,
+        release_unit,
+        release_index,
+        repay_amount,
+        repay_unit,
+        repay_index,
+        release_min_output,
+        release_output,
+        protocol_fee_amount,
+        remaining_amount,
+        swap_router,
+    });
+
+    Ok(())
+}
+
+#[inline(never)]
+fn check_pause(ctx: &Context<VaultLeverageCloseSwap>) -> Result<()> {
+    require_not_paused(&[
+        ctx.accounts.protocol.load()?.pause_flags,
+        ctx.accounts.leverage_config.load()?.pause_flags,
+    ], PauseFlags::CLOSE)
+}
+
+#[derive(Accounts)]
+pub struct VaultLeverageCloseSwap<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account(
+        has_one = leverage_fee_vault,
+    )]
+    pub leverage_config: AccountLoader<'info, LeverageConfig>,
+    /// CHECK Safe
+    #[account(mut)]
+    pub leverage_fee_vault: AccountInfo<'info>,
+    /// CHECK VAULT LEVERAGE AUTHORITY
+    #[account(
+        seeds = [seeds::VAULT_LEVERAGE_AUTH, vault.key().as_ref()],
+        bump,
+    )]
+    pub vault_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+        has_one = leverage_config @ Errors::InvalidConfig,
+        has_one = borrow_vault,
+        has_one = token_collateral_token_program,
+        has_one = token_collateral_token_mint,
+        has_one = native_collateral_token_program,
+        has_one = native_collateral_token_mint,
+    )]
+    pub vault: AccountLoader<'info, VaultLeverage>,
+    #[account()]
+    pub earn_config: AccountLoader<'info, EarnConfig>,
+    #[account(
+        mut,
+        associated_token::token_program = native_collateral_token_program,
+        associated_token::mint = native_collateral_token_mint,
+        associated_token::authority = vault_authority,
+    )]
+    pub native_collateral_vault_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK VAULT FOR BORROWING AUTHORITY
+    #[account(
+        seeds = [seeds::VAULT_EARN_AUTH, borrow_vault.key().as_ref()],
+        bump,
+    )]
+    pub borrow_vault_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        has_one = earn_config @ Errors::InvalidConfig,
+    )]
+    pub borrow_vault: AccountLoader<'info, VaultEarn>,
+    #[account(
+        mut,
+        associated_token::token_program = token_collateral_token_program,
+        associated_token::mint = token_collateral_token_mint,
+        associated_token::authority = borrow_vault_authority,
+    )]
+    pub borrow_vault_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [seeds::OBLIGATION, vault.key().as_ref(), token_collateral_token_mint.key().as_ref(), native_collateral_token_mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub obligation: AccountLoader<'info, Obligation>,
+    /// CHECK: owner of the obligation, validated by the obligation seeds
+    pub owner: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub user_nft_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::token_program = token_collateral_token_program,
+        associated_token::mint = token_collateral_token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::token_program = native_collateral_token_program,
+        associated_token::mint = native_collateral_token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_native_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: price oracle of the vault, read according to its oracle source
+    #[account(
+        address = vault.load()?.token_collateral_price_oracle @ Errors::InvalidPriceOracle,
+    )]
+    pub token_collateral_price_oracle: UncheckedAccount<'info>,
+    /// CHECK: price oracle of the vault, read according to its oracle source
+    #[account(
+        address = vault.load()?.native_collateral_price_oracle @ Errors::InvalidPriceOracle,
+    )]
+    pub native_collateral_price_oracle: UncheckedAccount<'info>,
+
+    #[account(
+        mint::token_program = token_collateral_token_program,
+    )]
+    pub token_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mint::token_program = native_collateral_token_program,
+    )]
+    pub native_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: swap routers of the vault, empty until the owner creates them
+    #[account(
+        seeds = [seeds::SWAP_ROUTERS, vault.key().as_ref()],
+        bump,
+    )]
+    pub swap_routers: UncheckedAccount<'info>,
+    /// CHECK: router program of the swap, checked against the swap routers of the vault
+    #[account(executable)]
+    pub swap_program: UncheckedAccount<'info>,
+
+    pub token_collateral_token_program: Interface<'info, TokenInterface>,
+    pub native_collateral_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
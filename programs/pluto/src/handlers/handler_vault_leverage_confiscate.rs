@@ -6,17 +6,18 @@ use anchor_spl::token_interface::{close_account, CloseAccount, TokenInterface, M
 use crate::error::{ErrorLeverage, Errors};
 use crate::error::ErrorMath::MathOverflow;
 use crate::event::{EventLeverageOpen};
-use crate::state::{LeverageConfig, Obligation, Protocol, VaultLeverage};
+use crate::state::{LeverageConfig, Obligation, Protocol, SwapRouters, VaultLeverage};
 use crate::util::{decimals, seeds, transfer_token::transfer_token};
 use crate::util::constant::{PERCENT_DECIMALS, INDEX_DECIMALS, UNIT_DECIMALS, PERCENT_MAX};
+use crate::util::action::LeverageAction;
+use crate::util::pause::{require_not_paused, PauseFlags};
 
 pub fn handle(ctx: Context<VaultLeverageConfiscate>) -> Result<()> {
-    verify_next_ixs(&ctx)?;
-    check_freeze(&ctx)?;
+    let swap_router = verify_next_ixs(&ctx)?;
 
     let config = &ctx.accounts.leverage_config.load()?;
     let vault = &mut ctx.accounts.vault.load_mut()?;
-    let obligation = &mut ctx.accounts.obligation.load_mut()?;
+    let obligation = &mut Obligation::load_with_positions_mut(&ctx.accounts.obligation)?;
 
     msg!("vault address: {:?}", ctx.accounts.vault.key());
     msg!("vault config address: {:?}", ctx.accounts.leverage_config.key());
@@ -25,6 +26,8 @@ pub fn handle(ctx: Context<VaultLeverageConfiscate>) -> Result<()> {
     msg!("vault index: {:?}", vault.index);
 
     let position = obligation.find_pending_leveraged_position()?;
+    check_pause(&ctx, position.state.action)?;
+    position.require_holder(ctx.accounts.owner.key(), ctx.accounts.user.key(), &ctx.accounts.user_nft_account)?;
 
     let old_unit = position.unit;
     let old_index = position.avg_index;
@@ -36,6 +39,7 @@ pub fn handle(ctx: Context<VaultLeverageConfiscate>) -> Result<()> {
 
     msg!("pending_leveraged_amount: {:?}", leveraged_amount);
     msg!("pending_min_native_collateral_output: {:?}", min_native_collateral_output);
+    msg!("swap router: {:?}", swap_router);
 
     let mut fair_native_collateral_output = decimals::mul_ceil(vault.native_collateral_token_decimal, min_native_collateral_output as u128, vault.native_collateral_token_decimal, 100, 0)? as u64;
     fair_native_collateral_output = decimals::div_ceil(vault.native_collateral_token_decimal, fair_native_collateral_output as u128, vault.native_collateral_token_decimal, PERCENT_MAX.checked_sub(config.slippage_rate).ok_or(MathOverflow)? as u128, PERCENT_DECIMALS)? as u64;
@@ -65,7 +69,8 @@ pub fn handle(ctx: Context<VaultLeverageConfiscate>) -> Result<()> {
         ctx.accounts.native_collateral_token_mint.decimals,
     )?;
 
-    let token_to_collateral_ratio = decimals::div_ceil(INDEX_DECIMALS, taking_amount as u128, vault.native_collateral_token_decimal, position.state.leveraged_amount as u128, vault.token_collateral_token_decimal)?;
+    // Added collateral or position blends into the open ratio of the existing position
+    let token_to_collateral_ratio = position.blended_token_to_native_ratio(vault.token_collateral_token_decimal, vault.native_collateral_token_decimal, taking_amount, position.state.leveraged_amount)?;
     let unit = decimals::div_ceil(UNIT_DECIMALS, taking_amount as u128, vault.native_collateral_token_decimal, vault.index, INDEX_DECIMALS)? as u64;
 
     position.confiscate(vault.native_collateral_token_decimal, token_to_collateral_ratio, unit, vault.index)?;
@@ -92,6 +97,7 @@ pub fn handle(ctx: Context<VaultLeverageConfiscate>) -> Result<()> {
         real_native_collateral_output: taking_amount,
         unit,
         index: vault.index,
+        swap_router,
     });
 
     if ctx.accounts.user_ata.amount == 0 {
@@ -101,7 +107,7 @@ pub fn handle(ctx: Context<VaultLeverageConfiscate>) -> Result<()> {
     Ok(())
 }
 
-fn verify_next_ixs(ctx: &Context<VaultLeverageConfiscate>) -> Result<()> {
+fn verify_next_ixs(ctx: &Context<VaultLeverageConfiscate>) -> Result<Pubkey> {
     let ixs = ctx.accounts.instructions.to_account_info();
 
     // make sure this isnt a cpi call
@@ -111,13 +117,30 @@ fn verify_next_ixs(ctx: &Context<VaultLeverageConfiscate>) -> Result<()> {
         return Err(Errors::InvalidProgram.into());
     }
 
-    Ok(())
+    // the swap must sit between the previous instruction of this program and the confiscate
+    let mut swap_router = None;
+    let mut index = current_index;
+    while index > 0 {
+        index -= 1;
+        let ix = load_instruction_at_checked(index, &ixs)?;
+        if ix.program_id == crate::id() {
+            break;
+        }
+        if SwapRouters::is_allowed_swap(&ctx.accounts.swap_routers, &ix)? {
+            swap_router = Some(ix.program_id);
+        }
+    }
+
+    swap_router.ok_or(ErrorLeverage::MissingJupiterSwap.into())
 }
 
-fn check_freeze(ctx: &Context<VaultLeverageConfiscate>) -> Result<()> {
-    require!(!(ctx.accounts.protocol.load()?.freeze && !ctx.accounts.protocol.load()?.freeze_leverage), ErrorLeverage::VaultFrozen);
-    require!(!ctx.accounts.leverage_config.load()?.freeze, ErrorLeverage::VaultFrozen);
-    Ok(())
+#[inline(never)]
+fn check_pause(ctx: &Context<VaultLeverageConfiscate>, action: LeverageAction) -> Result<()> {
+    // Confiscate completes opening, adding to a position and adding collateral, each paused on its own
+    require_not_paused(&[
+        ctx.accounts.protocol.load()?.pause_flags,
+        ctx.accounts.leverage_config.load()?.pause_flags,
+    ], PauseFlags::of_action(action))
 }
 
 fn close_user_ata(ctx: &Context<VaultLeverageConfiscate>) -> Result<()> {
@@ -162,12 +185,15 @@ pub struct VaultLeverageConfiscate<'info> {
 
     #[account(
         mut,
-        seeds = [seeds::OBLIGATION, vault.key().as_ref(), token_collateral_token_mint.key().as_ref(), native_collateral_token_mint.key().as_ref(), user.key().as_ref()],
+        seeds = [seeds::OBLIGATION, vault.key().as_ref(), token_collateral_token_mint.key().as_ref(), native_collateral_token_mint.key().as_ref(), owner.key().as_ref()],
         bump,
     )]
     pub obligation: AccountLoader<'info, Obligation>,
+    /// CHECK: owner of the obligation, validated by the obligation seeds
+    pub owner: UncheckedAccount<'info>,
     #[account(mut)]
     pub user: Signer<'info>,
+    pub user_nft_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     #[account(
         mut,
@@ -194,6 +220,13 @@ pub struct VaultLeverageConfiscate<'info> {
     )]
     pub native_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
 
+    /// CHECK: swap routers of the vault, empty until the owner creates them
+    #[account(
+        seeds = [seeds::SWAP_ROUTERS, vault.key().as_ref()],
+        bump,
+    )]
+    pub swap_routers: UncheckedAccount<'info>,
+
     /// CHECK: check instructions account
     #[account(address = sysvar::instructions::ID @Errors::InvalidAddress)]
     pub instructions: UncheckedAccount<'info>,
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::error::Errors;
+use crate::event::{EventVaultLeverageCreatedSwapRouters};
+use crate::state::{InitSwapRoutersParams, Protocol, SwapRouters, VaultLeverage};
+use crate::util::seeds;
+
+pub fn handle(ctx: Context<VaultLeverageCreateSwapRouters>) -> Result<()> {
+    let swap_routers = &mut ctx.accounts.swap_routers.load_init()?;
+
+    swap_routers.init(InitSwapRoutersParams {
+        bump: ctx.bumps.swap_routers,
+        vault: ctx.accounts.vault.key(),
+    })?;
+
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("swap routers address: {:?}", ctx.accounts.swap_routers.key());
+    msg!("routers: {:?}", swap_routers.routers);
+
+    emit!(EventVaultLeverageCreatedSwapRouters {
+        vault: ctx.accounts.vault.key(),
+        swap_routers: ctx.accounts.swap_routers.key(),
+        routers: swap_routers.routers,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VaultLeverageCreateSwapRouters<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account(
+        has_one = protocol @ Errors::InvalidProtocol,
+    )]
+    pub vault: AccountLoader<'info, VaultLeverage>,
+    #[account(
+        init,
+        seeds = [seeds::SWAP_ROUTERS, vault.key().as_ref()],
+        bump,
+        payer = owner,
+        space = SwapRouters::INIT_SPACE+(1*8),
+    )]
+    pub swap_routers: AccountLoader<'info, SwapRouters>,
+
+    #[account(mut, address = protocol.load()?.owner @ Errors::NotOwner)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::Mint;
+use crate::error::{ErrorLeverage, Errors};
+use crate::error::ErrorMath::MathOverflow;
+use crate::event::{EventLeverageObligationExtended};
+use crate::state::{Obligation, Position, Protocol, VaultLeverage};
+use crate::util::seeds;
+use crate::util::constant::MAX_OBLIGATION_POSITIONS;
+
+pub fn handle(ctx: Context<VaultLeverageExtendObligation>, additional: u8) -> Result<()> {
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("obligation address: {:?}", ctx.accounts.obligation.key());
+
+    require_gt!(additional, 0, ErrorLeverage::InvalidAmount);
+
+    let (old_capacity, old_extra_positions) = {
+        let obligation = ctx.accounts.obligation.load()?;
+        require!(obligation.is_initialized, ErrorLeverage::InvalidObligationSize);
+        (obligation.capacity(), obligation.extra_positions)
+    };
+
+    let new_extra_positions = old_extra_positions.checked_add(additional as u32).ok_or(MathOverflow)?;
+    let new_capacity = old_capacity.checked_add(additional as usize).ok_or(MathOverflow)?;
+
+    require_gte!(MAX_OBLIGATION_POSITIONS, new_capacity, ErrorLeverage::MaxObligationPositionsReached);
+
+    // Obligation created with extra space may already fit the new positions
+    let obligation_info = ctx.accounts.obligation.to_account_info();
+    let old_space = obligation_info.data_len();
+    let new_space = Obligation::space_for(new_extra_positions).max(old_space);
+
+    msg!("capacity: {:?} -> {:?} space: {:?} -> {:?}", old_capacity, new_capacity, old_space, new_space);
+
+    require_gte!(MAX_PERMITTED_DATA_INCREASE, new_space - old_space, ErrorLeverage::InvalidObligationSize);
+
+    let rent_lamports = Rent::get()?.minimum_balance(new_space).saturating_sub(obligation_info.lamports());
+    if rent_lamports > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: obligation_info.clone(),
+                },
+            ),
+            rent_lamports,
+        )?;
+    }
+
+    if new_space > old_space {
+        obligation_info.realloc(new_space, true)?;
+    }
+
+    ctx.accounts.obligation.load_mut()?.extra_positions = new_extra_positions;
+
+    let obligation = &mut Obligation::load_with_positions_mut(&ctx.accounts.obligation)?;
+    for number in old_capacity..new_capacity {
+        *obligation.position_mut(number as u8)? = Position::default();
+    }
+    obligation.update_time()?;
+
+    emit!(EventLeverageObligationExtended {
+        vault: ctx.accounts.vault.key(),
+        user: ctx.accounts.owner.key(),
+        obligation: ctx.accounts.obligation.key(),
+        old_capacity: old_capacity as u8,
+        new_capacity: new_capacity as u8,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VaultLeverageExtendObligation<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account(
+        has_one = protocol @ Errors::InvalidProtocol,
+        has_one = token_collateral_token_mint,
+        has_one = native_collateral_token_mint,
+    )]
+    pub vault: AccountLoader<'info, VaultLeverage>,
+
+    #[account(
+        mut,
+        seeds = [seeds::OBLIGATION, vault.key().as_ref(), token_collateral_token_mint.key().as_ref(), native_collateral_token_mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub obligation: AccountLoader<'info, Obligation>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub token_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub native_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -0,0 +1,330 @@
+use anchor_lang::Discriminator;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_spl::associated_token::{AssociatedToken};
+use anchor_spl::token_interface::{TokenInterface, Mint, TokenAccount};
+use crate::error::{ErrorLeverage, Errors};
+use crate::error::ErrorMath::MathOverflow;
+use crate::event::{EventLeverageIncrease};
+use crate::state::{EarnConfig, LeverageConfig, Obligation, Protocol, VaultEarn, VaultLeverage};
+use crate::util::{decimals, oracle, seeds, transfer_token::{transfer_token, transfer_token_with_signer}};
+use crate::util::action::LeverageAction;
+use crate::util::constant::{INDEX_DECIMALS, LEVERAGE_ONE, PERCENT_MAX, UNIT_DECIMALS};
+use crate::util::pause::{require_not_paused, PauseFlags};
+
+pub fn handle(ctx: Context<VaultLeverageIncrease>, number: u8, amount: u64, leverage: u32) -> Result<()> {
+    verify_next_ixs(&ctx)?;
+    check_pause(&ctx)?;
+
+    let config = &ctx.accounts.leverage_config.load()?;
+    let earn_config = &ctx.accounts.earn_config.load()?;
+    let vault = &mut ctx.accounts.vault.load_mut()?;
+    let borrow_vault = &mut ctx.accounts.borrow_vault.load_mut()?;
+    let obligation = &mut Obligation::load_with_positions_mut(&ctx.accounts.obligation)?;
+
+    // Debt is priced on a borrowing index accrued up to now
+    vault.accrue_interest_from(borrow_vault, earn_config)?;
+
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("borrow_vault address: {:?}", ctx.accounts.borrow_vault.key());
+    msg!("obligation address: {:?}", ctx.accounts.obligation.key());
+
+    require_gt!(amount, 0, ErrorLeverage::InvalidAmount);
+    require_gte!(amount, config.min_leverage_limit, ErrorLeverage::InvalidAmount);
+    require_gte!(config.max_leverage_limit, amount, ErrorLeverage::InvalidAmount);
+    require!(config.leverage_step > 0 && leverage % config.leverage_step == 0, ErrorLeverage::InvalidLeverage);
+    require_gte!(leverage, config.min_leverage, ErrorLeverage::InvalidLeverage);
+    require_gte!(config.max_leverage, leverage, ErrorLeverage::InvalidLeverage);
+    require_gt!(leverage, LEVERAGE_ONE, ErrorLeverage::InvalidLeverage);
+
+    let position = obligation.position_mut(number)?;
+
+    require_gt!(position.unit, 0, ErrorLeverage::NoPositionFound);
+    position.require_holder(ctx.accounts.owner.key(), ctx.accounts.user.key(), &ctx.accounts.user_nft_account)?;
+
+    // Position must not be in the middle of another process
+    position.halt_on_leveraging()?;
+    position.halt_on_deleveraging()?;
+
+    // FEES AND BORROWING
+    let leverage_fee_amount = (amount as u128)
+        .checked_mul(config.leverage_fee as u128).ok_or(MathOverflow)?
+        .checked_div(PERCENT_MAX as u128).ok_or(MathOverflow)? as u64;
+    let borrowing_amount = (amount as u128)
+        .checked_mul(leverage.checked_sub(LEVERAGE_ONE).ok_or(MathOverflow)? as u128).ok_or(MathOverflow)?
+        .checked_div(LEVERAGE_ONE as u128).ok_or(MathOverflow)? as u64;
+    let borrow_fee_amount = (borrowing_amount as u128)
+        .checked_mul(earn_config.borrow_fee as u128).ok_or(MathOverflow)?
+        .checked_div(PERCENT_MAX as u128).ok_or(MathOverflow)? as u64;
+
+    msg!("leverage_fee_amount: {:?} borrowing_amount: {:?} borrow_fee_amount: {:?}", leverage_fee_amount, borrowing_amount, borrow_fee_amount);
+
+    require_gt!(borrowing_amount, 0, ErrorLeverage::InvalidAmount);
+    require_gte!(borrowing_amount, earn_config.min_borrow_limit, ErrorLeverage::InsufficientBorrowableAmount);
+    require_gte!(earn_config.max_borrow_limit, borrowing_amount, ErrorLeverage::InsufficientBorrowableAmount);
+    require_gte!(borrow_vault.borrow_available_amount(earn_config, ctx.accounts.borrow_vault_liquidity.amount)?, borrowing_amount as u128, ErrorLeverage::InsufficientBorrowableAmount);
+    require_gte!(ctx.accounts.user_ata.amount, amount, ErrorLeverage::InsufficientFund);
+
+    // Ceil to prevent less debt from rounding
+    let borrowing_unit = decimals::div_ceil(UNIT_DECIMALS, borrowing_amount as u128, vault.token_collateral_token_decimal, vault.borrowing_index, INDEX_DECIMALS)? as u64;
+    let leveraged_amount = amount
+        .checked_sub(leverage_fee_amount).ok_or(MathOverflow)?
+        .checked_add(borrowing_amount.checked_sub(borrow_fee_amount).ok_or(MathOverflow)?).ok_or(MathOverflow)?;
+
+    // PRICES
+    let (token_collateral_price, token_collateral_price_exponent, token_collateral_price_sources) = vault.token_collateral_price(&ctx.accounts.token_collateral_price_oracle, ctx.remaining_accounts)?;
+    let (native_collateral_price, native_collateral_price_exponent, native_collateral_price_sources) = vault.native_collateral_price(&ctx.accounts.native_collateral_price_oracle, ctx.remaining_accounts)?;
+
+    // Expected native collateral out of the swap, minus the allowed slippage
+    let leveraged_value = oracle::value(leveraged_amount, vault.token_collateral_token_decimal, token_collateral_price, token_collateral_price_exponent)?;
+    let expected_output = oracle::amount(leveraged_value, vault.native_collateral_token_decimal, native_collateral_price, native_collateral_price_exponent)?;
+    let min_native_collateral_output = (expected_output as u128)
+        .checked_mul(PERCENT_MAX.checked_sub(config.slippage_rate).ok_or(MathOverflow)? as u128).ok_or(MathOverflow)?
+        .checked_div(PERCENT_MAX as u128).ok_or(MathOverflow)? as u64;
+
+    require_gt!(min_native_collateral_output, 0, ErrorLeverage::InvalidAmount);
+
+    // The whole position must stay within the max leverage after the increase
+    let collateral_amount = position.collateral_amount(vault.native_collateral_token_decimal, vault.index)?;
+    let position_borrowing_amount = position.borrowing_amount(vault.token_collateral_token_decimal, vault.borrowing_index)?;
+    let native_collateral_value = oracle::value(collateral_amount, vault.native_collateral_token_decimal, native_collateral_price, native_collateral_price_exponent)?
+        .checked_add(leveraged_value).ok_or(MathOverflow)?;
+    let borrowing_value = oracle::value(position_borrowing_amount.checked_add(borrowing_amount).ok_or(MathOverflow)?, vault.token_collateral_token_decimal, token_collateral_price, token_collateral_price_exponent)?;
+    let position_leverage = position.current_leverage(native_collateral_value, borrowing_value)?;
+
+    msg!("leveraged_amount: {:?} min_native_collateral_output: {:?}", leveraged_amount, min_native_collateral_output);
+    msg!("position_leverage: {:?} max_leverage: {:?}", position_leverage, config.max_leverage);
+
+    require_gte!(config.max_leverage, position_leverage, ErrorLeverage::InvalidLeverage);
+
+    // TRANSFERS
+    if leverage_fee_amount > 0 {
+        transfer_token(
+            ctx.accounts.user_ata.to_account_info(),
+            ctx.accounts.leverage_fee_vault.to_account_info(),
+            ctx.accounts.user.to_account_info(),
+            ctx.accounts.token_collateral_token_program.to_account_info(),
+            ctx.accounts.token_collateral_token_mint.to_account_info(),
+            leverage_fee_amount,
+            ctx.accounts.token_collateral_token_mint.decimals,
+        )?;
+    }
+
+    let borrow_vault_key = ctx.accounts.borrow_vault.key();
+    let seeds = &[
+        seeds::VAULT_EARN_AUTH,
+        borrow_vault_key.as_ref(),
+        &[ctx.bumps.borrow_vault_authority],
+    ];
+
+    let signer_seeds = &[&seeds[..]];
+
+    if borrow_fee_amount > 0 {
+        transfer_token_with_signer(
+            ctx.accounts.borrow_vault_liquidity.to_account_info(),
+            ctx.accounts.earn_fee_vault.to_account_info(),
+            ctx.accounts.borrow_vault_authority.to_account_info(),
+            ctx.accounts.token_collateral_token_program.to_account_info(),
+            ctx.accounts.token_collateral_token_mint.to_account_info(),
+            borrow_fee_amount,
+            ctx.accounts.token_collateral_token_mint.decimals,
+            signer_seeds,
+        )?;
+    }
+
+    transfer_token_with_signer(
+        ctx.accounts.borrow_vault_liquidity.to_account_info(),
+        ctx.accounts.user_ata.to_account_info(),
+        ctx.accounts.borrow_vault_authority.to_account_info(),
+        ctx.accounts.token_collateral_token_program.to_account_info(),
+        ctx.accounts.token_collateral_token_mint.to_account_info(),
+        borrowing_amount.checked_sub(borrow_fee_amount).ok_or(MathOverflow)?,
+        ctx.accounts.token_collateral_token_mint.decimals,
+        signer_seeds,
+    )?;
+
+    borrow_vault.leverage(earn_config, borrowing_amount)?;
+    vault.mint_borrow(borrowing_unit)?;
+
+    position.set_action(LeverageAction::AddPosition)?;
+    position.set_config(config)?;
+    position.set_oracle(
+        vault.token_collateral_price_oracle, vault.token_collateral_price_feed, token_collateral_price, token_collateral_price_exponent,
+        vault.native_collateral_price_oracle, vault.native_collateral_price_feed, native_collateral_price, native_collateral_price_exponent,
+    )?;
+    position.increase(amount, leverage_fee_amount)?;
+    position.borrow_fund(borrowing_amount, borrowing_unit, vault.borrowing_index, borrow_fee_amount)?;
+    position.take_fund(vault.token_collateral_token_decimal)?;
+    position.leverage(leveraged_amount, min_native_collateral_output)?;
+
+    emit!(EventLeverageIncrease {
+        borrow_vault: ctx.accounts.borrow_vault.key(),
+        vault: ctx.accounts.vault.key(),
+        user: ctx.accounts.user.key(),
+        obligation: ctx.accounts.obligation.key(),
+        position_id: position.id,
+        position_number: number,
+        token_collateral_price,
+        token_collateral_price_exponent,
+        native_collateral_price,
+        native_collateral_price_exponent,
+        token_collateral_price_sources,
+        native_collateral_price_sources,
+        leverage,
+        fund_amount: amount,
+        leverage_fee_amount,
+        borrowing_amount,
+        borrowing_unit,
+        borrowing_index: vault.borrowing_index,
+        borrow_fee_amount,
+        leveraged_amount,
+        min_native_collateral_output,
+        position_leverage,
+    });
+
+    Ok(())
+}
+
+#[inline(never)]
+fn verify_next_ixs(ctx: &Context<VaultLeverageIncrease>) -> Result<()> {
+    let ixs = ctx.accounts.instructions.to_account_info();
+
+    // make sure this isnt a cpi call
+    let current_index = load_current_index_checked(&ixs)? as usize;
+    let current_ix = load_instruction_at_checked(current_index, &ixs)?;
+    if current_ix.program_id != *ctx.program_id {
+        return Err(Errors::InvalidProgram.into());
+    }
+
+    // next instruction of this program must be the confiscate taking the swap output
+    let mut index = current_index + 1;
+    loop {
+        if let Ok(ix) = load_instruction_at_checked(index, &ixs) {
+            if ix.program_id == crate::id() {
+                let ix_discriminator: [u8; 8] = ix.data[0..8]
+                    .try_into()
+                    .map_err(|_| Errors::UnknownInstruction)?;
+
+                if ix_discriminator == crate::instruction::LeverageVaultConfiscate::discriminator() {
+                    break;
+                } else {
+                    return Err(ErrorLeverage::NextInstructionMustBeConfiscate.into());
+                }
+            }
+        } else {
+            return Err(ErrorLeverage::MissingConfiscate.into());
+        }
+
+        index += 1
+    }
+
+    Ok(())
+}
+
+#[inline(never)]
+fn check_pause(ctx: &Context<VaultLeverageIncrease>) -> Result<()> {
+    require_not_paused(&[
+        ctx.accounts.protocol.load()?.pause_flags,
+        ctx.accounts.leverage_config.load()?.pause_flags,
+        ctx.accounts.earn_config.load()?.pause_flags,
+    ], PauseFlags::OPEN)
+}
+
+#[derive(Accounts)]
+pub struct VaultLeverageIncrease<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account(
+        has_one = leverage_fee_vault,
+    )]
+    pub leverage_config: AccountLoader<'info, LeverageConfig>,
+    /// CHECK Safe
+    #[account(mut)]
+    pub leverage_fee_vault: AccountInfo<'info>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+        has_one = leverage_config @ Errors::InvalidConfig,
+        has_one = borrow_vault,
+        has_one = token_collateral_token_program,
+        has_one = token_collateral_token_mint,
+        has_one = native_collateral_token_program,
+        has_one = native_collateral_token_mint,
+    )]
+    pub vault: AccountLoader<'info, VaultLeverage>,
+
+    #[account(
+        has_one = earn_fee_vault,
+    )]
+    pub earn_config: AccountLoader<'info, EarnConfig>,
+    /// CHECK Safe
+    #[account(mut)]
+    pub earn_fee_vault: AccountInfo<'info>,
+    /// CHECK VAULT FOR BORROWING AUTHORITY
+    #[account(
+        seeds = [seeds::VAULT_EARN_AUTH, borrow_vault.key().as_ref()],
+        bump,
+    )]
+    pub borrow_vault_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        has_one = earn_config @ Errors::InvalidConfig,
+    )]
+    pub borrow_vault: AccountLoader<'info, VaultEarn>,
+    #[account(
+        mut,
+        associated_token::token_program = token_collateral_token_program,
+        associated_token::mint = token_collateral_token_mint,
+        associated_token::authority = borrow_vault_authority,
+    )]
+    pub borrow_vault_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [seeds::OBLIGATION, vault.key().as_ref(), token_collateral_token_mint.key().as_ref(), native_collateral_token_mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub obligation: AccountLoader<'info, Obligation>,
+    /// CHECK: owner of the obligation, validated by the obligation seeds
+    pub owner: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub user: Signer<'info>,
+    pub user_nft_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        associated_token::token_program = token_collateral_token_program,
+        associated_token::mint = token_collateral_token_mint,
+        associated_token::authority = user,
+    )]
+    pub user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: price oracle of the vault, read according to its oracle source
+    #[account(
+        address = vault.load()?.token_collateral_price_oracle @ Errors::InvalidPriceOracle,
+    )]
+    pub token_collateral_price_oracle: UncheckedAccount<'info>,
+    /// CHECK: price oracle of the vault, read according to its oracle source
+    #[account(
+        address = vault.load()?.native_collateral_price_oracle @ Errors::InvalidPriceOracle,
+    )]
+    pub native_collateral_price_oracle: UncheckedAccount<'info>,
+
+    #[account(
+        mint::token_program = token_collateral_token_program,
+    )]
+    pub token_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mint::token_program = native_collateral_token_program,
+    )]
+    pub native_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: check instructions account
+    #[account(address = sysvar::instructions::ID @Errors::InvalidAddress)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_collateral_token_program: Interface<'info, TokenInterface>,
+    pub native_collateral_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -0,0 +1,196 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_spl::associated_token::{AssociatedToken};
+use anchor_spl::token_interface::{TokenInterface, Mint, TokenAccount};
+use crate::error::{ErrorLeverage, Errors};
+use crate::event::{EventLeverageKeeperClosing, EventLeverageProfitTaken};
+use crate::state::{LeverageConfig, Obligation, Protocol, VaultLeverage};
+use crate::util::{seeds, transfer_token::transfer_token};
+use crate::util::action::LeverageAction;
+use crate::util::pause::{require_not_paused, PauseFlags};
+
+#[inline(never)]
+pub fn handle(ctx: Context<VaultLeverageKeeperClosing>, number: u8) -> Result<()> {
+    verify_next_ixs(&ctx)?;
+
+    let vault = &mut ctx.accounts.vault.load_mut()?;
+    let obligation = &mut Obligation::load_with_positions_mut(&ctx.accounts.obligation)?;
+
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("obligation address: {:?}", ctx.accounts.obligation.key());
+
+    let position = obligation.position_mut(number)?;
+
+    check_pause(&ctx, position.state.action)?;
+    require_gt!(position.unit, 0, ErrorLeverage::NoPositionFound);
+    require!(position.state.is_keeper_action(), ErrorLeverage::InvalidKeeperAction);
+    require_keys_eq!(position.state.keeper, ctx.accounts.keeper.key(), ErrorLeverage::InvalidKeeper);
+    position.require_holder(ctx.accounts.owner.key(), ctx.accounts.holder.key(), &ctx.accounts.holder_nft_account)?;
+
+    let action = position.state.action;
+    let release_unit = position.state.release_unit;
+    let repay_unit = position.state.repay_unit;
+    let release_output = position.state.release_output;
+    let repay_borrow_amount = position.state.repay_borrow_amount;
+    let protocol_fee_amount = position.state.protocol_fee_amount;
+    let liquidation_fee_amount = position.state.liquidation_fee_amount;
+    let deleverage_fee_amount = position.state.deleverage_fee_amount;
+    let swap_router = position.state.swap_router;
+    let realized_profit = position.state.realized_profit;
+
+    // REMAINING SWAP OUTPUT BELONGS TO THE POSITION HOLDER
+    let remaining_amount = position.remaining_output()?;
+
+    msg!("remaining_amount: {:?}", remaining_amount);
+
+    if remaining_amount > 0 {
+        transfer_token(
+            ctx.accounts.keeper_ata.to_account_info(),
+            ctx.accounts.holder_ata.to_account_info(),
+            ctx.accounts.keeper.to_account_info(),
+            ctx.accounts.token_collateral_token_program.to_account_info(),
+            ctx.accounts.token_collateral_token_mint.to_account_info(),
+            remaining_amount,
+            ctx.accounts.token_collateral_token_mint.decimals,
+        )?;
+    }
+
+    position.closing()?;
+    vault.burn(release_unit)?;
+    if repay_unit > 0 {
+        vault.burn_borrow(repay_unit)?;
+    }
+
+    let position_id = position.id;
+    let closed = position.unit == 0;
+    if closed {
+        obligation.close_position(position_id)?;
+    }
+
+    emit!(EventLeverageKeeperClosing {
+        vault: ctx.accounts.vault.key(),
+        user: ctx.accounts.holder.key(),
+        keeper: ctx.accounts.keeper.key(),
+        obligation: ctx.accounts.obligation.key(),
+        position_id,
+        position_number: number,
+        action,
+        release_unit,
+        repay_unit,
+        release_output,
+        repay_borrow_amount,
+        protocol_fee_amount,
+        liquidation_fee_amount,
+        deleverage_fee_amount,
+        remaining_amount,
+        closed,
+        swap_router,
+    });
+
+    if action == LeverageAction::TakeProfit {
+        emit!(EventLeverageProfitTaken {
+            vault: ctx.accounts.vault.key(),
+            user: ctx.accounts.holder.key(),
+            keeper: ctx.accounts.keeper.key(),
+            obligation: ctx.accounts.obligation.key(),
+            position_id,
+            position_number: number,
+            release_output,
+            repay_borrow_amount,
+            protocol_fee_amount,
+            realized_profit,
+        });
+    }
+
+    Ok(())
+}
+
+#[inline(never)]
+fn verify_next_ixs(ctx: &Context<VaultLeverageKeeperClosing>) -> Result<()> {
+    let ixs = ctx.accounts.instructions.to_account_info();
+
+    // make sure this isnt a cpi call
+    let current_index = load_current_index_checked(&ixs)? as usize;
+    let current_ix = load_instruction_at_checked(current_index, &ixs)?;
+    if current_ix.program_id != *ctx.program_id {
+        return Err(Errors::InvalidProgram.into());
+    }
+
+    Ok(())
+}
+
+#[inline(never)]
+fn check_pause(ctx: &Context<VaultLeverageKeeperClosing>, action: LeverageAction) -> Result<()> {
+    // Ejection is never paused so positions of a frozen vault can be unwound
+    require_not_paused(&[
+        ctx.accounts.protocol.load()?.pause_flags,
+        ctx.accounts.leverage_config.load()?.pause_flags,
+    ], PauseFlags::of_action(action))
+}
+
+#[derive(Accounts)]
+pub struct VaultLeverageKeeperClosing<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account()]
+    pub leverage_config: AccountLoader<'info, LeverageConfig>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+        has_one = leverage_config @ Errors::InvalidConfig,
+        has_one = token_collateral_token_program,
+        has_one = token_collateral_token_mint,
+        has_one = native_collateral_token_program,
+        has_one = native_collateral_token_mint,
+    )]
+    pub vault: AccountLoader<'info, VaultLeverage>,
+
+    #[account(
+        mut,
+        seeds = [seeds::OBLIGATION, vault.key().as_ref(), token_collateral_token_mint.key().as_ref(), native_collateral_token_mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub obligation: AccountLoader<'info, Obligation>,
+    /// CHECK: owner of the obligation, validated by the obligation seeds
+    pub owner: UncheckedAccount<'info>,
+    /// CHECK: holder of the position, the owner or the holder of the position nft
+    pub holder: UncheckedAccount<'info>,
+    pub holder_nft_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        associated_token::token_program = token_collateral_token_program,
+        associated_token::mint = token_collateral_token_mint,
+        associated_token::authority = holder,
+    )]
+    pub holder_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    #[account(
+        mut,
+        associated_token::token_program = token_collateral_token_program,
+        associated_token::mint = token_collateral_token_mint,
+        associated_token::authority = keeper,
+    )]
+    pub keeper_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mint::token_program = token_collateral_token_program,
+    )]
+    pub token_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mint::token_program = native_collateral_token_program,
+    )]
+    pub native_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: check instructions account
+    #[account(address = sysvar::instructions::ID @Errors::InvalidAddress)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_collateral_token_program: Interface<'info, TokenInterface>,
+    pub native_collateral_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
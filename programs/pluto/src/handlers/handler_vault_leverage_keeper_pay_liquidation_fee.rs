@@ -0,0 +1,202 @@
+use anchor_lang::{Discriminator};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_spl::token_interface::{TokenInterface, Mint, TokenAccount};
+use crate::error::{ErrorLeverage, Errors};
+use crate::error::ErrorMath::MathOverflow;
+use crate::event::{EventLeverageLiquidationFee};
+use crate::state::{LeverageConfig, Obligation, Protocol, VaultEarn, VaultLeverage};
+use crate::util::{seeds, insurance::pay_fee_with_insurance};
+use crate::util::action::LeverageAction;
+use crate::util::constant::{PERCENT_MAX};
+use crate::util::pause::{require_not_paused, PauseFlags};
+
+#[inline(never)]
+pub fn handle(ctx: Context<VaultLeverageKeeperPayLiquidationFee>, number: u8) -> Result<()> {
+    verify_next_ixs(&ctx)?;
+
+    let config = &ctx.accounts.leverage_config.load()?;
+    let borrow_vault = &mut ctx.accounts.borrow_vault.load_mut()?;
+    let obligation = &mut Obligation::load_with_positions_mut(&ctx.accounts.obligation)?;
+
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("obligation address: {:?}", ctx.accounts.obligation.key());
+
+    let position = obligation.position_mut(number)?;
+
+    check_pause(&ctx, position.state.action)?;
+    require_gt!(position.unit, 0, ErrorLeverage::NoPositionFound);
+    require_keys_eq!(position.state.keeper, ctx.accounts.keeper.key(), ErrorLeverage::InvalidKeeper);
+
+    // Liquidation fee is taken from the swap output, capped by what is left after the repayment
+    let liquidation_fee_amount = (position.state.release_output as u128)
+        .checked_mul(position.state.liquidation_fee as u128).ok_or(MathOverflow)?
+        .checked_div(PERCENT_MAX as u128).ok_or(MathOverflow)? as u64;
+    let liquidation_fee_amount = liquidation_fee_amount.min(position.remaining_output()?);
+
+    let protocol_fee_amount = (liquidation_fee_amount as u128)
+        .checked_mul(position.state.liquidation_protocol_ratio as u128).ok_or(MathOverflow)?
+        .checked_div(PERCENT_MAX as u128).ok_or(MathOverflow)? as u64;
+    let liquidator_fee_amount = liquidation_fee_amount.saturating_sub(protocol_fee_amount);
+
+    msg!("liquidation_fee_amount: {:?}", liquidation_fee_amount);
+    msg!("protocol_fee_amount: {:?} liquidator_fee_amount: {:?}", protocol_fee_amount, liquidator_fee_amount);
+
+    position.pay_liquidation_fee(liquidation_fee_amount)?;
+
+    // Liquidator share stays in the liquidator ata, the protocol share is split with the insurance fund
+    let insurance_amount = config.insurance_amount(protocol_fee_amount)?;
+
+    msg!("insurance_amount: {:?}", insurance_amount);
+
+    pay_fee_with_insurance(
+        ctx.accounts.keeper_ata.to_account_info(),
+        ctx.accounts.leverage_fee_vault.to_account_info(),
+        ctx.accounts.borrow_vault_liquidity.to_account_info(),
+        ctx.accounts.keeper.to_account_info(),
+        ctx.accounts.token_collateral_token_program.to_account_info(),
+        ctx.accounts.token_collateral_token_mint.to_account_info(),
+        ctx.accounts.token_collateral_token_mint.decimals,
+        protocol_fee_amount,
+        insurance_amount,
+    )?;
+    borrow_vault.fund_insurance(insurance_amount)?;
+
+    emit!(EventLeverageLiquidationFee {
+        vault: ctx.accounts.vault.key(),
+        user: ctx.accounts.owner.key(),
+        liquidator: ctx.accounts.keeper.key(),
+        obligation: ctx.accounts.obligation.key(),
+        position_id: position.id,
+        position_number: number,
+        release_output: position.state.release_output,
+        liquidation_fee: position.state.liquidation_fee,
+        liquidation_protocol_ratio: position.state.liquidation_protocol_ratio,
+        liquidation_fee_amount,
+        protocol_fee_amount,
+        liquidator_fee_amount,
+    });
+
+    Ok(())
+}
+
+#[inline(never)]
+fn verify_next_ixs(ctx: &Context<VaultLeverageKeeperPayLiquidationFee>) -> Result<()> {
+    let ixs = ctx.accounts.instructions.to_account_info();
+
+    // make sure this isnt a cpi call
+    let current_index = load_current_index_checked(&ixs)? as usize;
+    let current_ix = load_instruction_at_checked(current_index, &ixs)?;
+    if current_ix.program_id != *ctx.program_id {
+        return Err(Errors::InvalidProgram.into());
+    }
+
+    let mut index = current_index + 1;
+    loop {
+        if let Ok(ix) = load_instruction_at_checked(index, &ixs) {
+            if ix.program_id == crate::id() {
+                let ix_discriminator: [u8; 8] = ix.data[0..8]
+                    .try_into()
+                    .map_err(|_| Errors::UnknownInstruction)?;
+
+                if ix_discriminator == crate::instruction::LeverageVaultKeeperClosing::discriminator() {
+                    break;
+                } else {
+                    return Err(ErrorLeverage::NextInstructionMustBeKeeperClosing.into());
+                }
+            }
+        } else {
+            return Err(ErrorLeverage::MissingKeeperClosing.into());
+        }
+
+        index += 1
+    }
+
+    Ok(())
+}
+
+#[inline(never)]
+fn check_pause(ctx: &Context<VaultLeverageKeeperPayLiquidationFee>, action: LeverageAction) -> Result<()> {
+    // Ejection is never paused so positions of a frozen vault can be unwound
+    require_not_paused(&[
+        ctx.accounts.protocol.load()?.pause_flags,
+        ctx.accounts.leverage_config.load()?.pause_flags,
+    ], PauseFlags::of_action(action))
+}
+
+#[derive(Accounts)]
+pub struct VaultLeverageKeeperPayLiquidationFee<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account(
+        has_one = leverage_fee_vault,
+    )]
+    pub leverage_config: AccountLoader<'info, LeverageConfig>,
+    /// CHECK Safe
+    #[account(mut)]
+    pub leverage_fee_vault: AccountInfo<'info>,
+    #[account(
+        has_one = protocol @ Errors::InvalidProtocol,
+        has_one = leverage_config @ Errors::InvalidConfig,
+        has_one = borrow_vault,
+        has_one = token_collateral_token_program,
+        has_one = token_collateral_token_mint,
+        has_one = native_collateral_token_program,
+        has_one = native_collateral_token_mint,
+    )]
+    pub vault: AccountLoader<'info, VaultLeverage>,
+
+    /// CHECK VAULT FOR BORROWING AUTHORITY
+    #[account(
+        seeds = [seeds::VAULT_EARN_AUTH, borrow_vault.key().as_ref()],
+        bump,
+    )]
+    pub borrow_vault_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+    )]
+    pub borrow_vault: AccountLoader<'info, VaultEarn>,
+    #[account(
+        mut,
+        associated_token::token_program = token_collateral_token_program,
+        associated_token::mint = token_collateral_token_mint,
+        associated_token::authority = borrow_vault_authority,
+    )]
+    pub borrow_vault_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [seeds::OBLIGATION, vault.key().as_ref(), token_collateral_token_mint.key().as_ref(), native_collateral_token_mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub obligation: AccountLoader<'info, Obligation>,
+    /// CHECK: owner of the obligation, validated by the obligation seeds
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    #[account(
+        mut,
+        associated_token::token_program = token_collateral_token_program,
+        associated_token::mint = token_collateral_token_mint,
+        associated_token::authority = keeper,
+    )]
+    pub keeper_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mint::token_program = token_collateral_token_program,
+    )]
+    pub token_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mint::token_program = native_collateral_token_program,
+    )]
+    pub native_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: check instructions account
+    #[account(address = sysvar::instructions::ID @Errors::InvalidAddress)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_collateral_token_program: Interface<'info, TokenInterface>,
+    pub native_collateral_token_program: Interface<'info, TokenInterface>,
+}
\ No newline at end of file
@@ -0,0 +1,199 @@
+use anchor_lang::Discriminator;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_spl::associated_token::{AssociatedToken};
+use anchor_spl::token_interface::{TokenInterface, Mint, TokenAccount};
+use crate::error::{ErrorLeverage, Errors};
+use crate::state::{LeverageConfig, Obligation, Protocol, SwapRouters, VaultLeverage};
+use crate::util::seeds;
+use crate::util::transfer_token::transfer_token_with_signer;
+use crate::util::action::LeverageAction;
+use crate::util::pause::{require_not_paused, PauseFlags};
+
+pub fn handle(ctx: Context<VaultLeverageKeeperRelease>, number: u8) -> Result<()> {
+    let swap_router = verify_next_ixs(&ctx)?;
+
+    let obligation = &mut Obligation::load_with_positions_mut(&ctx.accounts.obligation)?;
+
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("obligation address: {:?}", ctx.accounts.obligation.key());
+    msg!("keeper address: {:?}", ctx.accounts.keeper.key());
+
+    let position = obligation.position_mut(number)?;
+
+    check_pause(&ctx, position.state.action)?;
+    require_gt!(position.unit, 0, ErrorLeverage::NoPositionFound);
+    require!(position.state.is_keeper_action(), ErrorLeverage::InvalidKeeperAction);
+    require_keys_eq!(position.state.keeper, ctx.accounts.keeper.key(), ErrorLeverage::InvalidKeeper);
+
+    // Token collateral balance before the swap, the difference after the swap is the release output
+    let release_balance = ctx.accounts.keeper_token_collateral_ata.amount;
+
+    msg!("release_amount: {:?} release_balance: {:?} swap router: {:?}", position.state.release_amount, release_balance, swap_router);
+
+    position.keeper_release(release_balance)?;
+    position.set_swap_router(swap_router)?;
+
+    let vault_key = ctx.accounts.vault.key();
+    let vault_seeds = &[
+        seeds::VAULT_LEVERAGE_AUTH,
+        vault_key.as_ref(),
+        &[ctx.bumps.vault_authority],
+    ];
+
+    let vault_signer_seeds = &[&vault_seeds[..]];
+
+    // SEND TO KEEPER FOR SWAP
+    transfer_token_with_signer(
+        ctx.accounts.native_collateral_vault_liquidity.to_account_info(),
+        ctx.accounts.keeper_ata.to_account_info(),
+        ctx.accounts.vault_authority.to_account_info(),
+        ctx.accounts.native_collateral_token_program.to_account_info(),
+        ctx.accounts.native_collateral_token_mint.to_account_info(),
+        position.state.release_amount,
+        ctx.accounts.native_collateral_token_mint.decimals,
+        vault_signer_seeds,
+    )?;
+
+    Ok(())
+}
+
+#[inline(never)]
+fn verify_next_ixs(ctx: &Context<VaultLeverageKeeperRelease>) -> Result<Pubkey> {
+    let ixs = ctx.accounts.instructions.to_account_info();
+
+    // make sure this isnt a cpi call
+    let current_index = load_current_index_checked(&ixs)? as usize;
+    let current_ix = load_instruction_at_checked(current_index, &ixs)?;
+    if current_ix.program_id != *ctx.program_id {
+        return Err(Errors::InvalidProgram.into());
+    }
+
+    // loop through instructions, looking for the swap followed by the keeper repay borrow
+    let mut index = current_index + 1;
+    let mut swap_router = None;
+    loop {
+        if let Ok(ix) = load_instruction_at_checked(index, &ixs) {
+            if SwapRouters::is_allowed_swap(&ctx.accounts.swap_routers, &ix)? {
+                swap_router.get_or_insert(ix.program_id);
+                index += 1;
+                continue;
+            }
+            if ix.program_id == crate::id() {
+                if swap_router.is_none() {
+                    return Err(ErrorLeverage::MissingJupiterSwap.into());
+                }
+                let ix_discriminator: [u8; 8] = ix.data[0..8]
+                    .try_into()
+                    .map_err(|_| Errors::UnknownInstruction)?;
+
+                if ix_discriminator == crate::instruction::LeverageVaultKeeperRepayBorrow::discriminator() {
+                    break;
+                } else {
+                    return Err(ErrorLeverage::NextInstructionMustBeKeeperRepayBorrow.into());
+                }
+            }
+        } else {
+            return Err(ErrorLeverage::MissingKeeperRepayBorrow.into());
+        }
+
+        index += 1
+    }
+
+    swap_router.ok_or(ErrorLeverage::MissingJupiterSwap.into())
+}
+
+#[inline(never)]
+fn check_pause(ctx: &Context<VaultLeverageKeeperRelease>, action: LeverageAction) -> Result<()> {
+    // Ejection is never paused so positions of a frozen vault can be unwound
+    require_not_paused(&[
+        ctx.accounts.protocol.load()?.pause_flags,
+        ctx.accounts.leverage_config.load()?.pause_flags,
+    ], PauseFlags::of_action(action))
+}
+
+#[derive(Accounts)]
+pub struct VaultLeverageKeeperRelease<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account()]
+    pub leverage_config: AccountLoader<'info, LeverageConfig>,
+    /// CHECK VAULT LEVERAGE AUTHORITY
+    #[account(
+        seeds = [seeds::VAULT_LEVERAGE_AUTH, vault.key().as_ref()],
+        bump,
+    )]
+    pub vault_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+        has_one = leverage_config @ Errors::InvalidConfig,
+        has_one = token_collateral_token_program,
+        has_one = token_collateral_token_mint,
+        has_one = native_collateral_token_program,
+        has_one = native_collateral_token_mint,
+    )]
+    pub vault: AccountLoader<'info, VaultLeverage>,
+
+    #[account(
+        mut,
+        seeds = [seeds::OBLIGATION, vault.key().as_ref(), token_collateral_token_mint.key().as_ref(), native_collateral_token_mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub obligation: AccountLoader<'info, Obligation>,
+    /// CHECK: owner of the obligation, validated by the obligation seeds
+    pub owner: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        associated_token::token_program = native_collateral_token_program,
+        associated_token::mint = native_collateral_token_mint,
+        associated_token::authority = vault_authority,
+    )]
+    pub native_collateral_vault_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        associated_token::token_program = native_collateral_token_program,
+        associated_token::mint = native_collateral_token_mint,
+        associated_token::authority = keeper
+    )]
+    pub keeper_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        associated_token::token_program = token_collateral_token_program,
+        associated_token::mint = token_collateral_token_mint,
+        associated_token::authority = keeper
+    )]
+    pub keeper_token_collateral_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mint::token_program = token_collateral_token_program,
+    )]
+    pub token_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mint::token_program = native_collateral_token_program,
+    )]
+    pub native_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: swap routers of the vault, empty until the owner creates them
+    #[account(
+        seeds = [seeds::SWAP_ROUTERS, vault.key().as_ref()],
+        bump,
+    )]
+    pub swap_routers: UncheckedAccount<'info>,
+
+    /// CHECK: check instructions account
+    #[account(address = sysvar::instructions::ID @Errors::InvalidAddress)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_collateral_token_program: Interface<'info, TokenInterface>,
+    pub native_collateral_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -0,0 +1,280 @@
+use anchor_lang::{Discriminator};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_spl::associated_token::{AssociatedToken};
+use anchor_spl::token_interface::{TokenInterface, Mint, TokenAccount};
+use crate::error::{ErrorLeverage, Errors};
+use crate::error::ErrorMath::MathOverflow;
+use crate::event::{EventEarnBadDebt};
+use crate::state::{EarnConfig, LeverageConfig, Obligation, Protocol, VaultEarn, VaultLeverage};
+use crate::util::{decimals, seeds, transfer_token::transfer_token, insurance::pay_fee_with_insurance};
+use crate::util::action::LeverageAction;
+use crate::util::constant::{INDEX_DECIMALS, PERCENT_MAX};
+use crate::util::pause::{require_not_paused, PauseFlags};
+
+#[inline(never)]
+pub fn handle(ctx: Context<VaultLeverageKeeperRepayBorrow>, number: u8) -> Result<()> {
+    let vault = &mut ctx.accounts.vault.load_mut()?;
+    let config = &ctx.accounts.leverage_config.load()?;
+    let earn_config = &ctx.accounts.earn_config.load()?;
+    let borrow_vault = &mut ctx.accounts.borrow_vault.load_mut()?;
+    let obligation = &mut Obligation::load_with_positions_mut(&ctx.accounts.obligation)?;
+
+    // Debt is priced on a borrowing index accrued up to now
+    vault.accrue_interest_from(borrow_vault, earn_config)?;
+
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("borrow_vault address: {:?}", ctx.accounts.borrow_vault.key());
+    msg!("obligation address: {:?}", ctx.accounts.obligation.key());
+
+    let position = obligation.position_mut(number)?;
+
+    check_pause(&ctx, position.state.action)?;
+    require_gt!(position.unit, 0, ErrorLeverage::NoPositionFound);
+    require!(position.state.is_keeper_action(), ErrorLeverage::InvalidKeeperAction);
+    require_keys_eq!(position.state.keeper, ctx.accounts.keeper.key(), ErrorLeverage::InvalidKeeper);
+
+    verify_next_ixs(&ctx, position.state.action)?;
+
+    let release_output = ctx.accounts.keeper_ata.amount.saturating_sub(position.state.release_balance);
+
+    msg!("release_output: {:?} release_min_output: {:?}", release_output, position.state.release_min_output);
+
+    require_gte!(release_output, position.state.release_min_output, ErrorLeverage::SlippageReached);
+
+    let utilization_rate = borrow_vault.utilization_rate()?;
+
+    // REPAY BORROW, AS MUCH AS THE SWAP OUTPUT COVERS
+    let borrowing_amount = position.state.repay_amount.min(release_output);
+
+    msg!("borrowing_amount: {:?}", borrowing_amount);
+
+    if borrowing_amount > 0 {
+        transfer_token(
+            ctx.accounts.keeper_ata.to_account_info(),
+            ctx.accounts.borrow_vault_liquidity.to_account_info(),
+            ctx.accounts.keeper.to_account_info(),
+            ctx.accounts.token_collateral_token_program.to_account_info(),
+            ctx.accounts.token_collateral_token_mint.to_account_info(),
+            borrowing_amount,
+            ctx.accounts.token_collateral_token_mint.decimals,
+        )?;
+    }
+
+    position.keeper_repay_borrow(release_output, borrowing_amount)?;
+    if position.state.repay_unit > 0 {
+        borrow_vault.deleverage(position.state.repay_unit)?;
+    }
+
+    // BAD DEBT, THE SWAP OUTPUT DID NOT COVER THE BORROW
+    let shortfall_amount = position.state.repay_amount.saturating_sub(borrowing_amount);
+    if shortfall_amount > 0 {
+        let bad_debt = borrow_vault.cover_bad_debt(shortfall_amount)?;
+
+        msg!("shortfall_amount: {:?} covered_amount: {:?} socialized_amount: {:?}", shortfall_amount, bad_debt.covered_amount, bad_debt.socialized_amount);
+
+        emit!(EventEarnBadDebt {
+            vault: ctx.accounts.borrow_vault.key(),
+            leverage_vault: ctx.accounts.vault.key(),
+            obligation: ctx.accounts.obligation.key(),
+            position_id: position.id,
+            position_number: number,
+            shortfall_amount,
+            covered_amount: bad_debt.covered_amount,
+            socialized_amount: bad_debt.socialized_amount,
+            insurance_fund_amount: borrow_vault.insurance_fund_amount,
+            old_index: bad_debt.old_index,
+            index: borrow_vault.index,
+        });
+    }
+
+    // PAY PROTOCOL
+    let protocol_fee_factor = vault.protocol_fee_factor(config.protocol_fee, utilization_rate, position.avg_borrowing_index, vault.borrowing_index)?;
+
+    msg!("utilization_rate: {:?}", utilization_rate);
+    msg!("protocol_fee_factor: {:?}", protocol_fee_factor);
+
+    let protocol_fee_amount = (decimals::mul_ceil(
+        vault.token_collateral_token_decimal, position.state.release_min_output as u128, vault.token_collateral_token_decimal,
+        protocol_fee_factor, INDEX_DECIMALS
+    )? as u64).saturating_div(100).min(position.remaining_output()?);
+
+    let insurance_amount = config.insurance_amount(protocol_fee_amount)?;
+
+    pay_fee_with_insurance(
+        ctx.accounts.keeper_ata.to_account_info(),
+        ctx.accounts.leverage_fee_vault.to_account_info(),
+        ctx.accounts.borrow_vault_liquidity.to_account_info(),
+        ctx.accounts.keeper.to_account_info(),
+        ctx.accounts.token_collateral_token_program.to_account_info(),
+        ctx.accounts.token_collateral_token_mint.to_account_info(),
+        ctx.accounts.token_collateral_token_mint.decimals,
+        protocol_fee_amount,
+        insurance_amount,
+    )?;
+    borrow_vault.fund_insurance(insurance_amount)?;
+
+    msg!("protocol_fee_amount: {:?} insurance_amount: {:?}", protocol_fee_amount, insurance_amount);
+
+    position.pay_protocol_fee(utilization_rate, protocol_fee_factor, protocol_fee_amount)?;
+
+    // PAY DELEVERAGE FEE
+    if position.state.action == LeverageAction::Deleverage {
+        let deleverage_fee_amount = ((release_output as u128)
+            .checked_mul(position.state.deleverage_fee as u128).ok_or(MathOverflow)?
+            .checked_div(PERCENT_MAX as u128).ok_or(MathOverflow)? as u64)
+            .min(position.remaining_output()?);
+
+        if deleverage_fee_amount > 0 {
+            transfer_token(
+                ctx.accounts.keeper_ata.to_account_info(),
+                ctx.accounts.leverage_fee_vault.to_account_info(),
+                ctx.accounts.keeper.to_account_info(),
+                ctx.accounts.token_collateral_token_program.to_account_info(),
+                ctx.accounts.token_collateral_token_mint.to_account_info(),
+                deleverage_fee_amount,
+                ctx.accounts.token_collateral_token_mint.decimals,
+            )?;
+        }
+
+        msg!("deleverage_fee_amount: {:?}", deleverage_fee_amount);
+
+        position.pay_deleverage_fee(deleverage_fee_amount)?;
+    }
+
+    Ok(())
+}
+
+#[inline(never)]
+fn verify_next_ixs(ctx: &Context<VaultLeverageKeeperRepayBorrow>, action: LeverageAction) -> Result<()> {
+    let ixs = ctx.accounts.instructions.to_account_info();
+
+    // make sure this isnt a cpi call
+    let current_index = load_current_index_checked(&ixs)? as usize;
+    let current_ix = load_instruction_at_checked(current_index, &ixs)?;
+    if current_ix.program_id != *ctx.program_id {
+        return Err(Errors::InvalidProgram.into());
+    }
+
+    // liquidation must pay the liquidation fee before closing
+    let next_discriminator = if action == LeverageAction::Liquidate {
+        crate::instruction::LeverageVaultKeeperPayLiquidationFee::discriminator()
+    } else {
+        crate::instruction::LeverageVaultKeeperClosing::discriminator()
+    };
+
+    let mut index = current_index + 1;
+    loop {
+        if let Ok(ix) = load_instruction_at_checked(index, &ixs) {
+            if ix.program_id == crate::id() {
+                let ix_discriminator: [u8; 8] = ix.data[0..8]
+                    .try_into()
+                    .map_err(|_| Errors::UnknownInstruction)?;
+
+                if ix_discriminator == next_discriminator {
+                    break;
+                } else {
+                    return Err(ErrorLeverage::NextInstructionMustBeKeeperClosingOrKeeperPayLiquidationFee.into());
+                }
+            }
+        } else {
+            return Err(ErrorLeverage::MissingKeeperClosing.into());
+        }
+
+        index += 1
+    }
+
+    Ok(())
+}
+
+#[inline(never)]
+fn check_pause(ctx: &Context<VaultLeverageKeeperRepayBorrow>, action: LeverageAction) -> Result<()> {
+    // Ejection is never paused so positions of a frozen vault can be unwound
+    require_not_paused(&[
+        ctx.accounts.protocol.load()?.pause_flags,
+        ctx.accounts.leverage_config.load()?.pause_flags,
+    ], PauseFlags::of_action(action))
+}
+
+#[derive(Accounts)]
+pub struct VaultLeverageKeeperRepayBorrow<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account(
+        has_one = leverage_fee_vault,
+    )]
+    pub leverage_config: AccountLoader<'info, LeverageConfig>,
+    /// CHECK Safe
+    #[account(mut)]
+    pub leverage_fee_vault: AccountInfo<'info>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+        has_one = leverage_config @ Errors::InvalidConfig,
+        has_one = borrow_vault,
+        has_one = token_collateral_token_program,
+        has_one = token_collateral_token_mint,
+        has_one = native_collateral_token_program,
+        has_one = native_collateral_token_mint,
+    )]
+    pub vault: AccountLoader<'info, VaultLeverage>,
+    #[account()]
+    pub earn_config: AccountLoader<'info, EarnConfig>,
+
+    /// CHECK VAULT FOR BORROWING AUTHORITY
+    #[account(
+        seeds = [seeds::VAULT_EARN_AUTH, borrow_vault.key().as_ref()],
+        bump,
+    )]
+    pub borrow_vault_authority: AccountInfo<'info>,
+    #[account(
+        mut,
+        has_one = earn_config @ Errors::InvalidConfig,
+    )]
+    pub borrow_vault: AccountLoader<'info, VaultEarn>,
+    #[account(
+        mut,
+        associated_token::token_program = token_collateral_token_program,
+        associated_token::mint = token_collateral_token_mint,
+        associated_token::authority = borrow_vault_authority,
+    )]
+    pub borrow_vault_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [seeds::OBLIGATION, vault.key().as_ref(), token_collateral_token_mint.key().as_ref(), native_collateral_token_mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub obligation: AccountLoader<'info, Obligation>,
+    /// CHECK: owner of the obligation, validated by the obligation seeds
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    #[account(
+        mut,
+        associated_token::token_program = token_collateral_token_program,
+        associated_token::mint = token_collateral_token_mint,
+        associated_token::authority = keeper,
+    )]
+    pub keeper_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mint::token_program = token_collateral_token_program,
+    )]
+    pub token_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mint::token_program = native_collateral_token_program,
+    )]
+    pub native_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: check instructions account
+    #[account(address = sysvar::instructions::ID @Errors::InvalidAddress)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_collateral_token_program: Interface<'info, TokenInterface>,
+    pub native_collateral_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
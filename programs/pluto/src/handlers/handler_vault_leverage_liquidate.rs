@@ -0,0 +1,220 @@
+use anchor_lang::Discriminator;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_spl::token_interface::{TokenInterface, Mint};
+use crate::error::{ErrorLeverage, Errors};
+use crate::error::ErrorMath::MathOverflow;
+use crate::event::{EventLeverageLiquidate};
+use crate::state::{EarnConfig, LeverageConfig, Obligation, Protocol, VaultEarn, VaultLeverage};
+use crate::util::{oracle, seeds};
+use crate::util::action::LeverageAction;
+use crate::util::constant::{HEALTH_FACTOR_ONE, LIQUIDATION_HF_THRESHOLD, PERCENT_MAX};
+use crate::util::pause::{require_not_paused, PauseFlags};
+
+pub fn handle(ctx: Context<VaultLeverageLiquidate>, number: u8) -> Result<()> {
+    verify_next_ixs(&ctx)?;
+    check_pause(&ctx)?;
+
+    let config = &ctx.accounts.leverage_config.load()?;
+    let earn_config = &ctx.accounts.earn_config.load()?;
+    let vault = &mut ctx.accounts.vault.load_mut()?;
+    let borrow_vault = &mut ctx.accounts.borrow_vault.load_mut()?;
+    let obligation = &mut Obligation::load_with_positions_mut(&ctx.accounts.obligation)?;
+
+    // Debt is priced on a borrowing index accrued up to now
+    vault.accrue_interest_from(borrow_vault, earn_config)?;
+
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("obligation address: {:?}", ctx.accounts.obligation.key());
+    msg!("liquidator address: {:?}", ctx.accounts.liquidator.key());
+
+    msg!("vault index: {:?}", vault.index);
+    msg!("vault borrowing index: {:?}", vault.borrowing_index);
+
+    let position = obligation.position_mut(number)?;
+
+    require_gt!(position.unit, 0, ErrorLeverage::NoPositionFound);
+
+    // Position must not be in the middle of another process
+    position.halt_on_leveraging()?;
+    position.halt_on_deleveraging()?;
+
+    let (token_collateral_price, token_collateral_price_exponent, token_collateral_price_sources) = vault.token_collateral_price(&ctx.accounts.token_collateral_price_oracle, ctx.remaining_accounts)?;
+    let (native_collateral_price, native_collateral_price_exponent, native_collateral_price_sources) = vault.native_collateral_price(&ctx.accounts.native_collateral_price_oracle, ctx.remaining_accounts)?;
+
+    msg!("token_collateral_price: {:?} exponent: {:?}", token_collateral_price, token_collateral_price_exponent);
+    msg!("native_collateral_price: {:?} exponent: {:?}", native_collateral_price, native_collateral_price_exponent);
+
+    let release_amount = position.collateral_amount(vault.native_collateral_token_decimal, vault.index)?;
+    let repay_amount = position.borrowing_amount(vault.token_collateral_token_decimal, vault.borrowing_index)?;
+
+    let native_collateral_value = oracle::value(release_amount, vault.native_collateral_token_decimal, native_collateral_price, native_collateral_price_exponent)?;
+    let borrowing_value = oracle::value(repay_amount, vault.token_collateral_token_decimal, token_collateral_price, token_collateral_price_exponent)?;
+
+    let health_factor = position.health_factor(native_collateral_value, borrowing_value, config.liquidation_threshold)?;
+
+    msg!("native_collateral_value: {:?} borrowing_value: {:?}", native_collateral_value, borrowing_value);
+    msg!("health_factor: {:?}", health_factor);
+
+    require!(health_factor < (LIQUIDATION_HF_THRESHOLD as u32).checked_mul(HEALTH_FACTOR_ONE).ok_or(MathOverflow)?, ErrorLeverage::UnmetHealthFactorThreshold);
+
+    // Expected token collateral out of the swap, minus the slippage allowed to the caller
+    let expected_output = oracle::amount(native_collateral_value, vault.token_collateral_token_decimal, token_collateral_price, token_collateral_price_exponent)?;
+    let slippage_rate = config.slippage_rate_for(ctx.accounts.liquidator.key());
+    let release_min_output = (expected_output as u128)
+        .checked_mul(PERCENT_MAX.checked_sub(slippage_rate).ok_or(MathOverflow)? as u128).ok_or(MathOverflow)?
+        .checked_div(PERCENT_MAX as u128).ok_or(MathOverflow)? as u64;
+
+    msg!("release_amount: {:?} repay_amount: {:?} release_min_output: {:?}", release_amount, repay_amount, release_min_output);
+
+    position.set_action(LeverageAction::Liquidate)?;
+    position.set_config(config)?;
+    position.set_oracle(
+        vault.token_collateral_price_oracle, vault.token_collateral_price_feed, token_collateral_price, token_collateral_price_exponent,
+        vault.native_collateral_price_oracle, vault.native_collateral_price_feed, native_collateral_price, native_collateral_price_exponent,
+    )?;
+    position.set_health_factor(health_factor)?;
+    position.set_keeper(ctx.accounts.liquidator.key())?;
+    position.release(
+        release_amount, position.unit, vault.index,
+        PERCENT_MAX,
+        repay_amount, position.borrowing_unit, vault.borrowing_index,
+        release_min_output,
+    )?;
+
+    emit!(EventLeverageLiquidate {
+        vault: ctx.accounts.vault.key(),
+        user: ctx.accounts.owner.key(),
+        liquidator: ctx.accounts.liquidator.key(),
+        obligation: ctx.accounts.obligation.key(),
+        position_id: position.id,
+        position_number: number,
+        token_collateral_price,
+        token_collateral_price_exponent,
+        native_collateral_price,
+        native_collateral_price_exponent,
+        token_collateral_price_sources,
+        native_collateral_price_sources,
+        health_factor,
+        liquidation_threshold: config.liquidation_threshold,
+        release_amount,
+        release_unit: position.state.release_unit,
+        release_index: position.state.release_index,
+        repay_amount,
+        repay_unit: position.state.repay_unit,
+        repay_index: position.state.repay_index,
+        release_min_output,
+    });
+
+    Ok(())
+}
+
+#[inline(never)]
+fn verify_next_ixs(ctx: &Context<VaultLeverageLiquidate>) -> Result<()> {
+    let ixs = ctx.accounts.instructions.to_account_info();
+
+    // make sure this isnt a cpi call
+    let current_index = load_current_index_checked(&ixs)? as usize;
+    let current_ix = load_instruction_at_checked(current_index, &ixs)?;
+    if current_ix.program_id != *ctx.program_id {
+        return Err(Errors::InvalidProgram.into());
+    }
+
+    // next instruction of this program must be the keeper release
+    let mut index = current_index + 1;
+    loop {
+        if let Ok(ix) = load_instruction_at_checked(index, &ixs) {
+            if ix.program_id == crate::id() {
+                let ix_discriminator: [u8; 8] = ix.data[0..8]
+                    .try_into()
+                    .map_err(|_| Errors::UnknownInstruction)?;
+
+                if ix_discriminator == crate::instruction::LeverageVaultKeeperRelease::discriminator() {
+                    break;
+                } else {
+                    return Err(ErrorLeverage::NextInstructionMustBeKeeperRelease.into());
+                }
+            }
+        } else {
+            return Err(ErrorLeverage::MissingKeeperRelease.into());
+        }
+
+        index += 1
+    }
+
+    Ok(())
+}
+
+#[inline(never)]
+fn check_pause(ctx: &Context<VaultLeverageLiquidate>) -> Result<()> {
+    require_not_paused(&[
+        ctx.accounts.protocol.load()?.pause_flags,
+        ctx.accounts.leverage_config.load()?.pause_flags,
+    ], PauseFlags::LIQUIDATE)
+}
+
+#[derive(Accounts)]
+pub struct VaultLeverageLiquidate<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account()]
+    pub leverage_config: AccountLoader<'info, LeverageConfig>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+        has_one = leverage_config @ Errors::InvalidConfig,
+        has_one = borrow_vault,
+        has_one = token_collateral_token_program,
+        has_one = token_collateral_token_mint,
+        has_one = native_collateral_token_program,
+        has_one = native_collateral_token_mint,
+    )]
+    pub vault: AccountLoader<'info, VaultLeverage>,
+    #[account()]
+    pub earn_config: AccountLoader<'info, EarnConfig>,
+    #[account(
+        mut,
+        has_one = earn_config @ Errors::InvalidConfig,
+    )]
+    pub borrow_vault: AccountLoader<'info, VaultEarn>,
+
+    #[account(
+        mut,
+        seeds = [seeds::OBLIGATION, vault.key().as_ref(), token_collateral_token_mint.key().as_ref(), native_collateral_token_mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub obligation: AccountLoader<'info, Obligation>,
+    /// CHECK: owner of the obligation, validated by the obligation seeds
+    pub owner: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub liquidator: Signer<'info>,
+
+    /// CHECK: price oracle of the vault, read according to its oracle source
+    #[account(
+        address = vault.load()?.token_collateral_price_oracle @ Errors::InvalidPriceOracle,
+    )]
+    pub token_collateral_price_oracle: UncheckedAccount<'info>,
+    /// CHECK: price oracle of the vault, read according to its oracle source
+    #[account(
+        address = vault.load()?.native_collateral_price_oracle @ Errors::InvalidPriceOracle,
+    )]
+    pub native_collateral_price_oracle: UncheckedAccount<'info>,
+
+    #[account(
+        mint::token_program = token_collateral_token_program,
+    )]
+    pub token_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mint::token_program = native_collateral_token_program,
+    )]
+    pub native_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: check instructions account
+    #[account(address = sysvar::instructions::ID @Errors::InvalidAddress)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_collateral_token_program: Interface<'info, TokenInterface>,
+    pub native_collateral_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
@@ -0,0 +1,211 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::{
+    create_master_edition_v3, create_metadata_accounts_v3, CreateMasterEditionV3, CreateMetadataAccountsV3, Metadata,
+    mpl_token_metadata::types::DataV2,
+};
+use anchor_spl::token::Token;
+use anchor_spl::token_interface::{mint_to, Mint, MintTo, TokenAccount};
+use crate::error::{ErrorLeverage, Errors};
+use crate::event::{EventLeverageMintPositionNft};
+use crate::state::{LeverageConfig, Obligation, Protocol, VaultLeverage};
+use crate::util::{oracle, seeds};
+use crate::util::common::{find_master_edition_account, find_metadata_account};
+use crate::util::constant::{LEVERAGE_ONE, POSITION_NFT_NAME, POSITION_NFT_SYMBOL};
+use crate::util::pause::{require_not_paused, PauseFlags};
+
+pub fn handle(ctx: Context<VaultLeverageMintPositionNft>, number: u8, uri: String) -> Result<()> {
+    check_pause(&ctx)?;
+
+    let vault = &ctx.accounts.vault.load()?;
+    let obligation = &mut Obligation::load_with_positions_mut(&ctx.accounts.obligation)?;
+
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("obligation address: {:?}", ctx.accounts.obligation.key());
+
+    let position = obligation.position_mut(number)?;
+
+    require_gt!(position.unit, 0, ErrorLeverage::NoPositionFound);
+    require_keys_eq!(position.nft_mint, Pubkey::default(), ErrorLeverage::PositionNftAlreadyMinted);
+
+    // Position must not be in the middle of another process
+    position.halt_on_leveraging()?;
+    position.halt_on_deleveraging()?;
+
+    // Entry leverage at the prices the position was last opened with
+    let collateral_open_amount = position.collateral_open_amount(vault.native_collateral_token_decimal)?;
+    let borrowing_open_amount = position.borrowing_open_amount(vault.token_collateral_token_decimal)?;
+    let native_collateral_value = oracle::value(collateral_open_amount, vault.native_collateral_token_decimal, position.native_collateral_price, position.native_collateral_price_exponent)?;
+    let borrowing_value = oracle::value(borrowing_open_amount, vault.token_collateral_token_decimal, position.token_collateral_price, position.token_collateral_price_exponent)?;
+    let entry_leverage = position.current_leverage(native_collateral_value, borrowing_value)?;
+
+    let name = format!("{} {}.{:02}x", POSITION_NFT_NAME, entry_leverage / LEVERAGE_ONE, (entry_leverage % LEVERAGE_ONE) / 10);
+
+    msg!("nft mint: {:?} entry_leverage: {:?}", ctx.accounts.nft_mint.key(), entry_leverage);
+
+    let vault_key = ctx.accounts.vault.key();
+    let seeds = &[
+        seeds::VAULT_LEVERAGE_AUTH,
+        vault_key.as_ref(),
+        &[ctx.bumps.vault_authority],
+    ];
+
+    let signer_seeds = &[&seeds[..]];
+
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.nft_token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.nft_mint.to_account_info(),
+                to: ctx.accounts.owner_nft_account.to_account_info(),
+                authority: ctx.accounts.vault_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        1,
+    )?;
+
+    create_metadata_accounts_v3(
+        CpiContext::new_with_signer(
+            ctx.accounts.metadata_program.to_account_info(),
+            CreateMetadataAccountsV3 {
+                metadata: ctx.accounts.metadata.to_account_info(),
+                mint: ctx.accounts.nft_mint.to_account_info(),
+                mint_authority: ctx.accounts.vault_authority.to_account_info(),
+                payer: ctx.accounts.owner.to_account_info(),
+                update_authority: ctx.accounts.vault_authority.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        DataV2 {
+            name,
+            symbol: POSITION_NFT_SYMBOL.to_string(),
+            uri,
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        },
+        false,
+        true,
+        None,
+    )?;
+
+    // Master edition with zero supply keeps the position nft 1 of 1
+    create_master_edition_v3(
+        CpiContext::new_with_signer(
+            ctx.accounts.metadata_program.to_account_info(),
+            CreateMasterEditionV3 {
+                edition: ctx.accounts.master_edition.to_account_info(),
+                mint: ctx.accounts.nft_mint.to_account_info(),
+                update_authority: ctx.accounts.vault_authority.to_account_info(),
+                mint_authority: ctx.accounts.vault_authority.to_account_info(),
+                payer: ctx.accounts.owner.to_account_info(),
+                metadata: ctx.accounts.metadata.to_account_info(),
+                token_program: ctx.accounts.nft_token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        Some(0),
+    )?;
+
+    position.set_nft_mint(ctx.accounts.nft_mint.key())?;
+
+    emit!(EventLeverageMintPositionNft {
+        vault: ctx.accounts.vault.key(),
+        user: ctx.accounts.owner.key(),
+        obligation: ctx.accounts.obligation.key(),
+        position_id: position.id,
+        position_number: number,
+        nft_mint: ctx.accounts.nft_mint.key(),
+        metadata: ctx.accounts.metadata.key(),
+        entry_leverage,
+        token_collateral_price: position.token_collateral_price,
+        token_collateral_price_exponent: position.token_collateral_price_exponent,
+        native_collateral_price: position.native_collateral_price,
+        native_collateral_price_exponent: position.native_collateral_price_exponent,
+    });
+
+    Ok(())
+}
+
+#[inline(never)]
+fn check_pause(ctx: &Context<VaultLeverageMintPositionNft>) -> Result<()> {
+    require_not_paused(&[
+        ctx.accounts.protocol.load()?.pause_flags,
+        ctx.accounts.leverage_config.load()?.pause_flags,
+    ], PauseFlags::OPEN)
+}
+
+#[derive(Accounts)]
+pub struct VaultLeverageMintPositionNft<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account()]
+    pub leverage_config: AccountLoader<'info, LeverageConfig>,
+    /// CHECK VAULT LEVERAGE AUTHORITY
+    #[account(
+        seeds = [seeds::VAULT_LEVERAGE_AUTH, vault.key().as_ref()],
+        bump,
+    )]
+    pub vault_authority: AccountInfo<'info>,
+    #[account(
+        has_one = protocol @ Errors::InvalidProtocol,
+        has_one = leverage_config @ Errors::InvalidConfig,
+        has_one = token_collateral_token_mint,
+        has_one = native_collateral_token_mint,
+    )]
+    pub vault: AccountLoader<'info, VaultLeverage>,
+
+    #[account(
+        mut,
+        seeds = [seeds::OBLIGATION, vault.key().as_ref(), token_collateral_token_mint.key().as_ref(), native_collateral_token_mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub obligation: AccountLoader<'info, Obligation>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        mint::decimals = 0,
+        mint::authority = vault_authority,
+        mint::freeze_authority = vault_authority,
+        mint::token_program = nft_token_program,
+    )]
+    pub nft_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init,
+        payer = owner,
+        associated_token::token_program = nft_token_program,
+        associated_token::mint = nft_mint,
+        associated_token::authority = owner,
+    )]
+    pub owner_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: metadata account of the nft mint, created by the metadata program
+    #[account(
+        mut,
+        address = find_metadata_account(&nft_mint.key()).0 @ Errors::InvalidAddress,
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    /// CHECK: master edition account of the nft mint, created by the metadata program
+    #[account(
+        mut,
+        address = find_master_edition_account(&nft_mint.key()).0 @ Errors::InvalidAddress,
+    )]
+    pub master_edition: UncheckedAccount<'info>,
+
+    pub token_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub native_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub nft_token_program: Program<'info, Token>,
+    pub metadata_program: Program<'info, Metadata>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
\ No newline at end of file
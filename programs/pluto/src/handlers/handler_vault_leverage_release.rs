@@ -9,17 +9,18 @@ use crate::error::{ErrorLeverage, Errors};
 use crate::error::ErrorMath::MathOverflow;
 use crate::event::{EventLeverageRelease};
 use crate::handlers::{VaultLeverageClose, VaultLeverageKeeperClosing};
-use crate::state::{EarnConfig, InitObligationParams, InitPositionParams, LeverageConfig, Obligation, Position, Protocol, VaultEarn, VaultLeverage};
-use crate::util::{constant, decimals, seeds, transfer_token::transfer_token};
+use crate::state::{EarnConfig, InitObligationParams, InitPositionParams, LeverageConfig, Obligation, Position, Protocol, SwapRouters, VaultEarn, VaultLeverage};
+use crate::util::{decimals, seeds, transfer_token::transfer_token};
 use crate::util::transfer_token::transfer_token_with_signer;
-use crate::util::constant::{PERCENT_DECIMALS, LEVERAGE_ONE, INDEX_DECIMALS, MAX_ORACLE_AGE, UNIT_DECIMALS, PERCENT_MAX, MAX_OBLIGATION_POSITIONS};
+use crate::util::constant::{PERCENT_DECIMALS, LEVERAGE_ONE, INDEX_DECIMALS, MAX_ORACLE_AGE, UNIT_DECIMALS, PERCENT_MAX};
+use crate::util::pause::{require_not_paused, PauseFlags};
 
 pub fn handle(ctx: Context<VaultLeverageRelease>, number: u8) -> Result<()> {
-    verify_next_ixs(&ctx)?;
-    check_freeze(&ctx)?;
+    let swap_router = verify_next_ixs(&ctx)?;
+    check_pause(&ctx)?;
 
     let vault = &mut ctx.accounts.vault.load_mut()?;
-    let obligation = &mut ctx.accounts.obligation.load_mut()?;
+    let obligation = &mut Obligation::load_with_positions_mut(&ctx.accounts.obligation)?;
 
     msg!("vault address: {:?}", ctx.accounts.vault.key());
     msg!("obligation address: {:?}", ctx.accounts.obligation.key());
@@ -27,11 +28,14 @@ pub fn handle(ctx: Context<VaultLeverageRelease>, number: u8) -> Result<()> {
     msg!("vault index: {:?}", vault.index);
     msg!("vault borrowing index: {:?}", vault.borrowing_index);
 
-    require!(number < MAX_OBLIGATION_POSITIONS, ErrorLeverage::InvalidPositionNumber);
-
-    let position = &mut obligation.positions[number as usize];
+    let position = obligation.position_mut(number)?;
 
     require_gt!(position.unit, 0, ErrorLeverage::NoPositionFound);
+    position.require_holder(ctx.accounts.owner.key(), ctx.accounts.user.key(), &ctx.accounts.user_nft_account)?;
+
+    msg!("swap router: {:?}", swap_router);
+
+    position.set_swap_router(swap_router)?;
 
     let vault_key = ctx.accounts.vault.key();
     let vault_seeds = &[
@@ -58,7 +62,7 @@ pub fn handle(ctx: Context<VaultLeverageRelease>, number: u8) -> Result<()> {
 }
 
 #[inline(never)]
-fn verify_next_ixs(ctx: &Context<VaultLeverageRelease>) -> Result<()> {
+fn verify_next_ixs(ctx: &Context<VaultLeverageRelease>) -> Result<Pubkey> {
     let ixs = ctx.accounts.instructions.to_account_info();
 
     // make sure this isnt a cpi call
@@ -70,17 +74,17 @@ fn verify_next_ixs(ctx: &Context<VaultLeverageRelease>) -> Result<()> {
 
     // loop through instructions, looking for an equivalent repay to this borrow
     let mut index = current_index + 1;
-    let mut jup_found = false;
+    let mut swap_router = None;
     loop {
         // get the next instruction, die if theres no more
         if let Ok(ix) = load_instruction_at_checked(index, &ixs) {
-            if ix.program_id == constant::JUPITER_SWAP_PROGRAM_ID {
-                jup_found = true;
+            if SwapRouters::is_allowed_swap(&ctx.accounts.swap_routers, &ix)? {
+                swap_router.get_or_insert(ix.program_id);
                 index += 1;
                 continue;
             }
             if ix.program_id == crate::id() {
-                if !jup_found {
+                if swap_router.is_none() {
                     return Err(ErrorLeverage::MissingJupiterSwap.into());
                 }
                 let ix_discriminator: [u8; 8] = ix.data[0..8]
@@ -102,14 +106,15 @@ fn verify_next_ixs(ctx: &Context<VaultLeverageRelease>) -> Result<()> {
         index += 1
     }
 
-    Ok(())
+    swap_router.ok_or(ErrorLeverage::MissingJupiterSwap.into())
 }
 
 #[inline(never)]
-fn check_freeze(ctx: &Context<VaultLeverageRelease>) -> Result<()> {
-    require!(!(ctx.accounts.protocol.load()?.freeze && !ctx.accounts.protocol.load()?.freeze_leverage), ErrorLeverage::VaultFrozen);
-    require!(!ctx.accounts.leverage_config.load()?.freeze, ErrorLeverage::VaultFrozen);
-    Ok(())
+fn check_pause(ctx: &Context<VaultLeverageRelease>) -> Result<()> {
+    require_not_paused(&[
+        ctx.accounts.protocol.load()?.pause_flags,
+        ctx.accounts.leverage_config.load()?.pause_flags,
+    ], PauseFlags::CLOSE)
 }
 
 #[derive(Accounts)]
@@ -137,12 +142,15 @@ pub struct VaultLeverageRelease<'info> {
 
     #[account(
         mut,
-        seeds = [seeds::OBLIGATION, vault.key().as_ref(), token_collateral_token_mint.key().as_ref(), native_collateral_token_mint.key().as_ref(), user.key().as_ref()],
+        seeds = [seeds::OBLIGATION, vault.key().as_ref(), token_collateral_token_mint.key().as_ref(), native_collateral_token_mint.key().as_ref(), owner.key().as_ref()],
         bump,
     )]
     pub obligation: AccountLoader<'info, Obligation>,
+    /// CHECK: owner of the obligation, validated by the obligation seeds
+    pub owner: UncheckedAccount<'info>,
     #[account(mut)]
     pub user: Signer<'info>,
+    pub user_nft_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     #[account(
         mut,
@@ -170,6 +178,13 @@ pub struct VaultLeverageRelease<'info> {
     )]
     pub native_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
 
+    /// CHECK: swap routers of the vault, empty until the owner creates them
+    #[account(
+        seeds = [seeds::SWAP_ROUTERS, vault.key().as_ref()],
+        bump,
+    )]
+    pub swap_routers: UncheckedAccount<'info>,
+
     /// CHECK: check instructions account
     #[account(address = sysvar::instructions::ID @Errors::InvalidAddress)]
     pub instructions: UncheckedAccount<'info>,
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::error::{ErrorLeverage, Errors};
+use crate::event::{EventVaultLeverageChangedSwapRouter};
+use crate::state::{Protocol, SwapRouters, VaultLeverage};
+use crate::util::seeds;
+
+pub fn handle(ctx: Context<VaultLeverageRemoveSwapRouter>, router: Pubkey) -> Result<()> {
+    let swap_routers = &mut ctx.accounts.swap_routers.load_mut()?;
+
+    swap_routers.remove(router)?;
+
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("swap routers address: {:?}", ctx.accounts.swap_routers.key());
+    msg!("removed router: {:?}", router);
+    msg!("routers: {:?}", swap_routers.routers);
+
+    emit!(EventVaultLeverageChangedSwapRouter {
+        vault: ctx.accounts.vault.key(),
+        swap_routers: ctx.accounts.swap_routers.key(),
+        router,
+        allowed: false,
+        routers: swap_routers.routers,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VaultLeverageRemoveSwapRouter<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account(
+        has_one = protocol @ Errors::InvalidProtocol,
+    )]
+    pub vault: AccountLoader<'info, VaultLeverage>,
+    #[account(
+        mut,
+        seeds = [seeds::SWAP_ROUTERS, vault.key().as_ref()],
+        bump = swap_routers.load()?.bump,
+        constraint = swap_routers.load()?.vault == vault.key() @ ErrorLeverage::InvalidSwapRouters,
+    )]
+    pub swap_routers: AccountLoader<'info, SwapRouters>,
+
+    #[account(mut, address = protocol.load()?.owner @ Errors::NotOwner)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
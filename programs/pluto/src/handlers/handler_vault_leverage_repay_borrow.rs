@@ -12,27 +12,32 @@ use crate::handlers::{VaultLeverageRelease};
 use crate::state::{EarnConfig, InitObligationParams, InitPositionParams, LeverageConfig, Obligation, Position, Protocol, VaultEarn, VaultLeverage};
 use crate::util::{constant, decimals, seeds, transfer_token::transfer_token};
 use crate::util::transfer_token::transfer_token_with_signer;
-use crate::util::constant::{PERCENT_DECIMALS, LEVERAGE_ONE, INDEX_DECIMALS, MAX_ORACLE_AGE, UNIT_DECIMALS, PERCENT_MAX, MAX_OBLIGATION_POSITIONS};
+use crate::util::insurance::pay_fee_with_insurance;
+use crate::util::constant::{PERCENT_DECIMALS, LEVERAGE_ONE, INDEX_DECIMALS, MAX_ORACLE_AGE, UNIT_DECIMALS, PERCENT_MAX};
+use crate::util::pause::{require_not_paused, PauseFlags};
 
 #[inline(never)]
 pub fn handle(ctx: Context<VaultLeverageRepayBorrow>, number: u8) -> Result<()> {
     verify_next_ixs(&ctx)?;
-    check_freeze(&ctx)?;
+    check_pause(&ctx)?;
 
     let vault = &mut ctx.accounts.vault.load_mut()?;
     let config = &ctx.accounts.leverage_config.load()?;
+    let earn_config = &ctx.accounts.earn_config.load()?;
     let borrow_vault = &mut ctx.accounts.borrow_vault.load_mut()?;
-    let obligation = &mut ctx.accounts.obligation.load_mut()?;
+    let obligation = &mut Obligation::load_with_positions_mut(&ctx.accounts.obligation)?;
+
+    // Debt is priced on a borrowing index accrued up to now
+    vault.accrue_interest_from(borrow_vault, earn_config)?;
 
     msg!("vault address: {:?}", ctx.accounts.vault.key());
     msg!("borrow_vault address: {:?}", ctx.accounts.borrow_vault.key());
     msg!("obligation address: {:?}", ctx.accounts.obligation.key());
 
-    require!(number < MAX_OBLIGATION_POSITIONS, ErrorLeverage::InvalidPositionNumber);
-
-    let position = &mut obligation.positions[number as usize];
+    let position = obligation.position_mut(number)?;
 
     require_gt!(position.unit, 0, ErrorLeverage::NoPositionFound);
+    position.require_holder(ctx.accounts.owner.key(), ctx.accounts.user.key(), &ctx.accounts.user_nft_account)?;
 
     let utilization_rate = borrow_vault.utilization_rate()?;
 
@@ -65,19 +70,22 @@ pub fn handle(ctx: Context<VaultLeverageRepayBorrow>, number: u8) -> Result<()>
         protocol_fee_factor, INDEX_DECIMALS
     )? as u64).saturating_div(100);
 
-    if protocol_fee_amount > 0 {
-        transfer_token(
-            ctx.accounts.user_ata.to_account_info(),
-            ctx.accounts.leverage_fee_vault.to_account_info(),
-            ctx.accounts.user.to_account_info(),
-            ctx.accounts.token_collateral_token_program.to_account_info(),
-            ctx.accounts.token_collateral_token_mint.to_account_info(),
-            protocol_fee_amount,
-            ctx.accounts.token_collateral_token_mint.decimals,
-        )?;
-    }
+    let insurance_amount = config.insurance_amount(protocol_fee_amount)?;
+
+    pay_fee_with_insurance(
+        ctx.accounts.user_ata.to_account_info(),
+        ctx.accounts.leverage_fee_vault.to_account_info(),
+        ctx.accounts.borrow_vault_liquidity.to_account_info(),
+        ctx.accounts.user.to_account_info(),
+        ctx.accounts.token_collateral_token_program.to_account_info(),
+        ctx.accounts.token_collateral_token_mint.to_account_info(),
+        ctx.accounts.token_collateral_token_mint.decimals,
+        protocol_fee_amount,
+        insurance_amount,
+    )?;
+    borrow_vault.fund_insurance(insurance_amount)?;
 
-    msg!("protocol_fee_amount: {:?}", protocol_fee_amount);
+    msg!("protocol_fee_amount: {:?} insurance_amount: {:?}", protocol_fee_amount, insurance_amount);
 
     position.pay_protocol_fee(utilization_rate, protocol_fee_factor, protocol_fee_amount)?;
 
@@ -124,10 +132,11 @@ fn verify_next_ixs(ctx: &Context<VaultLeverageRepayBorrow>) -> Result<()> {
 }
 
 #[inline(never)]
-fn check_freeze(ctx: &Context<VaultLeverageRepayBorrow>) -> Result<()> {
-    require!(!(ctx.accounts.protocol.load()?.freeze && !ctx.accounts.protocol.load()?.freeze_leverage), ErrorLeverage::VaultFrozen);
-    require!(!ctx.accounts.leverage_config.load()?.freeze, ErrorLeverage::VaultFrozen);
-    Ok(())
+fn check_pause(ctx: &Context<VaultLeverageRepayBorrow>) -> Result<()> {
+    require_not_paused(&[
+        ctx.accounts.protocol.load()?.pause_flags,
+        ctx.accounts.leverage_config.load()?.pause_flags,
+    ], PauseFlags::CLOSE)
 }
 
 #[derive(Accounts)]
@@ -152,6 +161,8 @@ pub struct VaultLeverageRepayBorrow<'info> {
         has_one = native_collateral_token_mint,
     )]
     pub vault: AccountLoader<'info, VaultLeverage>,
+    #[account()]
+    pub earn_config: AccountLoader<'info, EarnConfig>,
 
     /// CHECK VAULT FOR BORROWING AUTHORITY
     #[account(
@@ -161,6 +172,7 @@ pub struct VaultLeverageRepayBorrow<'info> {
     pub borrow_vault_authority: AccountInfo<'info>,
     #[account(
         mut,
+        has_one = earn_config @ Errors::InvalidConfig,
     )]
     pub borrow_vault: AccountLoader<'info, VaultEarn>,
     #[account(
@@ -174,13 +186,15 @@ pub struct VaultLeverageRepayBorrow<'info> {
     /// CHECK VAULT LEVERAGE AUTHORITY
     #[account(
         mut,
-        seeds = [seeds::OBLIGATION, vault.key().as_ref(), token_collateral_token_mint.key().as_ref(), native_collateral_token_mint.key().as_ref(), user.key().as_ref()],
+        seeds = [seeds::OBLIGATION, vault.key().as_ref(), token_collateral_token_mint.key().as_ref(), native_collateral_token_mint.key().as_ref(), owner.key().as_ref()],
         bump,
     )]
     pub obligation: AccountLoader<'info, Obligation>,
-
+    /// CHECK: owner of the obligation, validated by the obligation seeds
+    pub owner: UncheckedAccount<'info>,
     #[account(mut)]
     pub user: Signer<'info>,
+    pub user_nft_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     #[account(
         mut,
         associated_token::token_program = token_collateral_token_program,
@@ -0,0 +1,243 @@
+use anchor_lang::Discriminator;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_spl::token_interface::{TokenInterface, Mint};
+use crate::error::{ErrorLeverage, Errors};
+use crate::error::ErrorMath::MathOverflow;
+use crate::event::{EventLeverageSave};
+use crate::state::{EarnConfig, LeverageConfig, Obligation, Protocol, VaultEarn, VaultLeverage};
+use crate::util::{decimals, oracle, seeds};
+use crate::util::action::LeverageAction;
+use crate::util::constant::{INDEX_DECIMALS, INDEX_ONE, LEVERAGE_ONE, PERCENT_MAX, UNIT_DECIMALS};
+use crate::util::pause::{require_not_paused, PauseFlags};
+
+pub fn handle(ctx: Context<VaultLeverageSave>, number: u8) -> Result<()> {
+    verify_next_ixs(&ctx)?;
+    check_pause(&ctx)?;
+
+    let config = &ctx.accounts.leverage_config.load()?;
+    let earn_config = &ctx.accounts.earn_config.load()?;
+    let vault = &mut ctx.accounts.vault.load_mut()?;
+    let borrow_vault = &mut ctx.accounts.borrow_vault.load_mut()?;
+    let obligation = &mut Obligation::load_with_positions_mut(&ctx.accounts.obligation)?;
+
+    // Debt is priced on a borrowing index accrued up to now
+    vault.accrue_interest_from(borrow_vault, earn_config)?;
+
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("obligation address: {:?}", ctx.accounts.obligation.key());
+
+    let position = obligation.position_mut(number)?;
+
+    require_gt!(position.unit, 0, ErrorLeverage::NoPositionFound);
+    require!(position.safety_mode, ErrorLeverage::SafetyModeDisabled);
+
+    // Position must not be in the middle of another process
+    position.halt_on_leveraging()?;
+    position.halt_on_deleveraging()?;
+
+    let (token_collateral_price, token_collateral_price_exponent, token_collateral_price_sources) = vault.token_collateral_price(&ctx.accounts.token_collateral_price_oracle, ctx.remaining_accounts)?;
+    let (native_collateral_price, native_collateral_price_exponent, native_collateral_price_sources) = vault.native_collateral_price(&ctx.accounts.native_collateral_price_oracle, ctx.remaining_accounts)?;
+
+    let collateral_amount = position.collateral_amount(vault.native_collateral_token_decimal, vault.index)?;
+    let borrowing_amount = position.borrowing_amount(vault.token_collateral_token_decimal, vault.borrowing_index)?;
+
+    let native_collateral_value = oracle::value(collateral_amount, vault.native_collateral_token_decimal, native_collateral_price, native_collateral_price_exponent)?;
+    let borrowing_value = oracle::value(borrowing_amount, vault.token_collateral_token_decimal, token_collateral_price, token_collateral_price_exponent)?;
+
+    // Saver threshold is expressed as a health factor, 1 = 10^3
+    let health_factor = position.health_factor(native_collateral_value, borrowing_value, config.liquidation_threshold)?;
+
+    msg!("native_collateral_value: {:?} borrowing_value: {:?}", native_collateral_value, borrowing_value);
+    msg!("health_factor: {:?} saver_threshold: {:?}", health_factor, config.saver_threshold);
+
+    require_gte!(config.saver_threshold, health_factor, ErrorLeverage::UnmetSaverThreshold);
+
+    let current_leverage = position.current_leverage(native_collateral_value, borrowing_value)?;
+    let target_leverage = current_leverage.saturating_sub(config.saver_target_reduction).max(LEVERAGE_ONE);
+
+    msg!("current_leverage: {:?} target_leverage: {:?}", current_leverage, target_leverage);
+
+    require_gt!(current_leverage, target_leverage, ErrorLeverage::SaverOnlyWorksForReducingLeverage);
+
+    let release_value = position.release_value_for_leverage(native_collateral_value, borrowing_value, target_leverage)?;
+    require_gt!(release_value, 0, ErrorLeverage::InvalidAmount);
+
+    // Portion of the position to release, 1 = 10^12
+    let release_ratio = release_value
+        .checked_mul(INDEX_ONE).ok_or(MathOverflow)?
+        .checked_div(native_collateral_value).ok_or(MathOverflow)?;
+    let release_rate = release_ratio
+        .checked_mul(PERCENT_MAX as u128).ok_or(MathOverflow)?
+        .checked_div(INDEX_ONE).ok_or(MathOverflow)? as u32;
+    let release_unit = (position.unit as u128)
+        .checked_mul(release_ratio).ok_or(MathOverflow)?
+        .checked_div(INDEX_ONE).ok_or(MathOverflow)? as u64;
+    let release_amount = decimals::mul_floor(vault.native_collateral_token_decimal, release_unit as u128, UNIT_DECIMALS, vault.index, INDEX_DECIMALS)? as u64;
+
+    // The whole swap output goes to the debt
+    let expected_output = oracle::amount(release_value, vault.token_collateral_token_decimal, token_collateral_price, token_collateral_price_exponent)?;
+    let release_min_output = (expected_output as u128)
+        .checked_mul(PERCENT_MAX.checked_sub(config.slippage_rate).ok_or(MathOverflow)? as u128).ok_or(MathOverflow)?
+        .checked_div(PERCENT_MAX as u128).ok_or(MathOverflow)? as u64;
+    let repay_amount = release_min_output.min(borrowing_amount);
+    let repay_unit = (decimals::div_floor(UNIT_DECIMALS, repay_amount as u128, vault.token_collateral_token_decimal, vault.borrowing_index, INDEX_DECIMALS)? as u64)
+        .min(position.borrowing_unit);
+
+    msg!("release_amount: {:?} release_unit: {:?} release_rate: {:?}", release_amount, release_unit, release_rate);
+    msg!("repay_amount: {:?} repay_unit: {:?} release_min_output: {:?}", repay_amount, repay_unit, release_min_output);
+
+    position.set_action(LeverageAction::Safe)?;
+    position.set_config(config)?;
+    position.set_oracle(
+        vault.token_collateral_price_oracle, vault.token_collateral_price_feed, token_collateral_price, token_collateral_price_exponent,
+        vault.native_collateral_price_oracle, vault.native_collateral_price_feed, native_collateral_price, native_collateral_price_exponent,
+    )?;
+    position.set_health_factor(health_factor)?;
+    position.set_keeper(ctx.accounts.keeper.key())?;
+    position.release_reduce(
+        release_amount, release_unit, vault.index,
+        release_rate,
+        repay_amount, repay_unit, vault.borrowing_index,
+        release_min_output, current_leverage, target_leverage,
+    )?;
+    position.safety_level = position.safety_level.saturating_add(1);
+
+    emit!(EventLeverageSave {
+        vault: ctx.accounts.vault.key(),
+        user: ctx.accounts.owner.key(),
+        keeper: ctx.accounts.keeper.key(),
+        obligation: ctx.accounts.obligation.key(),
+        position_id: position.id,
+        position_number: number,
+        safety_level: position.safety_level,
+        token_collateral_price,
+        token_collateral_price_exponent,
+        native_collateral_price,
+        native_collateral_price_exponent,
+        token_collateral_price_sources,
+        native_collateral_price_sources,
+        health_factor,
+        saver_threshold: config.saver_threshold,
+        current_leverage,
+        target_leverage,
+        release_amount,
+        release_unit,
+        release_rate,
+        repay_amount,
+        repay_unit,
+        release_min_output,
+    });
+
+    Ok(())
+}
+
+#[inline(never)]
+fn verify_next_ixs(ctx: &Context<VaultLeverageSave>) -> Result<()> {
+    let ixs = ctx.accounts.instructions.to_account_info();
+
+    // make sure this isnt a cpi call
+    let current_index = load_current_index_checked(&ixs)? as usize;
+    let current_ix = load_instruction_at_checked(current_index, &ixs)?;
+    if current_ix.program_id != *ctx.program_id {
+        return Err(Errors::InvalidProgram.into());
+    }
+
+    // next instruction of this program must be the keeper release
+    let mut index = current_index + 1;
+    loop {
+        if let Ok(ix) = load_instruction_at_checked(index, &ixs) {
+            if ix.program_id == crate::id() {
+                let ix_discriminator: [u8; 8] = ix.data[0..8]
+                    .try_into()
+                    .map_err(|_| Errors::UnknownInstruction)?;
+
+                if ix_discriminator == crate::instruction::LeverageVaultKeeperRelease::discriminator() {
+                    break;
+                } else {
+                    return Err(ErrorLeverage::NextInstructionMustBeKeeperRelease.into());
+                }
+            }
+        } else {
+            return Err(ErrorLeverage::MissingKeeperRelease.into());
+        }
+
+        index += 1
+    }
+
+    Ok(())
+}
+
+#[inline(never)]
+fn check_pause(ctx: &Context<VaultLeverageSave>) -> Result<()> {
+    require_not_paused(&[
+        ctx.accounts.protocol.load()?.pause_flags,
+        ctx.accounts.leverage_config.load()?.pause_flags,
+    ], PauseFlags::CLOSE)
+}
+
+#[derive(Accounts)]
+pub struct VaultLeverageSave<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account()]
+    pub leverage_config: AccountLoader<'info, LeverageConfig>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+        has_one = leverage_config @ Errors::InvalidConfig,
+        has_one = borrow_vault,
+        has_one = token_collateral_token_program,
+        has_one = token_collateral_token_mint,
+        has_one = native_collateral_token_program,
+        has_one = native_collateral_token_mint,
+    )]
+    pub vault: AccountLoader<'info, VaultLeverage>,
+    #[account()]
+    pub earn_config: AccountLoader<'info, EarnConfig>,
+    #[account(
+        mut,
+        has_one = earn_config @ Errors::InvalidConfig,
+    )]
+    pub borrow_vault: AccountLoader<'info, VaultEarn>,
+
+    #[account(
+        mut,
+        seeds = [seeds::OBLIGATION, vault.key().as_ref(), token_collateral_token_mint.key().as_ref(), native_collateral_token_mint.key().as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub obligation: AccountLoader<'info, Obligation>,
+    /// CHECK: owner of the obligation, validated by the obligation seeds
+    pub owner: UncheckedAccount<'info>,
+    #[account(mut, address = leverage_config.load()?.keeper @ ErrorLeverage::InvalidKeeper)]
+    pub keeper: Signer<'info>,
+
+    /// CHECK: price oracle of the vault, read according to its oracle source
+    #[account(
+        address = vault.load()?.token_collateral_price_oracle @ Errors::InvalidPriceOracle,
+    )]
+    pub token_collateral_price_oracle: UncheckedAccount<'info>,
+    /// CHECK: price oracle of the vault, read according to its oracle source
+    #[account(
+        address = vault.load()?.native_collateral_price_oracle @ Errors::InvalidPriceOracle,
+    )]
+    pub native_collateral_price_oracle: UncheckedAccount<'info>,
+
+    #[account(
+        mint::token_program = token_collateral_token_program,
+    )]
+    pub token_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mint::token_program = native_collateral_token_program,
+    )]
+    pub native_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: check instructions account
+    #[account(address = sysvar::instructions::ID @Errors::InvalidAddress)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_collateral_token_program: Interface<'info, TokenInterface>,
+    pub native_collateral_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
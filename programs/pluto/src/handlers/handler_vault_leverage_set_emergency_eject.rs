@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::Mint;
+use anchor_spl::token_interface::{Mint, TokenAccount};
 use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 use crate::error::{ErrorLeverage, Errors};
 use crate::event::{EventLeverageSetEmergencyEject};
@@ -8,22 +8,21 @@ use crate::state::{LeverageConfig, Obligation, Protocol, VaultLeverage};
 use crate::util::{
     seeds,
 };
-use crate::util::constant::MAX_OBLIGATION_POSITIONS;
+use crate::util::pause::{require_not_paused, PauseFlags};
 
 pub fn handle(ctx: Context<VaultLeverageSetEmergencyEject>, number: u8, state: bool) -> Result<()> {
-    check_freeze(&ctx)?;
+    check_pause(&ctx)?;
     let vault = &mut ctx.accounts.vault.load_mut()?;
-    let obligation = &mut ctx.accounts.obligation.load_mut()?;
+    let obligation = &mut Obligation::load_with_positions_mut(&ctx.accounts.obligation)?;
     let owner = &mut ctx.accounts.owner;
 
     msg!("vault address: {:?}", ctx.accounts.vault.key());
     msg!("obligation address: {:?}", ctx.accounts.obligation.key());
 
-    require!(number < MAX_OBLIGATION_POSITIONS, ErrorLeverage::InvalidPositionNumber);
-
-    let position = &mut obligation.positions[number as usize];
+    let position = obligation.position_mut(number)?;
 
     require_gt!(position.unit, 0, ErrorLeverage::NoPositionFound);
+    position.require_holder(ctx.accounts.owner.key(), ctx.accounts.holder.key(), &ctx.accounts.holder_nft_account)?;
 
     let old_state = position.emergency_eject;
 
@@ -31,7 +30,7 @@ pub fn handle(ctx: Context<VaultLeverageSetEmergencyEject>, number: u8, state: b
 
     emit!(EventLeverageSetEmergencyEject {
         vault: ctx.accounts.vault.key(),
-        user: ctx.accounts.owner.key(),
+        user: ctx.accounts.holder.key(),
         obligation: ctx.accounts.obligation.key(),
         position_id: position.id,
         position_number: number,
@@ -43,10 +42,11 @@ pub fn handle(ctx: Context<VaultLeverageSetEmergencyEject>, number: u8, state: b
 }
 
 #[inline(never)]
-fn check_freeze(ctx: &Context<VaultLeverageSetEmergencyEject>) -> Result<()> {
-    require!(!(ctx.accounts.protocol.load()?.freeze && !ctx.accounts.protocol.load()?.freeze_leverage), ErrorLeverage::VaultFrozen);
-    require!(!ctx.accounts.leverage_config.load()?.freeze, ErrorLeverage::VaultFrozen);
-    Ok(())
+fn check_pause(ctx: &Context<VaultLeverageSetEmergencyEject>) -> Result<()> {
+    require_not_paused(&[
+        ctx.accounts.protocol.load()?.pause_flags,
+        ctx.accounts.leverage_config.load()?.pause_flags,
+    ], PauseFlags::CLOSE)
 }
 
 #[derive(Accounts)]
@@ -73,7 +73,10 @@ pub struct VaultLeverageSetEmergencyEject<'info> {
     pub token_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
     pub native_collateral_token_mint: Box<InterfaceAccount<'info, Mint>>,
 
+    /// CHECK: owner of the obligation, validated by the obligation seeds
+    pub owner: UncheckedAccount<'info>,
     #[account(mut)]
-    pub owner: Signer<'info>,
+    pub holder: Signer<'info>,
+    pub holder_nft_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     pub system_program: Program<'info, System>,
 }
\ No newline at end of file
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use crate::error::Errors;
+use crate::event::{EventVaultLeverageSetFallbackPriceOracle};
+use crate::state::{FallbackOracle, Protocol, VaultLeverage};
+use crate::util::constant::MAX_FALLBACK_PRICE_ORACLES;
+use crate::util::oracle;
+use crate::util::oracle_source::OracleSource;
+
+// Omitting the oracle account of a side clears its fallback slot
+pub fn handle(
+    ctx: Context<VaultLeverageSetFallbackPriceOracle>,
+    index: u8,
+    token_collateral_price_source: OracleSource,
+    token_collateral_price_feed: [u8; 64],
+    native_collateral_price_source: OracleSource,
+    native_collateral_price_feed: [u8; 64],
+) -> Result<()> {
+    let vault = &mut ctx.accounts.vault.load_mut()?;
+
+    require_gt!(MAX_FALLBACK_PRICE_ORACLES, index as usize, Errors::InvalidFallbackPriceOracle);
+
+    let old_token_collateral = vault.token_collateral_fallback_price_oracles[index as usize];
+    let old_native_collateral = vault.native_collateral_fallback_price_oracles[index as usize];
+
+    let token_collateral = match &ctx.accounts.token_collateral_price_oracle {
+        Some(price_oracle) => {
+            oracle::validate_source(token_collateral_price_source, price_oracle, &token_collateral_price_feed, 0)?;
+            FallbackOracle::new(token_collateral_price_source, price_oracle.key(), token_collateral_price_feed)
+        }
+        None => FallbackOracle::default(),
+    };
+    let native_collateral = match &ctx.accounts.native_collateral_price_oracle {
+        Some(price_oracle) => {
+            oracle::validate_source(native_collateral_price_source, price_oracle, &native_collateral_price_feed, 0)?;
+            FallbackOracle::new(native_collateral_price_source, price_oracle.key(), native_collateral_price_feed)
+        }
+        None => FallbackOracle::default(),
+    };
+
+    msg!("protocol address: {:?}", ctx.accounts.protocol.key());
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("fallback index: {:?}", index);
+    msg!("old principal fallback price oracle: {:?}", old_token_collateral);
+    msg!("new principal fallback price oracle: {:?}", token_collateral);
+    msg!("old collateral fallback price oracle: {:?}", old_native_collateral);
+    msg!("new collateral fallback price oracle: {:?}", native_collateral);
+
+    vault.set_fallback_price_oracle(index as usize, token_collateral, native_collateral)?;
+
+    emit!(EventVaultLeverageSetFallbackPriceOracle {
+        vault: ctx.accounts.vault.key(),
+        index,
+        old_token_collateral_price_source: old_token_collateral.source,
+        new_token_collateral_price_source: token_collateral.source,
+        old_token_collateral_price_oracle: old_token_collateral.oracle,
+        new_token_collateral_price_oracle: token_collateral.oracle,
+        old_token_collateral_price_feed: old_token_collateral.feed,
+        new_token_collateral_price_feed: token_collateral.feed,
+        old_native_collateral_price_source: old_native_collateral.source,
+        new_native_collateral_price_source: native_collateral.source,
+        old_native_collateral_price_oracle: old_native_collateral.oracle,
+        new_native_collateral_price_oracle: native_collateral.oracle,
+        old_native_collateral_price_feed: old_native_collateral.feed,
+        new_native_collateral_price_feed: native_collateral.feed,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VaultLeverageSetFallbackPriceOracle<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+    )]
+    pub vault: AccountLoader<'info, VaultLeverage>,
+
+    /// CHECK: validated against the token collateral price source
+    pub token_collateral_price_oracle: Option<UncheckedAccount<'info>>,
+    /// CHECK: validated against the native collateral price source
+    pub native_collateral_price_oracle: Option<UncheckedAccount<'info>>,
+
+    #[account(mut, address = protocol.load()?.owner @ Errors::NotOwner)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
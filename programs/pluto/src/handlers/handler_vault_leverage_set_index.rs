@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::error::Errors;
+use crate::event::EventVaultLeverageSetIndex;
+use crate::state::{LeverageConfig, Protocol, VaultLeverage};
+use crate::util::pause::{require_not_paused, PauseFlags};
+
+pub fn handle(ctx: Context<VaultLeverageSetIndex>, index: u128, apy: u32, borrowing_index: u128, borrowing_apy: u32) -> Result<()> {
+    require_not_paused(&[
+        ctx.accounts.protocol.load()?.pause_flags,
+        ctx.accounts.leverage_config.load()?.pause_flags,
+    ], PauseFlags::INDEX_UPDATE)?;
+
+    let vault = &mut ctx.accounts.vault.load_mut()?;
+    let old_index = vault.index;
+    let old_borrowing_index = vault.borrowing_index;
+    vault.set_index(index, apy, borrowing_index, borrowing_apy)?;
+
+    msg!("vault address: {:?}", ctx.accounts.vault.key());
+    msg!("old index: {:?} index: {:?} apy: {:?}", old_index, index, apy);
+    msg!("old borrowing index: {:?} borrowing index: {:?} borrowing apy: {:?}", old_borrowing_index, borrowing_index, borrowing_apy);
+
+    emit!(EventVaultLeverageSetIndex{
+        vault: ctx.accounts.vault.key(),
+        indexer: ctx.accounts.indexer.key(),
+        old_index,
+        index,
+        apy,
+        old_borrowing_index,
+        borrowing_index,
+        borrowing_apy,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VaultLeverageSetIndex<'info> {
+    #[account()]
+    pub protocol: AccountLoader<'info, Protocol>,
+    #[account(
+        has_one = protocol @ Errors::InvalidProtocol,
+    )]
+    pub leverage_config: AccountLoader<'info, LeverageConfig>,
+    #[account(
+        mut,
+        has_one = protocol @ Errors::InvalidProtocol,
+        has_one = leverage_config @ Errors::InvalidConfig,
+    )]
+    pub vault: AccountLoader<'info, VaultLeverage>,
+
+    #[account(address = leverage_config.load()?.indexer @ Errors::NotIndexer)]
+    pub indexer: Signer<'info>,
+}
\ No newline at end of file
@@ -7,31 +7,81 @@ pub mod handler_protocol_set;
 pub mod handler_earn_config_create;
 pub mod handler_earn_config_set;
 pub mod handler_earn_config_change_indexer;
+pub mod handler_earn_config_set_rate_model;
+pub mod handler_earn_config_set_flash_fee;
+pub mod handler_earn_config_set_caps;
+pub mod handler_earn_config_collect_fees;
 
 pub mod handler_vault_earn_create;
 pub mod handler_vault_earn_change_price_oracle;
+pub mod handler_vault_earn_set_oracle_guard;
 pub mod handler_vault_earn_deposit;
 pub mod handler_vault_earn_withdraw;
+pub mod handler_vault_earn_flash_borrow;
+pub mod handler_vault_earn_flash_repay;
+pub mod handler_vault_earn_request_withdraw;
+pub mod handler_vault_earn_claim_withdraw;
+pub mod handler_vault_earn_fund_insurance;
+pub mod handler_vault_earn_accrue_interest;
+pub mod handler_vault_earn_set_index;
+pub mod handler_vault_earn_create_share_mint;
+pub mod handler_vault_earn_tokenize_lender;
 
 pub mod handler_leverage_config_create;
 pub mod handler_leverage_config_set;
+pub mod handler_leverage_config_collect_fees;
+pub mod handler_leverage_config_set_insurance_ratio;
 pub mod handler_protocol_change_owner;
+pub mod handler_protocol_set_fee_distribution;
+pub mod handler_protocol_accept_owner;
+pub mod handler_protocol_set_timelock_delay;
+pub mod handler_protocol_create_roles;
+pub mod handler_protocol_grant_role;
+pub mod handler_protocol_revoke_role;
+pub mod handler_earn_config_queue_set;
+pub mod handler_earn_config_execute_set;
+pub mod handler_earn_config_cancel_set;
+pub mod handler_leverage_config_queue_set;
+pub mod handler_leverage_config_execute_set;
+pub mod handler_leverage_config_cancel_set;
 pub mod handler_leverage_config_change_indexer;
 pub mod handler_leverage_config_change_keeper;
 
 pub mod handler_vault_leverage_create;
 pub mod handler_vault_leverage_create_liquidity;
 pub mod handler_vault_leverage_change_price_oracle;
+pub mod handler_vault_leverage_set_oracle_guard;
+pub mod handler_vault_leverage_set_fallback_price_oracle;
+pub mod handler_vault_leverage_create_swap_routers;
+pub mod handler_vault_leverage_add_swap_router;
+pub mod handler_vault_leverage_remove_swap_router;
+pub mod handler_vault_leverage_accrue_interest;
+pub mod handler_vault_leverage_set_index;
 pub mod handler_vault_leverage_fund;
 pub mod handler_vault_leverage_confiscate;
 pub mod handler_vault_leverage_close;
 pub mod handler_vault_leverage_release;
 pub mod handler_vault_leverage_repay_borrow;
 pub mod handler_vault_leverage_closing;
+pub mod handler_vault_leverage_liquidate;
+pub mod handler_vault_leverage_save;
+pub mod handler_vault_leverage_eject;
+pub mod handler_vault_leverage_take_profit;
+pub mod handler_vault_leverage_deleverage;
+pub mod handler_vault_leverage_add_collateral;
+pub mod handler_vault_leverage_increase;
+pub mod handler_vault_leverage_increase_swap;
+pub mod handler_vault_leverage_close_swap;
+pub mod handler_vault_leverage_keeper_release;
+pub mod handler_vault_leverage_keeper_repay_borrow;
+pub mod handler_vault_leverage_keeper_pay_liquidation_fee;
+pub mod handler_vault_leverage_keeper_closing;
 
 pub mod handler_vault_leverage_set_safety_mode;
 pub mod handler_vault_leverage_set_emergency_eject;
 pub mod handler_vault_leverage_set_profit_taker;
+pub mod handler_vault_leverage_mint_position_nft;
+pub mod handler_vault_leverage_extend_obligation;
 
 pub use handler_wrap_sol::*;
 pub use handler_unwrap_sol::*;
@@ -42,21 +92,56 @@ pub use handler_protocol_set::*;
 pub use handler_earn_config_create::*;
 pub use handler_earn_config_set::*;
 pub use handler_earn_config_change_indexer::*;
+pub use handler_earn_config_set_rate_model::*;
+pub use handler_earn_config_set_flash_fee::*;
+pub use handler_earn_config_set_caps::*;
+pub use handler_earn_config_collect_fees::*;
 
 pub use handler_vault_earn_create::*;
 pub use handler_vault_earn_change_price_oracle::*;
+pub use handler_vault_earn_set_oracle_guard::*;
 pub use handler_vault_earn_deposit::*;
 pub use handler_vault_earn_withdraw::*;
+pub use handler_vault_earn_flash_borrow::*;
+pub use handler_vault_earn_flash_repay::*;
+pub use handler_vault_earn_request_withdraw::*;
+pub use handler_vault_earn_claim_withdraw::*;
+pub use handler_vault_earn_fund_insurance::*;
+pub use handler_vault_earn_accrue_interest::*;
+pub use handler_vault_earn_set_index::*;
+pub use handler_vault_earn_create_share_mint::*;
+pub use handler_vault_earn_tokenize_lender::*;
 
 pub use handler_leverage_config_create::*;
 pub use handler_leverage_config_set::*;
+pub use handler_leverage_config_collect_fees::*;
+pub use handler_leverage_config_set_insurance_ratio::*;
 pub use handler_protocol_change_owner::*;
+pub use handler_protocol_set_fee_distribution::*;
+pub use handler_protocol_accept_owner::*;
+pub use handler_protocol_set_timelock_delay::*;
+pub use handler_protocol_create_roles::*;
+pub use handler_protocol_grant_role::*;
+pub use handler_protocol_revoke_role::*;
+pub use handler_earn_config_queue_set::*;
+pub use handler_earn_config_execute_set::*;
+pub use handler_earn_config_cancel_set::*;
+pub use handler_leverage_config_queue_set::*;
+pub use handler_leverage_config_execute_set::*;
+pub use handler_leverage_config_cancel_set::*;
 pub use handler_leverage_config_change_indexer::*;
 pub use handler_leverage_config_change_keeper::*;
 
 pub use handler_vault_leverage_create::*;
 pub use handler_vault_leverage_create_liquidity::*;
 pub use handler_vault_leverage_change_price_oracle::*;
+pub use handler_vault_leverage_set_oracle_guard::*;
+pub use handler_vault_leverage_set_fallback_price_oracle::*;
+pub use handler_vault_leverage_create_swap_routers::*;
+pub use handler_vault_leverage_add_swap_router::*;
+pub use handler_vault_leverage_remove_swap_router::*;
+pub use handler_vault_leverage_accrue_interest::*;
+pub use handler_vault_leverage_set_index::*;
 
 pub use handler_vault_leverage_fund::*;
 pub use handler_vault_leverage_confiscate::*;
@@ -65,7 +150,22 @@ pub use handler_vault_leverage_close::*;
 pub use handler_vault_leverage_release::*;
 pub use handler_vault_leverage_repay_borrow::*;
 pub use handler_vault_leverage_closing::*;
+pub use handler_vault_leverage_liquidate::*;
+pub use handler_vault_leverage_save::*;
+pub use handler_vault_leverage_eject::*;
+pub use handler_vault_leverage_take_profit::*;
+pub use handler_vault_leverage_deleverage::*;
+pub use handler_vault_leverage_add_collateral::*;
+pub use handler_vault_leverage_increase::*;
+pub use handler_vault_leverage_increase_swap::*;
+pub use handler_vault_leverage_close_swap::*;
+pub use handler_vault_leverage_keeper_release::*;
+pub use handler_vault_leverage_keeper_repay_borrow::*;
+pub use handler_vault_leverage_keeper_pay_liquidation_fee::*;
+pub use handler_vault_leverage_keeper_closing::*;
 
 pub use handler_vault_leverage_set_safety_mode::*;
 pub use handler_vault_leverage_set_emergency_eject::*;
-pub use handler_vault_leverage_set_profit_taker::*;
\ No newline at end of file
+pub use handler_vault_leverage_set_profit_taker::*;
+pub use handler_vault_leverage_mint_position_nft::*;
+pub use handler_vault_leverage_extend_obligation::*;
\ No newline at end of file
@@ -6,7 +6,8 @@ mod util;
 
 use anchor_lang::prelude::*;
 use crate::handlers::*;
-use crate::state::PositionSettings;
+use crate::state::{PositionSettings, Role, SetEarnConfigParams, SetLeverageConfigParams};
+use crate::util::oracle_source::OracleSource;
 
 declare_id!("BeaiD9HF7V2Byz6Md6bWn6B3Zq7Djry2gt4KK9oUwjgZ");
 //declare_id!("G7x8ig9axyVrLZZY8WgrNhZqWwWoWoJTrUdj3dsefpkf");
@@ -27,13 +28,13 @@ pub mod pluto {
     }
 
     #[inline(never)]
-    pub fn protocol_create(ctx: Context<ProtocolCreate>, freeze: bool, freeze_earn: bool, freeze_lend: bool, freeze_leverage: bool) -> Result<()> {
-        handler_protocol_create::handle(ctx, freeze, freeze_earn, freeze_lend, freeze_leverage)
+    pub fn protocol_create(ctx: Context<ProtocolCreate>, pause_flags: u16) -> Result<()> {
+        handler_protocol_create::handle(ctx, pause_flags)
     }
 
     #[inline(never)]
-    pub fn protocol_set(ctx: Context<ProtocolSet>, freeze: bool, freeze_earn: bool, freeze_lend: bool, freeze_leverage: bool) -> Result<()> {
-        handler_protocol_set::handle(ctx, freeze, freeze_earn, freeze_lend, freeze_leverage)
+    pub fn protocol_set(ctx: Context<ProtocolSet>, pause_flags: u16) -> Result<()> {
+        handler_protocol_set::handle(ctx, pause_flags)
     }
 
     #[inline(never)]
@@ -42,13 +43,58 @@ pub mod pluto {
     }
 
     #[inline(never)]
-    pub fn earn_config_create(ctx: Context<EarnConfigCreate>, freeze: bool, protocol_fee: u32, ltv: u32, deposit_fee: u32, min_deposit_limit: u64, max_deposit_limit: u64, withdraw_fee: u32, min_withdraw_limit: u64, max_withdraw_limit: u64, borrow_fee: u32, min_borrow_limit: u64, max_borrow_limit: u64, floor_cap_rate: u32) -> Result<()> {
-        handler_earn_config_create::handle(ctx, freeze, protocol_fee, ltv, deposit_fee, min_deposit_limit, max_deposit_limit, withdraw_fee, min_withdraw_limit, max_withdraw_limit, borrow_fee, min_borrow_limit, max_borrow_limit, floor_cap_rate)
+    pub fn protocol_accept_owner(ctx: Context<ProtocolAcceptOwner>) -> Result<()> {
+        handler_protocol_accept_owner::handle(ctx)
     }
 
     #[inline(never)]
-    pub fn earn_config_set(ctx: Context<EarnConfigSet>, freeze: bool, protocol_fee: u32, ltv: u32, deposit_fee: u32, min_deposit_limit: u64, max_deposit_limit: u64, withdraw_fee: u32, min_withdraw_limit: u64, max_withdraw_limit: u64, borrow_fee: u32, min_borrow_limit: u64, max_borrow_limit: u64, floor_cap_rate: u32) -> Result<()> {
-        handler_earn_config_set::handle(ctx, freeze, protocol_fee, ltv, deposit_fee, min_deposit_limit, max_deposit_limit, withdraw_fee, min_withdraw_limit, max_withdraw_limit, borrow_fee, min_borrow_limit, max_borrow_limit, floor_cap_rate)
+    pub fn protocol_set_timelock_delay(ctx: Context<ProtocolSetTimelockDelay>, timelock_delay: i64) -> Result<()> {
+        handler_protocol_set_timelock_delay::handle(ctx, timelock_delay)
+    }
+
+    #[inline(never)]
+    pub fn protocol_create_roles(ctx: Context<ProtocolCreateRoles>) -> Result<()> {
+        handler_protocol_create_roles::handle(ctx)
+    }
+
+    #[inline(never)]
+    pub fn protocol_grant_role(ctx: Context<ProtocolGrantRole>, member: Pubkey, role: Role) -> Result<()> {
+        handler_protocol_grant_role::handle(ctx, member, role)
+    }
+
+    #[inline(never)]
+    pub fn protocol_revoke_role(ctx: Context<ProtocolRevokeRole>, member: Pubkey, role: Role) -> Result<()> {
+        handler_protocol_revoke_role::handle(ctx, member, role)
+    }
+
+    #[inline(never)]
+    pub fn protocol_set_fee_distribution(ctx: Context<ProtocolSetFeeDistribution>, treasury: Pubkey, insurance_fund: Pubkey, buyback: Pubkey, treasury_ratio: u32, insurance_ratio: u32, buyback_ratio: u32) -> Result<()> {
+        handler_protocol_set_fee_distribution::handle(ctx, treasury, insurance_fund, buyback, treasury_ratio, insurance_ratio, buyback_ratio)
+    }
+
+    #[inline(never)]
+    pub fn earn_config_create(ctx: Context<EarnConfigCreate>, pause_flags: u16, protocol_fee: u32, ltv: u32, deposit_fee: u32, min_deposit_limit: u64, max_deposit_limit: u64, withdraw_fee: u32, min_withdraw_limit: u64, max_withdraw_limit: u64, borrow_fee: u32, min_borrow_limit: u64, max_borrow_limit: u64, floor_cap_rate: u32) -> Result<()> {
+        handler_earn_config_create::handle(ctx, pause_flags, protocol_fee, ltv, deposit_fee, min_deposit_limit, max_deposit_limit, withdraw_fee, min_withdraw_limit, max_withdraw_limit, borrow_fee, min_borrow_limit, max_borrow_limit, floor_cap_rate)
+    }
+
+    #[inline(never)]
+    pub fn earn_config_set(ctx: Context<EarnConfigSet>, params: SetEarnConfigParams) -> Result<()> {
+        handler_earn_config_set::handle(ctx, params)
+    }
+
+    #[inline(never)]
+    pub fn earn_config_queue_set(ctx: Context<EarnConfigQueueSet>, params: SetEarnConfigParams) -> Result<()> {
+        handler_earn_config_queue_set::handle(ctx, params)
+    }
+
+    #[inline(never)]
+    pub fn earn_config_execute_set(ctx: Context<EarnConfigExecuteSet>) -> Result<()> {
+        handler_earn_config_execute_set::handle(ctx)
+    }
+
+    #[inline(never)]
+    pub fn earn_config_cancel_set(ctx: Context<EarnConfigCancelSet>) -> Result<()> {
+        handler_earn_config_cancel_set::handle(ctx)
     }
 
     #[inline(never)]
@@ -56,14 +102,39 @@ pub mod pluto {
         handler_earn_config_change_indexer::handle(ctx, new_indexer)
     }
 
+    #[inline(never)]
+    pub fn earn_config_set_rate_model(ctx: Context<EarnConfigSetRateModel>, base_rate: u32, slope1: u32, optimal_utilization: u32, slope2: u32) -> Result<()> {
+        handler_earn_config_set_rate_model::handle(ctx, base_rate, slope1, optimal_utilization, slope2)
+    }
+
+    #[inline(never)]
+    pub fn earn_config_set_flash_fee(ctx: Context<EarnConfigSetFlashFee>, flash_fee: u32) -> Result<()> {
+        handler_earn_config_set_flash_fee::handle(ctx, flash_fee)
+    }
+
+    #[inline(never)]
+    pub fn earn_config_set_caps(ctx: Context<EarnConfigSetCaps>, supply_cap: u64, borrow_cap: u64, max_utilization: u32) -> Result<()> {
+        handler_earn_config_set_caps::handle(ctx, supply_cap, borrow_cap, max_utilization)
+    }
+
+    #[inline(never)]
+    pub fn earn_config_collect_fees(ctx: Context<EarnConfigCollectFees>) -> Result<()> {
+        handler_earn_config_collect_fees::handle(ctx)
+    }
+
     #[inline(never)]
     pub fn earn_vault_create(ctx: Context<VaultEarnCreate>, token_decimal: [u8; 64]) -> Result<()> {
         handler_vault_earn_create::handle(ctx, token_decimal)
     }
 
     #[inline(never)]
-    pub fn earn_vault_change_price_oracle(ctx: Context<VaultEarnChangePriceOracle>, token_decimal: [u8; 64]) -> Result<()> {
-        handler_vault_earn_change_price_oracle::handle(ctx, token_decimal)
+    pub fn earn_vault_change_price_oracle(ctx: Context<VaultEarnChangePriceOracle>, price_source: OracleSource, price_feed: [u8; 64], fixed_price: u64) -> Result<()> {
+        handler_vault_earn_change_price_oracle::handle(ctx, price_source, price_feed, fixed_price)
+    }
+
+    #[inline(never)]
+    pub fn earn_vault_set_oracle_guard(ctx: Context<VaultEarnSetOracleGuard>, max_age: u64, max_confidence_rate: u32, max_ema_deviation_rate: u32) -> Result<()> {
+        handler_vault_earn_set_oracle_guard::handle(ctx, max_age, max_confidence_rate, max_ema_deviation_rate)
     }
 
     #[inline(never)]
@@ -77,13 +148,83 @@ pub mod pluto {
     }
 
     #[inline(never)]
-    pub fn leverage_config_create(ctx: Context<LeverageConfigCreate>, freeze: bool, protocol_fee: u32, min_leverage: u32, max_leverage: u32, leverage_step: u32, leverage_fee: u32, min_leverage_limit: u64, max_leverage_limit: u64, deleverage_fee: u32, min_deleverage_limit: u64, max_deleverage_limit: u64, closing_fee: u32, spread_rate: u32, liquidation_fee: u32, liquidation_threshold: u32, liquidation_protocol_ratio: u32, slippage_rate: u32, emergency_eject_period: i64, saver_threshold: u32, saver_target_reduction: u32) -> Result<()> {
-        handler_leverage_config_create::handle(ctx, freeze, protocol_fee, min_leverage, max_leverage, leverage_step, leverage_fee, min_leverage_limit, max_leverage_limit, deleverage_fee, min_deleverage_limit, max_deleverage_limit, closing_fee, spread_rate, liquidation_fee, liquidation_threshold, liquidation_protocol_ratio, slippage_rate, emergency_eject_period, saver_threshold, saver_target_reduction)
+    pub fn earn_vault_flash_borrow(ctx: Context<VaultEarnFlashBorrow>, amount: u64) -> Result<()> {
+        handler_vault_earn_flash_borrow::handle(ctx, amount)
+    }
+
+    #[inline(never)]
+    pub fn earn_vault_flash_repay(ctx: Context<VaultEarnFlashRepay>, amount: u64) -> Result<()> {
+        handler_vault_earn_flash_repay::handle(ctx, amount)
+    }
+
+    #[inline(never)]
+    pub fn earn_vault_request_withdraw(ctx: Context<VaultEarnRequestWithdraw>, unit: u64) -> Result<()> {
+        handler_vault_earn_request_withdraw::handle(ctx, unit)
+    }
+
+    #[inline(never)]
+    pub fn earn_vault_claim_withdraw(ctx: Context<VaultEarnClaimWithdraw>) -> Result<()> {
+        handler_vault_earn_claim_withdraw::handle(ctx)
+    }
+
+    #[inline(never)]
+    pub fn earn_vault_fund_insurance(ctx: Context<VaultEarnFundInsurance>, amount: u64) -> Result<()> {
+        handler_vault_earn_fund_insurance::handle(ctx, amount)
+    }
+
+    #[inline(never)]
+    pub fn earn_vault_accrue_interest(ctx: Context<VaultEarnAccrueInterest>) -> Result<()> {
+        handler_vault_earn_accrue_interest::handle(ctx)
+    }
+
+    #[inline(never)]
+    pub fn earn_vault_set_index(ctx: Context<VaultEarnSetIndex>, index: u128, apy: u32) -> Result<()> {
+        handler_vault_earn_set_index::handle(ctx, index, apy)
+    }
+
+    #[inline(never)]
+    pub fn earn_vault_create_share_mint(ctx: Context<VaultEarnCreateShareMint>) -> Result<()> {
+        handler_vault_earn_create_share_mint::handle(ctx)
+    }
+
+    #[inline(never)]
+    pub fn earn_vault_tokenize_lender(ctx: Context<VaultEarnTokenizeLender>) -> Result<()> {
+        handler_vault_earn_tokenize_lender::handle(ctx)
+    }
+
+    #[inline(never)]
+    pub fn leverage_config_create(ctx: Context<LeverageConfigCreate>, pause_flags: u16, protocol_fee: u32, min_leverage: u32, max_leverage: u32, leverage_step: u32, leverage_fee: u32, min_leverage_limit: u64, max_leverage_limit: u64, deleverage_fee: u32, min_deleverage_limit: u64, max_deleverage_limit: u64, closing_fee: u32, spread_rate: u32, liquidation_fee: u32, liquidation_threshold: u32, liquidation_protocol_ratio: u32, slippage_rate: u32, emergency_eject_period: i64, saver_threshold: u32, saver_target_reduction: u32) -> Result<()> {
+        handler_leverage_config_create::handle(ctx, pause_flags, protocol_fee, min_leverage, max_leverage, leverage_step, leverage_fee, min_leverage_limit, max_leverage_limit, deleverage_fee, min_deleverage_limit, max_deleverage_limit, closing_fee, spread_rate, liquidation_fee, liquidation_threshold, liquidation_protocol_ratio, slippage_rate, emergency_eject_period, saver_threshold, saver_target_reduction)
+    }
+
+    #[inline(never)]
+    pub fn leverage_config_set(ctx: Context<LeverageConfigSet>, params: SetLeverageConfigParams) -> Result<()> {
+        handler_leverage_config_set::handle(ctx, params)
+    }
+
+    #[inline(never)]
+    pub fn leverage_config_queue_set(ctx: Context<LeverageConfigQueueSet>, params: SetLeverageConfigParams) -> Result<()> {
+        handler_leverage_config_queue_set::handle(ctx, params)
     }
 
     #[inline(never)]
-    pub fn leverage_config_set(ctx: Context<LeverageConfigSet>, freeze: bool, protocol_fee: u32, min_leverage: u32, max_leverage: u32, leverage_step: u32, leverage_fee: u32, min_leverage_limit: u64, max_leverage_limit: u64, deleverage_fee: u32, min_deleverage_limit: u64, max_deleverage_limit: u64, closing_fee: u32, spread_rate: u32, liquidation_fee: u32, liquidation_threshold: u32, liquidation_protocol_ratio: u32, slippage_rate: u32, emergency_eject_period: i64, saver_threshold: u32, saver_target_reduction: u32) -> Result<()> {
-        handler_leverage_config_set::handle(ctx, freeze, protocol_fee, min_leverage, max_leverage, leverage_step, leverage_fee, min_leverage_limit, max_leverage_limit, deleverage_fee, min_deleverage_limit, max_deleverage_limit, closing_fee, spread_rate, liquidation_fee, liquidation_threshold, liquidation_protocol_ratio, slippage_rate, emergency_eject_period, saver_threshold, saver_target_reduction)
+    pub fn leverage_config_execute_set(ctx: Context<LeverageConfigExecuteSet>) -> Result<()> {
+        handler_leverage_config_execute_set::handle(ctx)
+    }
+
+    #[inline(never)]
+    pub fn leverage_config_cancel_set(ctx: Context<LeverageConfigCancelSet>) -> Result<()> {
+        handler_leverage_config_cancel_set::handle(ctx)
+    }
+
+    #[inline(never)]
+    pub fn leverage_config_collect_fees(ctx: Context<LeverageConfigCollectFees>) -> Result<()> {
+        handler_leverage_config_collect_fees::handle(ctx)
+    }
+
+    #[inline(never)]
+    pub fn leverage_config_set_insurance_ratio(ctx: Context<LeverageConfigSetInsuranceRatio>, insurance_ratio: u32) -> Result<()> {
+        handler_leverage_config_set_insurance_ratio::handle(ctx, insurance_ratio)
     }
 
     #[inline(never)]
@@ -107,8 +248,43 @@ pub mod pluto {
     }
 
     #[inline(never)]
-    pub fn leverage_vault_change_price_oracle(ctx: Context<VaultLeverageChangePriceOracle>, token_collateral_decimal: [u8; 64], native_collateral_decimal: [u8; 64]) -> Result<()> {
-        handler_vault_leverage_change_price_oracle::handle(ctx, token_collateral_decimal, native_collateral_decimal)
+    pub fn leverage_vault_change_price_oracle(ctx: Context<VaultLeverageChangePriceOracle>, token_collateral_price_source: OracleSource, token_collateral_price_feed: [u8; 64], token_collateral_fixed_price: u64, native_collateral_price_source: OracleSource, native_collateral_price_feed: [u8; 64], native_collateral_fixed_price: u64) -> Result<()> {
+        handler_vault_leverage_change_price_oracle::handle(ctx, token_collateral_price_source, token_collateral_price_feed, token_collateral_fixed_price, native_collateral_price_source, native_collateral_price_feed, native_collateral_fixed_price)
+    }
+
+    #[inline(never)]
+    pub fn leverage_vault_set_oracle_guard(ctx: Context<VaultLeverageSetOracleGuard>, max_age: u64, max_confidence_rate: u32, max_ema_deviation_rate: u32) -> Result<()> {
+        handler_vault_leverage_set_oracle_guard::handle(ctx, max_age, max_confidence_rate, max_ema_deviation_rate)
+    }
+
+    #[inline(never)]
+    pub fn leverage_vault_set_fallback_price_oracle(ctx: Context<VaultLeverageSetFallbackPriceOracle>, index: u8, token_collateral_price_source: OracleSource, token_collateral_price_feed: [u8; 64], native_collateral_price_source: OracleSource, native_collateral_price_feed: [u8; 64]) -> Result<()> {
+        handler_vault_leverage_set_fallback_price_oracle::handle(ctx, index, token_collateral_price_source, token_collateral_price_feed, native_collateral_price_source, native_collateral_price_feed)
+    }
+
+    #[inline(never)]
+    pub fn leverage_vault_create_swap_routers(ctx: Context<VaultLeverageCreateSwapRouters>) -> Result<()> {
+        handler_vault_leverage_create_swap_routers::handle(ctx)
+    }
+
+    #[inline(never)]
+    pub fn leverage_vault_add_swap_router(ctx: Context<VaultLeverageAddSwapRouter>, router: Pubkey) -> Result<()> {
+        handler_vault_leverage_add_swap_router::handle(ctx, router)
+    }
+
+    #[inline(never)]
+    pub fn leverage_vault_remove_swap_router(ctx: Context<VaultLeverageRemoveSwapRouter>, router: Pubkey) -> Result<()> {
+        handler_vault_leverage_remove_swap_router::handle(ctx, router)
+    }
+
+    #[inline(never)]
+    pub fn leverage_vault_accrue_interest(ctx: Context<VaultLeverageAccrueInterest>) -> Result<()> {
+        handler_vault_leverage_accrue_interest::handle(ctx)
+    }
+
+    #[inline(never)]
+    pub fn leverage_vault_set_index(ctx: Context<VaultLeverageSetIndex>, index: u128, apy: u32, borrowing_index: u128, borrowing_apy: u32) -> Result<()> {
+        handler_vault_leverage_set_index::handle(ctx, index, apy, borrowing_index, borrowing_apy)
     }
 
     #[inline(never)]
@@ -136,6 +312,16 @@ pub mod pluto {
         handler_vault_leverage_set_profit_taker::handle(ctx, number, profit_taker, profit, take)
     }
 
+    #[inline(never)]
+    pub fn leverage_vault_mint_position_nft(ctx: Context<VaultLeverageMintPositionNft>, number: u8, uri: String) -> Result<()> {
+        handler_vault_leverage_mint_position_nft::handle(ctx, number, uri)
+    }
+
+    #[inline(never)]
+    pub fn leverage_vault_extend_obligation(ctx: Context<VaultLeverageExtendObligation>, additional: u8) -> Result<()> {
+        handler_vault_leverage_extend_obligation::handle(ctx, additional)
+    }
+
     #[inline(never)]
     pub fn leverage_vault_close(ctx: Context<VaultLeverageClose>, number: u8) -> Result<()> {
         handler_vault_leverage_close::handle(ctx, number)
@@ -155,4 +341,69 @@ pub mod pluto {
     pub fn leverage_vault_closing(ctx: Context<VaultLeverageClosing>, number: u8) -> Result<()> {
         handler_vault_leverage_closing::handle(ctx, number)
     }
-}
+
+    #[inline(never)]
+    pub fn leverage_vault_liquidate(ctx: Context<VaultLeverageLiquidate>, number: u8) -> Result<()> {
+        handler_vault_leverage_liquidate::handle(ctx, number)
+    }
+
+    #[inline(never)]
+    pub fn leverage_vault_save(ctx: Context<VaultLeverageSave>, number: u8) -> Result<()> {
+        handler_vault_leverage_save::handle(ctx, number)
+    }
+
+    #[inline(never)]
+    pub fn leverage_vault_eject(ctx: Context<VaultLeverageEject>, number: u8) -> Result<()> {
+        handler_vault_leverage_eject::handle(ctx, number)
+    }
+
+    #[inline(never)]
+    pub fn leverage_vault_take_profit(ctx: Context<VaultLeverageTakeProfit>, number: u8) -> Result<()> {
+        handler_vault_leverage_take_profit::handle(ctx, number)
+    }
+
+    #[inline(never)]
+    pub fn leverage_vault_deleverage(ctx: Context<VaultLeverageDeleverage>, number: u8, target_leverage: u32) -> Result<()> {
+        handler_vault_leverage_deleverage::handle(ctx, number, target_leverage)
+    }
+
+    #[inline(never)]
+    pub fn leverage_vault_add_collateral(ctx: Context<VaultLeverageAddCollateral>, number: u8, amount: u64) -> Result<()> {
+        handler_vault_leverage_add_collateral::handle(ctx, number, amount)
+    }
+
+    #[inline(never)]
+    pub fn leverage_vault_increase(ctx: Context<VaultLeverageIncrease>, number: u8, amount: u64, leverage: u32) -> Result<()> {
+        handler_vault_leverage_increase::handle(ctx, number, amount, leverage)
+    }
+
+    #[inline(never)]
+    pub fn leverage_vault_increase_swap<'info>(ctx: Context<'_, '_, 'info, 'info, VaultLeverageIncreaseSwap<'info>>, number: u8, amount: u64, leverage: u32, route_accounts_len: u8, route_data: Vec<u8>) -> Result<()> {
+        handler_vault_leverage_increase_swap::handle(ctx, number, amount, leverage, route_accounts_len, route_data)
+    }
+
+    #[inline(never)]
+    pub fn leverage_vault_close_swap<'info>(ctx: Context<'_, '_, 'info, 'info, VaultLeverageCloseSwap<'info>>, number: u8, route_accounts_len: u8, route_data: Vec<u8>) -> Result<()> {
+        handler_vault_leverage_close_swap::handle(ctx, number, route_accounts_len, route_data)
+    }
+
+    #[inline(never)]
+    pub fn leverage_vault_keeper_release(ctx: Context<VaultLeverageKeeperRelease>, number: u8) -> Result<()> {
+        handler_vault_leverage_keeper_release::handle(ctx, number)
+    }
+
+    #[inline(never)]
+    pub fn leverage_vault_keeper_repay_borrow(ctx: Context<VaultLeverageKeeperRepayBorrow>, number: u8) -> Result<()> {
+        handler_vault_leverage_keeper_repay_borrow::handle(ctx, number)
+    }
+
+    #[inline(never)]
+    pub fn leverage_vault_keeper_pay_liquidation_fee(ctx: Context<VaultLeverageKeeperPayLiquidationFee>, number: u8) -> Result<()> {
+        handler_vault_leverage_keeper_pay_liquidation_fee::handle(ctx, number)
+    }
+
+    #[inline(never)]
+    pub fn leverage_vault_keeper_closing(ctx: Context<VaultLeverageKeeperClosing>, number: u8) -> Result<()> {
+        handler_vault_leverage_keeper_closing::handle(ctx, number)
+    }
+}
\ No newline at end of file
@@ -3,8 +3,11 @@ use anchor_lang::prelude::*;
 use derivative::Derivative;
 use crate::error::{Errors, ErrorEarn};
 use crate::error::ErrorMath::MathOverflow;
+use crate::state::Role;
 use crate::util::{constant, decimals};
-use crate::util::constant::{INDEX_DECIMALS, INDEX_ONE, PERCENT_DECIMALS, UNIT_DECIMALS};
+use crate::util::access::{is_changed, is_within_cap_step, is_within_risk_step};
+use crate::util::pause::PauseFlags;
+use crate::util::constant::{FLOOR_CAP_RATIO, INDEX_DECIMALS, INDEX_ONE, MAX_FLASH_FEE, MAX_PROTOCOL_FEE, MAX_TRANSACTION_FEE, PERCENT_DECIMALS, UNIT_DECIMALS};
 
 #[derive(InitSpace, Derivative, PartialEq)]
 #[derivative(Debug)]
@@ -21,9 +24,9 @@ pub struct EarnConfig {
     pub authority: Pubkey,
     pub indexer: Pubkey,
     pub earn_fee_vault: Pubkey,
-    pub freeze: bool,
+    pub pause_flags: u16, // paused operations of the config vaults, see PauseFlags
     #[derivative(Debug = "ignore")]
-    pub align1: [u8; 7],
+    pub align1: [u8; 6],
     #[derivative(Default(value="0u32"))] // 0%
     pub protocol_fee: u32, // protocol fee in percentage 100% = 10^5
     #[derivative(Default(value="5 * 10u32.pow(4)"))] // 50%
@@ -57,8 +60,28 @@ pub struct EarnConfig {
     #[derivative(Debug = "ignore")]
     pub align5: [u8; 4],
     pub last_updated: i64,
+    #[derivative(Default(value="0u32"))] // 0%
+    pub base_rate: u32, // borrow rate at 0% utilization in percentage 100% = 10^5
+    #[derivative(Default(value="4 * 10u32.pow(3)"))] // 4%
+    pub slope1: u32, // borrow rate added up to optimal utilization in percentage 100% = 10^5
+    #[derivative(Default(value="8 * 10u32.pow(4)"))] // 80%
+    pub optimal_utilization: u32, // kink utilization in percentage 100% = 10^5
+    #[derivative(Default(value="75 * 10u32.pow(3)"))] // 75%
+    pub slope2: u32, // borrow rate added from optimal to full utilization in percentage 100% = 10^5
+    #[derivative(Default(value="0u32"))] // 0%
+    pub flash_fee: u32, // flash loan fee in percentage 100% = 10^5
+    #[derivative(Debug = "ignore")]
+    pub align6: [u8; 4],
+    #[derivative(Default(value="0u64"))]
+    pub supply_cap: u64, // max total supply in token amount, 0 = no cap
+    #[derivative(Default(value="0u64"))]
+    pub borrow_cap: u64, // max total leverage borrowing in token amount, 0 = no cap
+    #[derivative(Default(value="0u32"))]
+    pub max_utilization: u32, // utilization ceiling for leverage borrowing in percentage 100% = 10^5, 0 = no ceiling
     #[derivative(Debug = "ignore")]
-    pub padding1: [u64; 32],
+    pub align7: [u8; 4],
+    #[derivative(Debug = "ignore")]
+    pub padding1: [u64; 26],
 }
 
 impl Default for EarnConfig {
@@ -73,8 +96,8 @@ impl Default for EarnConfig {
             authority: Pubkey::default(),
             indexer: Pubkey::default(),
             earn_fee_vault: Pubkey::default(),
-            freeze: false,
-            align1: [0;7],
+            pause_flags: 0,
+            align1: [0;6],
             protocol_fee: 0,
             ltv: 0,
             deposit_fee: 0,
@@ -92,21 +115,23 @@ impl Default for EarnConfig {
             floor_cap_rate: 0,
             align5: [0;4],
             last_updated: 0,
-            padding1: [0; 32],
+            base_rate: 0,
+            slope1: 0,
+            optimal_utilization: 0,
+            slope2: 0,
+            flash_fee: 0,
+            align6: [0;4],
+            supply_cap: 0,
+            borrow_cap: 0,
+            max_utilization: 0,
+            align7: [0;4],
+            padding1: [0; 26],
         }
     }
 }
 
 impl EarnConfig {
     pub fn init(&mut self, params: InitEarnConfigParams) -> Result<()> {
-        require_gt!(params.ltv, 0, ErrorEarn::InvalidLTV);
-        require_gte!(params.max_deposit_limit, params.min_deposit_limit, ErrorEarn::InvalidMaxDepositLimitLessThanMinDepositLimit);
-        require_gt!(params.max_deposit_limit, 0, ErrorEarn::InvalidMaxDepositLimit);
-        require_gte!(params.max_withdraw_limit, params.min_withdraw_limit, ErrorEarn::InvalidMaxWithdrawLimitLessThanMinWithdrawLimit);
-        require_gt!(params.max_withdraw_limit, 0, ErrorEarn::InvalidMaxWithdrawLimit);
-        require_gte!(params.max_borrow_limit, params.min_borrow_limit, ErrorEarn::InvalidMaxBorrowLimitLessThanMinBorrowLimit);
-        require_gt!(params.max_borrow_limit, 0, ErrorEarn::InvalidMaxBorrowLimit);
-        require_gt!(params.floor_cap_rate, 0, ErrorEarn::InvalidFloorCapRate);
         *self = Self::default();
         self.is_initialized = true;
         self.version = 1;
@@ -116,7 +141,7 @@ impl EarnConfig {
         self.authority = params.authority;
         self.indexer = params.indexer;
         self.earn_fee_vault = params.earn_fee_vault;
-        self.freeze = params.freeze;
+        self.pause_flags = params.pause_flags;
         self.protocol_fee = params.protocol_fee;
         self.ltv = params.ltv;
         self.deposit_fee = params.deposit_fee;
@@ -129,43 +154,179 @@ impl EarnConfig {
         self.min_borrow_limit = params.min_borrow_limit;
         self.max_borrow_limit = params.max_borrow_limit;
         self.floor_cap_rate = params.floor_cap_rate;
+        self.validate()?;
         self.last_updated = Clock::get()?.unix_timestamp;
 
         Ok(())
     }
 
-    pub fn set_config(&mut self, params: SetEarnConfigParams) -> Result<()> {
-        require_gt!(params.ltv, 0, ErrorEarn::InvalidLTV);
-        require_gte!(params.max_deposit_limit, params.min_deposit_limit, ErrorEarn::InvalidMaxDepositLimitLessThanMinDepositLimit);
-        require_gt!(params.max_deposit_limit, 0, ErrorEarn::InvalidMaxDepositLimit);
-        require_gte!(params.max_withdraw_limit, params.min_withdraw_limit, ErrorEarn::InvalidMaxWithdrawLimitLessThanMinWithdrawLimit);
-        require_gt!(params.max_withdraw_limit, 0, ErrorEarn::InvalidMaxWithdrawLimit);
-        require_gte!(params.max_borrow_limit, params.min_borrow_limit, ErrorEarn::InvalidMaxBorrowLimitLessThanMinBorrowLimit);
-        require_gt!(params.max_borrow_limit, 0, ErrorEarn::InvalidMaxBorrowLimit);
-        require_gt!(params.floor_cap_rate, 0, ErrorEarn::InvalidFloorCapRate);
-        self.earn_fee_vault = params.earn_fee_vault;
-        self.freeze = params.freeze;
-        self.protocol_fee = params.protocol_fee;
-        self.ltv = params.ltv;
-        self.deposit_fee = params.deposit_fee;
-        self.min_deposit_limit = params.min_deposit_limit;
-        self.max_deposit_limit = params.max_deposit_limit;
-        self.withdraw_fee = params.withdraw_fee;
-        self.min_withdraw_limit = params.min_withdraw_limit;
-        self.max_withdraw_limit = params.max_withdraw_limit;
-        self.borrow_fee = params.borrow_fee;
-        self.min_borrow_limit = params.min_borrow_limit;
-        self.max_borrow_limit = params.max_borrow_limit;
-        self.floor_cap_rate = params.floor_cap_rate;
+    // Invariants of the whole config, checked on create and after every set
+    pub fn validate(&self) -> Result<()> {
+        require!(PauseFlags::is_valid(self.pause_flags), Errors::InvalidPauseFlags);
+        require_gte!(MAX_PROTOCOL_FEE, self.protocol_fee, ErrorEarn::InvalidFee);
+        require_gte!(MAX_TRANSACTION_FEE, self.deposit_fee, ErrorEarn::InvalidFee);
+        require_gte!(MAX_TRANSACTION_FEE, self.withdraw_fee, ErrorEarn::InvalidFee);
+        require_gte!(MAX_TRANSACTION_FEE, self.borrow_fee, ErrorEarn::InvalidFee);
+        require_gte!(MAX_FLASH_FEE, self.flash_fee, ErrorEarn::InvalidFlashFee);
+        require_gte!(constant::PERCENT_MAX, self.max_utilization, ErrorEarn::InvalidMaxUtilization);
+        require_gt!(self.ltv, 0, ErrorEarn::InvalidLTV);
+        require_gte!(constant::PERCENT_MAX, self.ltv, ErrorEarn::InvalidLTV);
+        require_gte!(self.max_deposit_limit, self.min_deposit_limit, ErrorEarn::InvalidMaxDepositLimitLessThanMinDepositLimit);
+        require_gt!(self.max_deposit_limit, 0, ErrorEarn::InvalidMaxDepositLimit);
+        require_gte!(self.max_withdraw_limit, self.min_withdraw_limit, ErrorEarn::InvalidMaxWithdrawLimitLessThanMinWithdrawLimit);
+        require_gt!(self.max_withdraw_limit, 0, ErrorEarn::InvalidMaxWithdrawLimit);
+        require_gte!(self.max_borrow_limit, self.min_borrow_limit, ErrorEarn::InvalidMaxBorrowLimitLessThanMinBorrowLimit);
+        require_gt!(self.max_borrow_limit, 0, ErrorEarn::InvalidMaxBorrowLimit);
+        require_gt!(self.floor_cap_rate, 0, ErrorEarn::InvalidFloorCapRate);
+        require_gte!(FLOOR_CAP_RATIO, self.floor_cap_rate, ErrorEarn::InvalidFloorCapRate);
+
+        Ok(())
+    }
+
+    // Only the provided fields are applied, the resulting config is validated as a whole
+    pub fn set_config(&mut self, params: &SetEarnConfigParams) -> Result<()> {
+        self.earn_fee_vault = params.earn_fee_vault.unwrap_or(self.earn_fee_vault);
+        self.pause_flags = params.pause_flags.unwrap_or(self.pause_flags);
+        self.protocol_fee = params.protocol_fee.unwrap_or(self.protocol_fee);
+        self.ltv = params.ltv.unwrap_or(self.ltv);
+        self.deposit_fee = params.deposit_fee.unwrap_or(self.deposit_fee);
+        self.min_deposit_limit = params.min_deposit_limit.unwrap_or(self.min_deposit_limit);
+        self.max_deposit_limit = params.max_deposit_limit.unwrap_or(self.max_deposit_limit);
+        self.withdraw_fee = params.withdraw_fee.unwrap_or(self.withdraw_fee);
+        self.min_withdraw_limit = params.min_withdraw_limit.unwrap_or(self.min_withdraw_limit);
+        self.max_withdraw_limit = params.max_withdraw_limit.unwrap_or(self.max_withdraw_limit);
+        self.borrow_fee = params.borrow_fee.unwrap_or(self.borrow_fee);
+        self.min_borrow_limit = params.min_borrow_limit.unwrap_or(self.min_borrow_limit);
+        self.max_borrow_limit = params.max_borrow_limit.unwrap_or(self.max_borrow_limit);
+        self.floor_cap_rate = params.floor_cap_rate.unwrap_or(self.floor_cap_rate);
+        self.validate()?;
         self.last_updated = Clock::get()?.unix_timestamp;
 
         Ok(())
     }
 
+    // Changes that cost lenders or borrowers wait behind the protocol timelock
+    pub fn requires_timelock(&self, params: &SetEarnConfigParams) -> bool {
+        params.protocol_fee.is_some_and(|protocol_fee| protocol_fee > self.protocol_fee)
+            || params.deposit_fee.is_some_and(|deposit_fee| deposit_fee > self.deposit_fee)
+            || params.withdraw_fee.is_some_and(|withdraw_fee| withdraw_fee > self.withdraw_fee)
+            || params.borrow_fee.is_some_and(|borrow_fee| borrow_fee > self.borrow_fee)
+            || params.ltv.is_some_and(|ltv| ltv < self.ltv)
+    }
+
+    // Role allowed to make the change alone, None when it needs the owner
+    pub fn required_role(&self, params: &SetEarnConfigParams) -> Option<Role> {
+        let pause_changed = is_changed(params.pause_flags, self.pause_flags);
+        let pauses_only = params.pause_flags.map_or(true, |pause_flags| pause_flags & self.pause_flags == self.pause_flags);
+        let fees_changed = is_changed(params.protocol_fee, self.protocol_fee)
+            || is_changed(params.deposit_fee, self.deposit_fee)
+            || is_changed(params.withdraw_fee, self.withdraw_fee)
+            || is_changed(params.borrow_fee, self.borrow_fee);
+        let risk_changed = is_changed(params.ltv, self.ltv);
+        let risk_within_bounds = params.ltv.map_or(true, |ltv| is_within_risk_step(self.ltv, ltv));
+        let others_changed = is_changed(params.earn_fee_vault, self.earn_fee_vault)
+            || is_changed(params.min_deposit_limit, self.min_deposit_limit)
+            || is_changed(params.max_deposit_limit, self.max_deposit_limit)
+            || is_changed(params.min_withdraw_limit, self.min_withdraw_limit)
+            || is_changed(params.max_withdraw_limit, self.max_withdraw_limit)
+            || is_changed(params.min_borrow_limit, self.min_borrow_limit)
+            || is_changed(params.max_borrow_limit, self.max_borrow_limit)
+            || is_changed(params.floor_cap_rate, self.floor_cap_rate);
+
+        match (pause_changed, fees_changed, risk_changed, others_changed) {
+            (true, false, false, false) if pauses_only => Some(Role::Guardian),
+            (false, true, false, false) => Some(Role::FeeManager),
+            (false, false, true, false) if risk_within_bounds => Some(Role::RiskManager),
+            _ => None,
+        }
+    }
+
+    // Risk managers can only move the caps by a bounded step, removing or adding a cap needs the owner
+    pub fn caps_required_role(&self, params: &SetCapsParams) -> Option<Role> {
+        let utilization_within_bounds = self.max_utilization == params.max_utilization
+            || (self.max_utilization > 0 && params.max_utilization > 0 && is_within_risk_step(self.max_utilization, params.max_utilization));
+
+        match (is_within_cap_step(self.supply_cap, params.supply_cap), is_within_cap_step(self.borrow_cap, params.borrow_cap), utilization_within_bounds) {
+            (true, true, true) => Some(Role::RiskManager),
+            _ => None,
+        }
+    }
+
     pub fn change_indexer(&mut self, indexer: Pubkey) -> Result<()> {
         self.indexer = indexer;
         Ok(())
     }
+
+    pub fn set_rate_model(&mut self, params: SetRateModelParams) -> Result<()> {
+        require_gt!(params.optimal_utilization, 0, ErrorEarn::InvalidOptimalUtilization);
+        require_gt!(constant::PERCENT_MAX, params.optimal_utilization, ErrorEarn::InvalidOptimalUtilization);
+        require_gte!(params.slope2, params.slope1, ErrorEarn::InvalidRateSlope);
+        self.base_rate = params.base_rate;
+        self.slope1 = params.slope1;
+        self.optimal_utilization = params.optimal_utilization;
+        self.slope2 = params.slope2;
+        self.last_updated = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+
+    pub fn set_flash_fee(&mut self, flash_fee: u32) -> Result<()> {
+        self.flash_fee = flash_fee;
+        self.validate()?;
+        self.last_updated = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+
+    pub fn set_caps(&mut self, params: SetCapsParams) -> Result<()> {
+        self.supply_cap = params.supply_cap;
+        self.borrow_cap = params.borrow_cap;
+        self.max_utilization = params.max_utilization;
+        self.validate()?;
+        self.last_updated = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+
+    // Fee of a flash loan, ceiled so small loans are not free
+    pub fn flash_fee_amount(&self, amount: u64) -> Result<u64> {
+        let fee_amount = (amount as u128)
+            .checked_mul(self.flash_fee as u128).ok_or(MathOverflow)?
+            .checked_add(constant::PERCENT_MAX as u128 - 1).ok_or(MathOverflow)?
+            .checked_div(constant::PERCENT_MAX as u128).ok_or(MathOverflow)?;
+        Ok(u64::try_from(fee_amount).map_err(|_| MathOverflow)?)
+    }
+
+    // Rate model is disabled until an optimal utilization is set, the indexer drives the index meanwhile
+    pub fn has_rate_model(&self) -> bool {
+        self.optimal_utilization > 0
+    }
+
+    // Kinked borrow rate: base + slope1 up to the optimal utilization, then slope2 up to 100%
+    pub fn borrow_rate(&self, utilization_rate: u32) -> Result<u32> {
+        let utilization_rate = utilization_rate.min(constant::PERCENT_MAX) as u64;
+        let optimal_utilization = self.optimal_utilization as u64;
+        let rate = if utilization_rate <= optimal_utilization {
+            (self.slope1 as u64).checked_mul(utilization_rate).ok_or(MathOverflow)?
+                .checked_div(optimal_utilization).ok_or(MathOverflow)?
+        } else {
+            let excess = utilization_rate.checked_sub(optimal_utilization).ok_or(MathOverflow)?;
+            let excess_range = (constant::PERCENT_MAX as u64).checked_sub(optimal_utilization).ok_or(MathOverflow)?;
+            (self.slope2 as u64).checked_mul(excess).ok_or(MathOverflow)?
+                .checked_div(excess_range).ok_or(MathOverflow)?
+                .checked_add(self.slope1 as u64).ok_or(MathOverflow)?
+        };
+        let rate = rate.checked_add(self.base_rate as u64).ok_or(MathOverflow)?;
+        Ok(u32::try_from(rate).map_err(|_| MathOverflow)?)
+    }
+
+    // Lenders earn the borrow rate on the borrowed share of the supply
+    pub fn supply_rate(&self, utilization_rate: u32, borrow_rate: u32) -> Result<u32> {
+        let utilization_rate = utilization_rate.min(constant::PERCENT_MAX) as u64;
+        let rate = (borrow_rate as u64).checked_mul(utilization_rate).ok_or(MathOverflow)?
+            .checked_div(constant::PERCENT_MAX as u64).ok_or(MathOverflow)?;
+        Ok(rate as u32)
+    }
 }
 
 pub struct InitEarnConfigParams {
@@ -175,7 +336,7 @@ pub struct InitEarnConfigParams {
     pub authority: Pubkey,
     pub indexer: Pubkey,
     pub earn_fee_vault: Pubkey,
-    pub freeze: bool,
+    pub pause_flags: u16,
     pub protocol_fee: u32,
     pub ltv: u32,
     pub deposit_fee: u32,
@@ -190,19 +351,34 @@ pub struct InitEarnConfigParams {
     pub floor_cap_rate: u32,
 }
 
+// Fields left to None keep their current value
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
 pub struct SetEarnConfigParams {
-    pub earn_fee_vault: Pubkey,
-    pub freeze: bool,
-    pub protocol_fee: u32,
-    pub ltv: u32,
-    pub deposit_fee: u32,
-    pub min_deposit_limit: u64,
-    pub max_deposit_limit: u64,
-    pub withdraw_fee: u32,
-    pub min_withdraw_limit: u64,
-    pub max_withdraw_limit: u64,
-    pub borrow_fee: u32,
-    pub min_borrow_limit: u64,
-    pub max_borrow_limit: u64,
-    pub floor_cap_rate: u32,
+    pub earn_fee_vault: Option<Pubkey>,
+    pub pause_flags: Option<u16>,
+    pub protocol_fee: Option<u32>,
+    pub ltv: Option<u32>,
+    pub deposit_fee: Option<u32>,
+    pub min_deposit_limit: Option<u64>,
+    pub max_deposit_limit: Option<u64>,
+    pub withdraw_fee: Option<u32>,
+    pub min_withdraw_limit: Option<u64>,
+    pub max_withdraw_limit: Option<u64>,
+    pub borrow_fee: Option<u32>,
+    pub min_borrow_limit: Option<u64>,
+    pub max_borrow_limit: Option<u64>,
+    pub floor_cap_rate: Option<u32>,
+}
+
+pub struct SetRateModelParams {
+    pub base_rate: u32,
+    pub slope1: u32,
+    pub optimal_utilization: u32,
+    pub slope2: u32,
+}
+
+pub struct SetCapsParams {
+    pub supply_cap: u64,
+    pub borrow_cap: u64,
+    pub max_utilization: u32,
 }
\ No newline at end of file
@@ -0,0 +1,43 @@
+use anchor_lang::{InitSpace};
+use anchor_lang::prelude::*;
+use derivative::Derivative;
+use crate::util::oracle_source::OracleSource;
+
+// Additional price oracle of a vault side, read next to the primary oracle for the median price
+#[derive(InitSpace, Derivative, PartialEq)]
+#[derivative(Debug)]
+#[zero_copy(unsafe)]
+#[repr(C)]
+pub struct FallbackOracle {
+    pub source: OracleSource,
+    #[derivative(Debug = "ignore")]
+    pub align0: [u8; 7],
+    pub oracle: Pubkey, // default = empty slot
+    pub feed: [u8; 64],
+}
+
+impl Default for FallbackOracle {
+    fn default() -> Self {
+        Self {
+            source: OracleSource::Pyth,
+            align0: [0; 7],
+            oracle: Pubkey::default(),
+            feed: [0; 64],
+        }
+    }
+}
+
+impl FallbackOracle {
+    pub fn new(source: OracleSource, oracle: Pubkey, feed: [u8; 64]) -> Self {
+        Self {
+            source,
+            oracle,
+            feed,
+            ..Self::default()
+        }
+    }
+
+    pub fn is_set(&self) -> bool {
+        self.oracle != Pubkey::default()
+    }
+}
\ No newline at end of file
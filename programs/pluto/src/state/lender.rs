@@ -30,8 +30,9 @@ pub struct Lender {
     pub pending_withdraw_index: u128,
     pub unit: u64,
     pub index: u128,
+    pub share_unit: u64, // part of the unit already minted as share tokens
     #[derivative(Debug = "ignore")]
-    pub padding1: [u64; 10],
+    pub padding1: [u64; 9],
 }
 
 impl Lender {
@@ -85,26 +86,16 @@ impl Lender {
         Ok(())
     }
 
-    pub fn withdraw(&mut self, amount: u64, unit: u64, index: u128) -> Result<()> {
-        require!(unit > 0, Errors::InvalidAmountZero);
-        require_eq!(self.pending_withdraw_amount, 0, Errors::IncompleteProcess);
-        require_eq!(self.pending_withdraw_unit, 0, Errors::IncompleteProcess);
-        self.unit = self.unit.saturating_sub(unit);
-        self.pending_withdraw_amount = amount;
-        self.pending_withdraw_unit = unit;
-        self.pending_withdraw_index = index;
-
-        Ok(())
+    // Units deposited but not minted as share tokens yet
+    pub fn untokenized_unit(&self) -> u64 {
+        self.unit.saturating_sub(self.share_unit)
     }
 
-    pub fn confirm_withdraw(&mut self) -> Result<()> {
-        require_gt!(self.pending_withdraw_amount, 0, Errors::InvalidAmountZero);
-        require_gt!(self.pending_withdraw_unit, 0, Errors::InvalidAmountZero);
-        self.pending_withdraw_amount = 0;
-        self.pending_withdraw_unit = 0;
-        self.pending_withdraw_index = 0;
+    pub fn tokenize(&mut self) -> Result<u64> {
+        let unit = self.untokenized_unit();
+        self.share_unit = self.unit;
 
-        Ok(())
+        Ok(unit)
     }
 }
 
@@ -3,8 +3,11 @@ use anchor_lang::prelude::*;
 use derivative::Derivative;
 use crate::error::{Errors, ErrorLeverage};
 use crate::error::ErrorMath::MathOverflow;
+use crate::state::Role;
 use crate::util::{constant, decimals};
-use crate::util::constant::{INDEX_DECIMALS, INDEX_ONE, PERCENT_DECIMALS, UNIT_DECIMALS};
+use crate::util::access::{is_changed, is_within_risk_step};
+use crate::util::pause::PauseFlags;
+use crate::util::constant::{HEALTH_FACTOR_ONE, INDEX_DECIMALS, INDEX_ONE, LEVERAGE_ONE, MAX_LEVERAGE, MAX_LIQUIDATION_FEE, MAX_LIQUIDATOR_SLIPPAGE, MAX_PROTOCOL_FEE, MAX_TRANSACTION_FEE, MIN_LEVERAGE, PERCENT_DECIMALS, UNIT_DECIMALS};
 
 #[derive(InitSpace, Derivative, PartialEq)]
 #[derivative(Debug)]
@@ -22,9 +25,9 @@ pub struct LeverageConfig {
     pub indexer: Pubkey,
     pub keeper: Pubkey,
     pub leverage_fee_vault: Pubkey,
-    pub freeze: bool,
+    pub pause_flags: u16, // paused operations of the config vaults, see PauseFlags
     #[derivative(Debug = "ignore")]
-    pub align1: [u8; 7],
+    pub align1: [u8; 6],
     #[derivative(Default(value="0u32"))] // 0%
     pub protocol_fee: u32, // protocol fee in percentage 100% = 10^5
     #[derivative(Default(value="15 * 10"))] // 1.5x
@@ -69,8 +72,11 @@ pub struct LeverageConfig {
     pub last_updated: i64,
     pub profit_target_rate: u32,
     pub profit_taking_rate: u32,
+    pub insurance_ratio: u32, // share of protocol and liquidation fees sent to the borrow vault insurance fund 100% = 10^5
     #[derivative(Debug = "ignore")]
-    pub padding1: [u64; 31],
+    pub align4: [u8; 4],
+    #[derivative(Debug = "ignore")]
+    pub padding1: [u64; 30],
 }
 
 impl Default for LeverageConfig {
@@ -86,8 +92,8 @@ impl Default for LeverageConfig {
             indexer: Pubkey::default(),
             keeper: Pubkey::default(),
             leverage_fee_vault: Pubkey::default(),
-            freeze: false,
-            align1: [0; 7],
+            pause_flags: 0,
+            align1: [0; 6],
             protocol_fee: 0,
             min_leverage: 0,
             max_leverage: 0,
@@ -112,29 +118,15 @@ impl Default for LeverageConfig {
             last_updated: 0,
             profit_target_rate: 0,
             profit_taking_rate: 0,
-            padding1: [0; 31],
+            insurance_ratio: 0,
+            align4: [0; 4],
+            padding1: [0; 30],
         }
     }
 }
 
 impl LeverageConfig {
     pub fn init(&mut self, params: InitLeverageConfigParams) -> Result<()> {
-        require_gte!(params.protocol_fee, 0, ErrorLeverage::InvalidProtocolFee);
-        require_gte!(params.max_leverage, params.min_leverage, ErrorLeverage::InvalidMaxLeverageLessThanMinLeverage);
-        require_gt!(params.max_leverage, 1, ErrorLeverage::InvalidMaxLeverage);
-        require_gt!(params.min_leverage, 1, ErrorLeverage::InvalidMinLeverage);
-        require_gt!(params.leverage_step, 0, ErrorLeverage::InvalidLeverageStep);
-        require_gte!(params.max_leverage_limit, params.min_leverage_limit, ErrorLeverage::InvalidMaxLeverageLessThanMinLeverage);
-        require_gt!(params.max_leverage_limit, 0, ErrorLeverage::InvalidMaxLeverageLimit);
-        require_gt!(params.min_leverage_limit, 0, ErrorLeverage::InvalidMinLeverageLimit);
-        require_gte!(params.max_deleverage_limit, params.min_deleverage_limit, ErrorLeverage::InvalidMaxDeleverageLessThanMinDeleverage);
-        require_gt!(params.max_deleverage_limit, 0, ErrorLeverage::InvalidMaxDeleverageLimit);
-        require_gt!(params.min_deleverage_limit, 0, ErrorLeverage::InvalidMinDeleverageLimit);
-        require_gt!(params.spread_rate, 0, ErrorLeverage::InvalidSpreadRate);
-        require_gt!(params.saver_threshold, 0, ErrorLeverage::InvalidSaverThreshold);
-        require_gt!(params.saver_target_reduction, 0, ErrorLeverage::InvalidSaverTarget);
-        require_gte!(params.saver_target_reduction, params.leverage_step, ErrorLeverage::InvalidSaverTargetLessThanLeverageStep);
-        require!(params.saver_target_reduction % params.leverage_step == 0, ErrorLeverage::InvalidSaverTargetNotMultipleOfLeverageStep);
         *self = Self::default();
         self.is_initialized = true;
         self.version = 1;
@@ -145,7 +137,7 @@ impl LeverageConfig {
         self.indexer = params.indexer;
         self.keeper = params.keeper;
         self.leverage_fee_vault = params.leverage_fee_vault;
-        self.freeze = params.freeze;
+        self.pause_flags = params.pause_flags;
         self.protocol_fee = params.protocol_fee;
         self.min_leverage = params.min_leverage;
         self.max_leverage = params.max_leverage;
@@ -165,54 +157,151 @@ impl LeverageConfig {
         self.emergency_eject_period = params.emergency_eject_period;
         self.saver_threshold = params.saver_threshold;
         self.saver_target_reduction = params.saver_target_reduction;
+        self.validate()?;
         self.last_updated = Clock::get()?.unix_timestamp;
 
         Ok(())
     }
 
-    pub fn set_config(&mut self, params: SetLeverageConfigParams) -> Result<()> {
-        require_gte!(params.protocol_fee, 0, ErrorLeverage::InvalidProtocolFee);
-        require_gte!(params.max_leverage, params.min_leverage, ErrorLeverage::InvalidMaxLeverageLessThanMinLeverage);
-        require_gt!(params.max_leverage, 1, ErrorLeverage::InvalidMaxLeverage);
-        require_gt!(params.min_leverage, 1, ErrorLeverage::InvalidMinLeverage);
-        require_gt!(params.leverage_step, 0, ErrorLeverage::InvalidLeverageStep);
-        require_gte!(params.max_leverage_limit, params.min_leverage_limit, ErrorLeverage::InvalidMaxLeverageLessThanMinLeverage);
-        require_gt!(params.max_leverage_limit, 0, ErrorLeverage::InvalidMaxLeverageLimit);
-        require_gt!(params.min_leverage_limit, 0, ErrorLeverage::InvalidMinLeverageLimit);
-        require_gte!(params.max_deleverage_limit, params.min_deleverage_limit, ErrorLeverage::InvalidMaxDeleverageLessThanMinDeleverage);
-        require_gt!(params.max_deleverage_limit, 0, ErrorLeverage::InvalidMaxDeleverageLimit);
-        require_gt!(params.min_deleverage_limit, 0, ErrorLeverage::InvalidMinDeleverageLimit);
-        require_gt!(params.spread_rate, 0, ErrorLeverage::InvalidSpreadRate);
-        require_gt!(params.saver_threshold, 0, ErrorLeverage::InvalidSaverThreshold);
-        require_gt!(params.saver_target_reduction, 0, ErrorLeverage::InvalidSaverTarget);
-        require_gte!(params.saver_target_reduction, params.leverage_step, ErrorLeverage::InvalidSaverTargetLessThanLeverageStep);
-        require!(params.saver_target_reduction % params.leverage_step == 0, ErrorLeverage::InvalidSaverTargetNotMultipleOfLeverageStep);
-        self.leverage_fee_vault = params.leverage_fee_vault;
-        self.freeze = params.freeze;
-        self.protocol_fee = params.protocol_fee;
-        self.min_leverage = params.min_leverage;
-        self.max_leverage = params.max_leverage;
-        self.leverage_step = params.leverage_step;
-        self.leverage_fee = params.leverage_fee;
-        self.min_leverage_limit = params.min_leverage_limit;
-        self.max_leverage_limit = params.max_leverage_limit;
-        self.deleverage_fee = params.deleverage_fee;
-        self.min_deleverage_limit = params.min_deleverage_limit;
-        self.max_deleverage_limit = params.max_deleverage_limit;
-        self.closing_fee = params.closing_fee;
-        self.spread_rate = params.spread_rate;
-        self.liquidation_fee = params.liquidation_fee;
-        self.liquidation_threshold = params.liquidation_threshold;
-        self.liquidation_protocol_ratio = params.liquidation_protocol_ratio;
-        self.slippage_rate = params.slippage_rate;
-        self.emergency_eject_period = params.emergency_eject_period;
-        self.saver_threshold = params.saver_threshold;
-        self.saver_target_reduction = params.saver_target_reduction;
+    // Invariants of the whole config, checked on create and after every set
+    pub fn validate(&self) -> Result<()> {
+        require!(PauseFlags::is_valid(self.pause_flags), Errors::InvalidPauseFlags);
+        require_gte!(MAX_PROTOCOL_FEE, self.protocol_fee, ErrorLeverage::InvalidProtocolFee);
+        require_gte!(MAX_TRANSACTION_FEE, self.leverage_fee, ErrorLeverage::InvalidFee);
+        require_gte!(MAX_TRANSACTION_FEE, self.deleverage_fee, ErrorLeverage::InvalidFee);
+        require_gte!(MAX_TRANSACTION_FEE, self.closing_fee, ErrorLeverage::InvalidFee);
+        require_gte!(MAX_LIQUIDATION_FEE, self.liquidation_fee, ErrorLeverage::InvalidFee);
+        require_gte!(constant::PERCENT_MAX, self.insurance_ratio, ErrorLeverage::InvalidInsuranceRatio);
+        require_gte!(self.max_leverage, self.min_leverage, ErrorLeverage::InvalidMaxLeverageLessThanMinLeverage);
+        require_gte!(self.min_leverage, MIN_LEVERAGE, ErrorLeverage::InvalidMinLeverage);
+        require_gte!(MAX_LEVERAGE, self.max_leverage, ErrorLeverage::InvalidMaxLeverage);
+        require_gt!(self.leverage_step, 0, ErrorLeverage::InvalidLeverageStep);
+        require_gte!(self.max_leverage_limit, self.min_leverage_limit, ErrorLeverage::InvalidMaxLeverageLimitLessThanMinLeverage);
+        require_gt!(self.max_leverage_limit, 0, ErrorLeverage::InvalidMaxLeverageLimit);
+        require_gt!(self.min_leverage_limit, 0, ErrorLeverage::InvalidMinLeverageLimit);
+        require_gte!(self.max_deleverage_limit, self.min_deleverage_limit, ErrorLeverage::InvalidMaxDeleverageLessThanMinDeleverage);
+        require_gt!(self.max_deleverage_limit, 0, ErrorLeverage::InvalidMaxDeleverageLimit);
+        require_gt!(self.min_deleverage_limit, 0, ErrorLeverage::InvalidMinDeleverageLimit);
+        require_gt!(self.spread_rate, 0, ErrorLeverage::InvalidSpreadRate);
+        require_gt!(constant::PERCENT_MAX, self.spread_rate, ErrorLeverage::InvalidSpreadRate);
+        require_gte!(self.spread_rate, self.slippage_rate, ErrorLeverage::InvalidSlippageRate);
+        require_gt!(self.liquidation_threshold, 0, ErrorLeverage::InvalidLiquidationThreshold);
+        require_gte!(constant::PERCENT_MAX, self.liquidation_threshold, ErrorLeverage::InvalidLiquidationThreshold);
+        require_gte!(constant::PERCENT_MAX, self.liquidation_protocol_ratio, ErrorLeverage::InvalidLiquidationProtocolRatio);
+        require_gte!(self.emergency_eject_period, 0, ErrorLeverage::InvalidEmergencyEjectPeriod);
+        require_gt!(self.saver_threshold, HEALTH_FACTOR_ONE, ErrorLeverage::InvalidSaverThreshold);
+        require_gt!(self.saver_target_reduction, 0, ErrorLeverage::InvalidSaverTarget);
+        require_gte!(self.saver_target_reduction, self.leverage_step, ErrorLeverage::InvalidSaverTargetLessThanLeverageStep);
+        require!(self.saver_target_reduction % self.leverage_step == 0, ErrorLeverage::InvalidSaverTargetNotMultipleOfLeverageStep);
+
+        // A position opened at max leverage has to start above the liquidation health factor
+        let initial_health_factor = (self.max_leverage as u128)
+            .checked_mul(self.liquidation_threshold as u128).ok_or(MathOverflow)?
+            .checked_mul(HEALTH_FACTOR_ONE as u128).ok_or(MathOverflow)?
+            .checked_div(((self.max_leverage - LEVERAGE_ONE) as u128).checked_mul(constant::PERCENT_MAX as u128).ok_or(MathOverflow)?).ok_or(MathOverflow)?;
+        require_gt!(initial_health_factor, HEALTH_FACTOR_ONE as u128, ErrorLeverage::InvalidMaxLeverageHealthFactor);
+
+        Ok(())
+    }
+
+    // Only the provided fields are applied, the resulting config is validated as a whole
+    pub fn set_config(&mut self, params: &SetLeverageConfigParams) -> Result<()> {
+        self.leverage_fee_vault = params.leverage_fee_vault.unwrap_or(self.leverage_fee_vault);
+        self.pause_flags = params.pause_flags.unwrap_or(self.pause_flags);
+        self.protocol_fee = params.protocol_fee.unwrap_or(self.protocol_fee);
+        self.min_leverage = params.min_leverage.unwrap_or(self.min_leverage);
+        self.max_leverage = params.max_leverage.unwrap_or(self.max_leverage);
+        self.leverage_step = params.leverage_step.unwrap_or(self.leverage_step);
+        self.leverage_fee = params.leverage_fee.unwrap_or(self.leverage_fee);
+        self.min_leverage_limit = params.min_leverage_limit.unwrap_or(self.min_leverage_limit);
+        self.max_leverage_limit = params.max_leverage_limit.unwrap_or(self.max_leverage_limit);
+        self.deleverage_fee = params.deleverage_fee.unwrap_or(self.deleverage_fee);
+        self.min_deleverage_limit = params.min_deleverage_limit.unwrap_or(self.min_deleverage_limit);
+        self.max_deleverage_limit = params.max_deleverage_limit.unwrap_or(self.max_deleverage_limit);
+        self.closing_fee = params.closing_fee.unwrap_or(self.closing_fee);
+        self.spread_rate = params.spread_rate.unwrap_or(self.spread_rate);
+        self.liquidation_fee = params.liquidation_fee.unwrap_or(self.liquidation_fee);
+        self.liquidation_threshold = params.liquidation_threshold.unwrap_or(self.liquidation_threshold);
+        self.liquidation_protocol_ratio = params.liquidation_protocol_ratio.unwrap_or(self.liquidation_protocol_ratio);
+        self.slippage_rate = params.slippage_rate.unwrap_or(self.slippage_rate);
+        self.emergency_eject_period = params.emergency_eject_period.unwrap_or(self.emergency_eject_period);
+        self.saver_threshold = params.saver_threshold.unwrap_or(self.saver_threshold);
+        self.saver_target_reduction = params.saver_target_reduction.unwrap_or(self.saver_target_reduction);
+        self.validate()?;
+        self.last_updated = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+
+    // Changes that cost position holders wait behind the protocol timelock
+    pub fn requires_timelock(&self, params: &SetLeverageConfigParams) -> bool {
+        params.protocol_fee.is_some_and(|protocol_fee| protocol_fee > self.protocol_fee)
+            || params.leverage_fee.is_some_and(|leverage_fee| leverage_fee > self.leverage_fee)
+            || params.deleverage_fee.is_some_and(|deleverage_fee| deleverage_fee > self.deleverage_fee)
+            || params.closing_fee.is_some_and(|closing_fee| closing_fee > self.closing_fee)
+            || params.liquidation_fee.is_some_and(|liquidation_fee| liquidation_fee > self.liquidation_fee)
+            || params.liquidation_threshold.is_some_and(|liquidation_threshold| liquidation_threshold < self.liquidation_threshold)
+    }
+
+    // Role allowed to make the change alone, None when it needs the owner
+    pub fn required_role(&self, params: &SetLeverageConfigParams) -> Option<Role> {
+        let pause_changed = is_changed(params.pause_flags, self.pause_flags);
+        let pauses_only = params.pause_flags.map_or(true, |pause_flags| pause_flags & self.pause_flags == self.pause_flags);
+        let fees_changed = is_changed(params.protocol_fee, self.protocol_fee)
+            || is_changed(params.leverage_fee, self.leverage_fee)
+            || is_changed(params.deleverage_fee, self.deleverage_fee)
+            || is_changed(params.closing_fee, self.closing_fee)
+            || is_changed(params.liquidation_fee, self.liquidation_fee);
+        let risk_changed = is_changed(params.liquidation_threshold, self.liquidation_threshold)
+            || is_changed(params.saver_threshold, self.saver_threshold);
+        let risk_within_bounds = params.liquidation_threshold.map_or(true, |liquidation_threshold| is_within_risk_step(self.liquidation_threshold, liquidation_threshold))
+            && params.saver_threshold.map_or(true, |saver_threshold| is_within_risk_step(self.saver_threshold, saver_threshold));
+        let others_changed = is_changed(params.leverage_fee_vault, self.leverage_fee_vault)
+            || is_changed(params.min_leverage, self.min_leverage)
+            || is_changed(params.max_leverage, self.max_leverage)
+            || is_changed(params.leverage_step, self.leverage_step)
+            || is_changed(params.min_leverage_limit, self.min_leverage_limit)
+            || is_changed(params.max_leverage_limit, self.max_leverage_limit)
+            || is_changed(params.min_deleverage_limit, self.min_deleverage_limit)
+            || is_changed(params.max_deleverage_limit, self.max_deleverage_limit)
+            || is_changed(params.spread_rate, self.spread_rate)
+            || is_changed(params.liquidation_protocol_ratio, self.liquidation_protocol_ratio)
+            || is_changed(params.slippage_rate, self.slippage_rate)
+            || is_changed(params.emergency_eject_period, self.emergency_eject_period)
+            || is_changed(params.saver_target_reduction, self.saver_target_reduction);
+
+        match (pause_changed, fees_changed, risk_changed, others_changed) {
+            (true, false, false, false) if pauses_only => Some(Role::Guardian),
+            (false, true, false, false) => Some(Role::FeeManager),
+            (false, false, true, false) if risk_within_bounds => Some(Role::RiskManager),
+            _ => None,
+        }
+    }
+
+    pub fn set_insurance_ratio(&mut self, insurance_ratio: u32) -> Result<()> {
+        self.insurance_ratio = insurance_ratio;
+        self.validate()?;
         self.last_updated = Clock::get()?.unix_timestamp;
 
         Ok(())
     }
 
+    // Slippage allowed on a release swap, callers other than the keeper get a tighter bounded tolerance
+    pub fn slippage_rate_for(&self, caller: Pubkey) -> u32 {
+        if caller == self.keeper {
+            self.slippage_rate
+        } else {
+            self.slippage_rate.min(MAX_LIQUIDATOR_SLIPPAGE)
+        }
+    }
+
+    // Part of a fee routed to the insurance fund, the rest goes to the fee vault
+    pub fn insurance_amount(&self, fee_amount: u64) -> Result<u64> {
+        Ok((fee_amount as u128)
+            .checked_mul(self.insurance_ratio as u128).ok_or(MathOverflow)?
+            .checked_div(constant::PERCENT_MAX as u128).ok_or(MathOverflow)? as u64)
+    }
+
     pub fn change_indexer(&mut self, indexer: Pubkey) -> Result<()> {
         self.indexer = indexer;
         Ok(())
@@ -232,7 +321,7 @@ pub struct InitLeverageConfigParams {
     pub indexer: Pubkey,
     pub keeper: Pubkey,
     pub leverage_fee_vault: Pubkey,
-    pub freeze: bool,
+    pub pause_flags: u16,
     pub protocol_fee: u32,
     pub min_leverage: u32,
     pub max_leverage: u32,
@@ -254,26 +343,28 @@ pub struct InitLeverageConfigParams {
     pub saver_target_reduction: u32,
 }
 
+// Fields left to None keep their current value
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
 pub struct SetLeverageConfigParams {
-    pub leverage_fee_vault: Pubkey,
-    pub freeze: bool,
-    pub protocol_fee: u32,
-    pub min_leverage: u32,
-    pub max_leverage: u32,
-    pub leverage_step: u32,
-    pub leverage_fee: u32,
-    pub min_leverage_limit: u64,
-    pub max_leverage_limit: u64,
-    pub deleverage_fee: u32,
-    pub min_deleverage_limit: u64,
-    pub max_deleverage_limit: u64,
-    pub closing_fee: u32,
-    pub spread_rate: u32,
-    pub liquidation_fee: u32,
-    pub liquidation_threshold: u32,
-    pub liquidation_protocol_ratio: u32,
-    pub slippage_rate: u32,
-    pub emergency_eject_period: i64,
-    pub saver_threshold: u32,
-    pub saver_target_reduction: u32,
+    pub leverage_fee_vault: Option<Pubkey>,
+    pub pause_flags: Option<u16>,
+    pub protocol_fee: Option<u32>,
+    pub min_leverage: Option<u32>,
+    pub max_leverage: Option<u32>,
+    pub leverage_step: Option<u32>,
+    pub leverage_fee: Option<u32>,
+    pub min_leverage_limit: Option<u64>,
+    pub max_leverage_limit: Option<u64>,
+    pub deleverage_fee: Option<u32>,
+    pub min_deleverage_limit: Option<u64>,
+    pub max_deleverage_limit: Option<u64>,
+    pub closing_fee: Option<u32>,
+    pub spread_rate: Option<u32>,
+    pub liquidation_fee: Option<u32>,
+    pub liquidation_threshold: Option<u32>,
+    pub liquidation_protocol_ratio: Option<u32>,
+    pub slippage_rate: Option<u32>,
+    pub emergency_eject_period: Option<i64>,
+    pub saver_threshold: Option<u32>,
+    pub saver_target_reduction: Option<u32>,
 }
\ No newline at end of file
@@ -4,22 +4,34 @@ pub mod leverage_config;
 pub mod vault_earn;
 pub mod lender;
 pub mod rate;
+pub mod oracle_guard;
+pub mod fallback_oracle;
 pub mod vault_leverage;
 pub mod obligation;
 pub mod position;
 pub mod position_state;
 pub mod position_settings;
+pub mod swap_routers;
 pub mod stats;
+pub mod withdraw_request;
+pub mod pending_config;
+pub mod roles;
 
 pub use protocol::*;
 pub use earn_config::*;
 pub use leverage_config::*;
 pub use vault_earn::*;
 pub use rate::*;
+pub use oracle_guard::*;
+pub use fallback_oracle::*;
 pub use vault_leverage::*;
 pub use obligation::*;
 pub use position::*;
 pub use position_state::*;
 pub use position_settings::*;
 pub use lender::*;
-pub use stats::*;
\ No newline at end of file
+pub use swap_routers::*;
+pub use stats::*;
+pub use withdraw_request::*;
+pub use pending_config::*;
+pub use roles::*;
\ No newline at end of file
@@ -1,8 +1,13 @@
+use std::cell::RefMut;
+use std::mem::size_of;
+use std::ops::{Deref, DerefMut};
 use anchor_lang::{account, InitSpace};
+use anchor_lang::__private::bytemuck;
 use anchor_lang::prelude::*;
 use derivative::Derivative;
 use crate::error::{ErrorLeverage};
 use crate::state::Position;
+use crate::util::constant::OBLIGATION_INLINE_POSITIONS;
 
 #[derive(InitSpace, Derivative, PartialEq)]
 #[derivative(Debug)]
@@ -20,8 +25,11 @@ pub struct Obligation {
     pub borrow_vault: Pubkey,
     pub last_updated: i64,
     pub positions: [Position; 3],
+    pub extra_positions: u32,
     #[derivative(Debug = "ignore")]
-    pub padding1: [u64; 64],
+    pub align1: [u8; 4],
+    #[derivative(Debug = "ignore")]
+    pub padding1: [u64; 63],
 }
 
 impl Default for Obligation {
@@ -37,7 +45,9 @@ impl Default for Obligation {
             borrow_vault: Pubkey::default(),
             last_updated: 0,
             positions: [Position::default(); 3],
-            padding1: [0; 64],
+            extra_positions: 0,
+            align1: [0; 4],
+            padding1: [0; 63],
         }
     }
 }
@@ -58,14 +68,88 @@ impl Obligation {
         Ok(())
     }
 
+    // Account size holding the inline positions and the extra positions after them
+    pub fn space_for(extra_positions: u32) -> usize {
+        8 + size_of::<Obligation>() + extra_positions as usize * size_of::<Position>()
+    }
+
+    pub fn capacity(&self) -> usize {
+        OBLIGATION_INLINE_POSITIONS + self.extra_positions as usize
+    }
+
+    // Loads the obligation together with the extra positions stored after it
+    pub fn load_with_positions_mut<'a>(loader: &'a AccountLoader<'_, Obligation>) -> Result<ObligationMut<'a>> {
+        let extra_positions = loader.load_mut()?.extra_positions as usize;
+
+        let header_end = 8 + size_of::<Obligation>();
+        let extra_end = header_end + extra_positions * size_of::<Position>();
+
+        let data = loader.as_ref().try_borrow_mut_data()?;
+        require_gte!(data.len(), extra_end, ErrorLeverage::InvalidObligationSize);
+
+        let (obligation, extra) = RefMut::map_split(data, |data| {
+            let (header, extra) = data.split_at_mut(header_end);
+            (
+                bytemuck::from_bytes_mut::<Obligation>(&mut header[8..]),
+                bytemuck::cast_slice_mut::<u8, Position>(&mut extra[..extra_end - header_end]),
+            )
+        });
+
+        Ok(ObligationMut { obligation, extra })
+    }
+
+    pub fn update_time(&mut self) -> Result<()> {
+        self.last_updated = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+}
+
+pub struct ObligationMut<'a> {
+    obligation: RefMut<'a, Obligation>,
+    extra: RefMut<'a, [Position]>,
+}
+
+impl Deref for ObligationMut<'_> {
+    type Target = Obligation;
+
+    fn deref(&self) -> &Self::Target {
+        &self.obligation
+    }
+}
+
+impl DerefMut for ObligationMut<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.obligation
+    }
+}
+
+impl ObligationMut<'_> {
+    pub fn position_mut(&mut self, number: u8) -> Result<&mut Position> {
+        let index = number as usize;
+        if index < OBLIGATION_INLINE_POSITIONS {
+            Ok(&mut self.obligation.positions[index])
+        } else {
+            let position = self.extra
+                .get_mut(index - OBLIGATION_INLINE_POSITIONS)
+                .ok_or(ErrorLeverage::InvalidPositionNumber)?;
+            Ok(position)
+        }
+    }
+
+    fn positions(&self) -> impl Iterator<Item = &Position> {
+        self.obligation.positions.iter().chain(self.extra.iter())
+    }
+
+    fn find_index(&self, predicate: impl FnMut(&Position) -> bool) -> Option<u8> {
+        self.positions().position(predicate).map(|index| index as u8)
+    }
+
     pub fn generate_id(&mut self) -> Result<Pubkey> {
-        if let Some(index) = self.positions
-            .iter_mut()
-            .position(|p| p.id == Pubkey::default() && p.owner == Pubkey::default()) {
+        if let Some(index) = self.find_index(|p| p.id == Pubkey::default() && p.owner == Pubkey::default()) {
             let id = Pubkey::create_with_seed(
-                &self.owner,
+                &self.obligation.owner,
                 &index.to_string(),
-                &self.vault,
+                &self.obligation.vault,
             ).unwrap();
             Ok(id)
         } else {
@@ -74,16 +158,12 @@ impl Obligation {
     }
 
     pub fn find_or_add_position(&mut self, id: Pubkey, init_function: impl FnOnce(&mut Position) -> Result<()>,) -> Result<&mut Position> {
-        if let Some(index) = self.positions
-            .iter()
-            .position(|p| p.id == id) {
-            let position = &mut self.positions[index];
+        if let Some(index) = self.find_index(|p| p.id == id) {
+            let position = self.position_mut(index)?;
             position.number = index as i8;
             Ok(position)
-        } else if let Some(index) = self.positions
-            .iter_mut()
-            .position(|p| p.id == Pubkey::default() && p.owner == Pubkey::default()) {
-            let position = &mut self.positions[index];
+        } else if let Some(index) = self.find_index(|p| p.id == Pubkey::default() && p.owner == Pubkey::default()) {
+            let position = self.position_mut(index)?;
             *position = Position::new();
 
             init_function(position)?;
@@ -96,11 +176,9 @@ impl Obligation {
     }
 
     pub fn close_position(&mut self, id: Pubkey) -> Result<&mut Position> {
-        self.update_time()?;
-        if let Some(index) = self.positions
-            .iter_mut()
-            .position(|p| p.id == id) {
-            let position = &mut self.positions[index];
+        self.obligation.update_time()?;
+        if let Some(index) = self.find_index(|p| p.id == id) {
+            let position = self.position_mut(index)?;
             *position = Position::default();
 
             Ok(position)
@@ -110,52 +188,39 @@ impl Obligation {
     }
 
     pub fn find_pending_funded_position(&mut self) -> Result<&mut Position> {
-        if let Some(index) = self.positions
-            .iter()
-            .position(|p| p.state.fund_amount > 0) {
-            Ok(&mut self.positions[index])
+        if let Some(index) = self.find_index(|p| p.state.fund_amount > 0) {
+            self.position_mut(index)
         } else {
             Err(ErrorLeverage::NoPendingFundedPositionFound.into())
         }
     }
 
     pub fn find_pending_borrowed_position(&mut self) -> Result<&mut Position> {
-        if let Some(index) = self.positions
-            .iter()
-            .position(|p| p.state.borrow_amount > 0) {
-            Ok(&mut self.positions[index])
+        if let Some(index) = self.find_index(|p| p.state.borrow_amount > 0) {
+            self.position_mut(index)
         } else {
             Err(ErrorLeverage::NoPendingFundedPositionFound.into())
         }
     }
 
     pub fn find_pending_leveraged_position(&mut self) -> Result<&mut Position> {
-        if let Some(index) = self.positions
-            .iter()
-            .position(|p| p.state.leveraged_amount > 0) {
-            Ok(&mut self.positions[index])
+        if let Some(index) = self.find_index(|p| p.state.leveraged_amount > 0) {
+            self.position_mut(index)
         } else {
             Err(ErrorLeverage::NoPendingFundedPositionFound.into())
         }
     }
 
     pub fn find_releasable_position(&mut self) -> Result<&mut Position> {
-        if let Some(index) = self.positions
-            .iter()
-            .position(|p|
-                p.state.fund_amount == 0 && p.state.borrow_amount == 0 &&
-                    p.state.leveraged_amount == 0 && p.unit > 0
-            ) {
-            Ok(&mut self.positions[index])
+        if let Some(index) = self.find_index(|p|
+            p.state.fund_amount == 0 && p.state.borrow_amount == 0 &&
+                p.state.leveraged_amount == 0 && p.unit > 0
+        ) {
+            self.position_mut(index)
         } else {
             Err(ErrorLeverage::NoPendingFundedPositionFound.into())
         }
     }
-
-    pub fn update_time(&mut self) -> Result<()> {
-        self.last_updated = Clock::get()?.unix_timestamp;
-        Ok(())
-    }
 }
 
 pub struct InitObligationParams {
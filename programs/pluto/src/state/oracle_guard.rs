@@ -0,0 +1,49 @@
+use anchor_lang::{InitSpace};
+use anchor_lang::prelude::*;
+use derivative::Derivative;
+use crate::error::Errors;
+use crate::util::constant::{MAX_ORACLE_AGE, MAX_ORACLE_AGE_LIMIT, PERCENT_MAX};
+
+// Per vault limits on the oracle prices, zeroed fields fall back to the protocol defaults
+#[derive(InitSpace, Derivative, PartialEq)]
+#[derivative(Debug)]
+#[zero_copy(unsafe)]
+#[repr(C)]
+pub struct OracleGuard {
+    pub max_age: u64, // seconds, 0 = MAX_ORACLE_AGE
+    pub max_confidence_rate: u32, // confidence / price, 100% = PERCENT_MAX, 0 = unchecked
+    pub max_ema_deviation_rate: u32, // |price - ema price| / ema price, 100% = PERCENT_MAX, 0 = unchecked
+    #[derivative(Debug = "ignore")]
+    pub padding1: [u64; 2],
+}
+
+impl Default for OracleGuard {
+    fn default() -> Self {
+        Self {
+            max_age: 0,
+            max_confidence_rate: 0,
+            max_ema_deviation_rate: 0,
+            padding1: [0; 2],
+        }
+    }
+}
+
+impl OracleGuard {
+    pub fn set(&mut self, max_age: u64, max_confidence_rate: u32, max_ema_deviation_rate: u32) -> Result<()> {
+        require_gte!(MAX_ORACLE_AGE_LIMIT, max_age, Errors::InvalidOracleGuard);
+        require_gte!(PERCENT_MAX, max_confidence_rate, Errors::InvalidOracleGuard);
+        require_gte!(PERCENT_MAX, max_ema_deviation_rate, Errors::InvalidOracleGuard);
+
+        self.max_age = max_age;
+        self.max_confidence_rate = max_confidence_rate;
+        self.max_ema_deviation_rate = max_ema_deviation_rate;
+        Ok(())
+    }
+
+    pub fn max_age(&self) -> u64 {
+        if self.max_age == 0 {
+            return MAX_ORACLE_AGE;
+        }
+        self.max_age
+    }
+}
\ No newline at end of file
@@ -0,0 +1,238 @@
+use anchor_lang::{account, InitSpace};
+use anchor_lang::prelude::*;
+use derivative::Derivative;
+use crate::error::Errors;
+use crate::state::{SetEarnConfigParams, SetLeverageConfigParams};
+
+// Earn config change waiting for the protocol timelock
+#[derive(InitSpace, Derivative, Default, PartialEq)]
+#[derivative(Debug)]
+#[account(zero_copy(unsafe))]
+#[repr(C)]
+pub struct PendingEarnConfig {
+    pub is_initialized: bool,
+    pub version: u8,
+    pub bump: u8,
+    #[derivative(Debug = "ignore")]
+    pub align0: [u8; 5],
+    pub config: Pubkey,
+    pub proposer: Pubkey,
+    pub queued_at: i64,
+    pub execute_after: i64,
+    pub earn_fee_vault: Pubkey,
+    pub pause_flags: u16,
+    #[derivative(Debug = "ignore")]
+    pub align1: [u8; 2],
+    pub provided_fields: u32,
+    pub protocol_fee: u32,
+    pub ltv: u32,
+    pub deposit_fee: u32,
+    #[derivative(Debug = "ignore")]
+    pub align2: [u8; 4],
+    pub min_deposit_limit: u64,
+    pub max_deposit_limit: u64,
+    pub withdraw_fee: u32,
+    #[derivative(Debug = "ignore")]
+    pub align3: [u8; 4],
+    pub min_withdraw_limit: u64,
+    pub max_withdraw_limit: u64,
+    pub borrow_fee: u32,
+    #[derivative(Debug = "ignore")]
+    pub align4: [u8; 4],
+    pub min_borrow_limit: u64,
+    pub max_borrow_limit: u64,
+    pub floor_cap_rate: u32,
+    #[derivative(Debug = "ignore")]
+    pub align5: [u8; 4],
+    #[derivative(Debug = "ignore")]
+    pub padding1: [u64; 8],
+}
+
+impl PendingEarnConfig {
+    pub fn init(&mut self, params: InitPendingConfigParams, config_params: &SetEarnConfigParams) -> Result<()> {
+        *self = Self::default();
+        self.is_initialized = true;
+        self.version = 1;
+        self.bump = params.bump;
+        self.config = params.config;
+        self.proposer = params.proposer;
+        self.queued_at = Clock::get()?.unix_timestamp;
+        self.execute_after = params.execute_after;
+
+        let mut provided_fields = 0;
+        self.earn_fee_vault = provide(&mut provided_fields, 0, config_params.earn_fee_vault);
+        self.pause_flags = provide(&mut provided_fields, 1, config_params.pause_flags);
+        self.protocol_fee = provide(&mut provided_fields, 2, config_params.protocol_fee);
+        self.ltv = provide(&mut provided_fields, 3, config_params.ltv);
+        self.deposit_fee = provide(&mut provided_fields, 4, config_params.deposit_fee);
+        self.min_deposit_limit = provide(&mut provided_fields, 5, config_params.min_deposit_limit);
+        self.max_deposit_limit = provide(&mut provided_fields, 6, config_params.max_deposit_limit);
+        self.withdraw_fee = provide(&mut provided_fields, 7, config_params.withdraw_fee);
+        self.min_withdraw_limit = provide(&mut provided_fields, 8, config_params.min_withdraw_limit);
+        self.max_withdraw_limit = provide(&mut provided_fields, 9, config_params.max_withdraw_limit);
+        self.borrow_fee = provide(&mut provided_fields, 10, config_params.borrow_fee);
+        self.min_borrow_limit = provide(&mut provided_fields, 11, config_params.min_borrow_limit);
+        self.max_borrow_limit = provide(&mut provided_fields, 12, config_params.max_borrow_limit);
+        self.floor_cap_rate = provide(&mut provided_fields, 13, config_params.floor_cap_rate);
+        self.provided_fields = provided_fields;
+
+        Ok(())
+    }
+
+    pub fn params(&self) -> Result<SetEarnConfigParams> {
+        require!(Clock::get()?.unix_timestamp >= self.execute_after, Errors::TimelockNotElapsed);
+
+        Ok(SetEarnConfigParams {
+            earn_fee_vault: provided(self.provided_fields, 0, self.earn_fee_vault),
+            pause_flags: provided(self.provided_fields, 1, self.pause_flags),
+            protocol_fee: provided(self.provided_fields, 2, self.protocol_fee),
+            ltv: provided(self.provided_fields, 3, self.ltv),
+            deposit_fee: provided(self.provided_fields, 4, self.deposit_fee),
+            min_deposit_limit: provided(self.provided_fields, 5, self.min_deposit_limit),
+            max_deposit_limit: provided(self.provided_fields, 6, self.max_deposit_limit),
+            withdraw_fee: provided(self.provided_fields, 7, self.withdraw_fee),
+            min_withdraw_limit: provided(self.provided_fields, 8, self.min_withdraw_limit),
+            max_withdraw_limit: provided(self.provided_fields, 9, self.max_withdraw_limit),
+            borrow_fee: provided(self.provided_fields, 10, self.borrow_fee),
+            min_borrow_limit: provided(self.provided_fields, 11, self.min_borrow_limit),
+            max_borrow_limit: provided(self.provided_fields, 12, self.max_borrow_limit),
+            floor_cap_rate: provided(self.provided_fields, 13, self.floor_cap_rate),
+        })
+    }
+}
+
+// Leverage config change waiting for the protocol timelock
+#[derive(InitSpace, Derivative, Default, PartialEq)]
+#[derivative(Debug)]
+#[account(zero_copy(unsafe))]
+#[repr(C)]
+pub struct PendingLeverageConfig {
+    pub is_initialized: bool,
+    pub version: u8,
+    pub bump: u8,
+    #[derivative(Debug = "ignore")]
+    pub align0: [u8; 5],
+    pub config: Pubkey,
+    pub proposer: Pubkey,
+    pub queued_at: i64,
+    pub execute_after: i64,
+    pub leverage_fee_vault: Pubkey,
+    pub pause_flags: u16,
+    #[derivative(Debug = "ignore")]
+    pub align1: [u8; 2],
+    pub provided_fields: u32,
+    pub protocol_fee: u32,
+    pub min_leverage: u32,
+    pub max_leverage: u32,
+    pub leverage_step: u32,
+    pub leverage_fee: u32,
+    #[derivative(Debug = "ignore")]
+    pub align2: [u8; 4],
+    pub min_leverage_limit: u64,
+    pub max_leverage_limit: u64,
+    pub deleverage_fee: u32,
+    #[derivative(Debug = "ignore")]
+    pub align3: [u8; 4],
+    pub min_deleverage_limit: u64,
+    pub max_deleverage_limit: u64,
+    pub closing_fee: u32,
+    pub spread_rate: u32,
+    pub liquidation_fee: u32,
+    pub liquidation_threshold: u32,
+    pub liquidation_protocol_ratio: u32,
+    pub slippage_rate: u32,
+    pub emergency_eject_period: i64,
+    pub saver_threshold: u32,
+    pub saver_target_reduction: u32,
+    #[derivative(Debug = "ignore")]
+    pub padding1: [u64; 8],
+}
+
+impl PendingLeverageConfig {
+    pub fn init(&mut self, params: InitPendingConfigParams, config_params: &SetLeverageConfigParams) -> Result<()> {
+        *self = Self::default();
+        self.is_initialized = true;
+        self.version = 1;
+        self.bump = params.bump;
+        self.config = params.config;
+        self.proposer = params.proposer;
+        self.queued_at = Clock::get()?.unix_timestamp;
+        self.execute_after = params.execute_after;
+
+        let mut provided_fields = 0;
+        self.leverage_fee_vault = provide(&mut provided_fields, 0, config_params.leverage_fee_vault);
+        self.pause_flags = provide(&mut provided_fields, 1, config_params.pause_flags);
+        self.protocol_fee = provide(&mut provided_fields, 2, config_params.protocol_fee);
+        self.min_leverage = provide(&mut provided_fields, 3, config_params.min_leverage);
+        self.max_leverage = provide(&mut provided_fields, 4, config_params.max_leverage);
+        self.leverage_step = provide(&mut provided_fields, 5, config_params.leverage_step);
+        self.leverage_fee = provide(&mut provided_fields, 6, config_params.leverage_fee);
+        self.min_leverage_limit = provide(&mut provided_fields, 7, config_params.min_leverage_limit);
+        self.max_leverage_limit = provide(&mut provided_fields, 8, config_params.max_leverage_limit);
+        self.deleverage_fee = provide(&mut provided_fields, 9, config_params.deleverage_fee);
+        self.min_deleverage_limit = provide(&mut provided_fields, 10, config_params.min_deleverage_limit);
+        self.max_deleverage_limit = provide(&mut provided_fields, 11, config_params.max_deleverage_limit);
+        self.closing_fee = provide(&mut provided_fields, 12, config_params.closing_fee);
+        self.spread_rate = provide(&mut provided_fields, 13, config_params.spread_rate);
+        self.liquidation_fee = provide(&mut provided_fields, 14, config_params.liquidation_fee);
+        self.liquidation_threshold = provide(&mut provided_fields, 15, config_params.liquidation_threshold);
+        self.liquidation_protocol_ratio = provide(&mut provided_fields, 16, config_params.liquidation_protocol_ratio);
+        self.slippage_rate = provide(&mut provided_fields, 17, config_params.slippage_rate);
+        self.emergency_eject_period = provide(&mut provided_fields, 18, config_params.emergency_eject_period);
+        self.saver_threshold = provide(&mut provided_fields, 19, config_params.saver_threshold);
+        self.saver_target_reduction = provide(&mut provided_fields, 20, config_params.saver_target_reduction);
+        self.provided_fields = provided_fields;
+
+        Ok(())
+    }
+
+    pub fn params(&self) -> Result<SetLeverageConfigParams> {
+        require!(Clock::get()?.unix_timestamp >= self.execute_after, Errors::TimelockNotElapsed);
+
+        Ok(SetLeverageConfigParams {
+            leverage_fee_vault: provided(self.provided_fields, 0, self.leverage_fee_vault),
+            pause_flags: provided(self.provided_fields, 1, self.pause_flags),
+            protocol_fee: provided(self.provided_fields, 2, self.protocol_fee),
+            min_leverage: provided(self.provided_fields, 3, self.min_leverage),
+            max_leverage: provided(self.provided_fields, 4, self.max_leverage),
+            leverage_step: provided(self.provided_fields, 5, self.leverage_step),
+            leverage_fee: provided(self.provided_fields, 6, self.leverage_fee),
+            min_leverage_limit: provided(self.provided_fields, 7, self.min_leverage_limit),
+            max_leverage_limit: provided(self.provided_fields, 8, self.max_leverage_limit),
+            deleverage_fee: provided(self.provided_fields, 9, self.deleverage_fee),
+            min_deleverage_limit: provided(self.provided_fields, 10, self.min_deleverage_limit),
+            max_deleverage_limit: provided(self.provided_fields, 11, self.max_deleverage_limit),
+            closing_fee: provided(self.provided_fields, 12, self.closing_fee),
+            spread_rate: provided(self.provided_fields, 13, self.spread_rate),
+            liquidation_fee: provided(self.provided_fields, 14, self.liquidation_fee),
+            liquidation_threshold: provided(self.provided_fields, 15, self.liquidation_threshold),
+            liquidation_protocol_ratio: provided(self.provided_fields, 16, self.liquidation_protocol_ratio),
+            slippage_rate: provided(self.provided_fields, 17, self.slippage_rate),
+            emergency_eject_period: provided(self.provided_fields, 18, self.emergency_eject_period),
+            saver_threshold: provided(self.provided_fields, 19, self.saver_threshold),
+            saver_target_reduction: provided(self.provided_fields, 20, self.saver_target_reduction),
+        })
+    }
+}
+
+// Stores a provided field value and marks its bit, unset fields are stored as zero
+fn provide<T: Default>(provided_fields: &mut u32, bit: u32, value: Option<T>) -> T {
+    match value {
+        Some(value) => {
+            *provided_fields |= 1 << bit;
+            value
+        }
+        None => T::default(),
+    }
+}
+
+fn provided<T>(provided_fields: u32, bit: u32, value: T) -> Option<T> {
+    (provided_fields & (1 << bit) != 0).then_some(value)
+}
+
+pub struct InitPendingConfigParams {
+    pub bump: u8,
+    pub config: Pubkey,
+    pub proposer: Pubkey,
+    pub execute_after: i64,
+}
\ No newline at end of file
@@ -1,12 +1,14 @@
 use anchor_lang::{InitSpace};
+use anchor_lang::__private::bytemuck;
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
 use derivative::Derivative;
 use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
-use crate::error::{ErrorMath, Errors};
+use crate::error::{ErrorLeverage, ErrorMath, Errors};
 use crate::error::ErrorMath::MathOverflow;
 use crate::state::{LeverageConfig, PositionState};
 use crate::util::{
-    constant::{INDEX_DECIMALS, UNIT_DECIMALS},
+    constant::{HEALTH_FACTOR_ONE, INDEX_DECIMALS, LEVERAGE_ONE, PERCENT_MAX, UNIT_DECIMALS},
     decimals,
 };
 use crate::util::action::LeverageAction;
@@ -51,10 +53,15 @@ pub struct Position {
     pub align3: [u8; 3],
     pub profit_target_rate: u32,
     pub profit_taking_rate: u32,
+    pub nft_mint: Pubkey,
     #[derivative(Debug = "ignore")]
-    pub padding1: [u64; 63],
+    pub padding1: [u64; 59],
 }
 
+// Extra obligation positions are cast straight from the obligation account data
+unsafe impl bytemuck::Pod for Position {}
+unsafe impl bytemuck::Zeroable for Position {}
+
 impl Default for Position {
     fn default() -> Self {
         Self {
@@ -87,9 +94,10 @@ impl Default for Position {
             native_collateral_price_exponent: 0,
             align3: [0; 3],
             profit_taker: false,
-            padding1: [0; 63],
+            padding1: [0; 59],
             profit_target_rate: 0,
             profit_taking_rate: 0,
+            nft_mint: Pubkey::default(),
         }
     }
 }
@@ -126,9 +134,10 @@ impl Position {
             native_collateral_price_exponent: 0,
             align3: [0; 3],
             profit_taker: false,
-            padding1: [0; 63],
+            padding1: [0; 59],
             profit_target_rate: 0,
             profit_taking_rate: 0,
+            nft_mint: Pubkey::default(),
         }
     }
 
@@ -184,6 +193,31 @@ impl Position {
         Ok(val as u64)
     }
 
+    // Equity at open in token collateral, the native collateral is converted back with the open ratio
+    pub fn open_equity_amount(&mut self, token_decimal: u8, native_decimal: u8) -> Result<u64> {
+        if self.unit == 0 || self.token_to_native_ratio == 0 {
+            return Ok(0);
+        }
+        let native_amount = self.collateral_open_amount(native_decimal)?;
+        let token_amount = decimals::div_floor(token_decimal, native_amount as u128, native_decimal, self.token_to_native_ratio, INDEX_DECIMALS)? as u64;
+        Ok(token_amount.saturating_sub(self.borrowing_open_amount(token_decimal)?))
+    }
+
+    // Open ratio of the whole position after a new tranche, weighted by the token collateral spent
+    pub fn blended_token_to_native_ratio(&mut self, token_decimal: u8, native_decimal: u8, native_amount: u64, token_amount: u64) -> Result<u128> {
+        let open_native_amount = self.collateral_open_amount(native_decimal)?;
+        let open_token_amount = if self.token_to_native_ratio == 0 {
+            0
+        } else {
+            decimals::div_floor(token_decimal, open_native_amount as u128, native_decimal, self.token_to_native_ratio, INDEX_DECIMALS)?
+        };
+        decimals::div_ceil(
+            INDEX_DECIMALS,
+            (open_native_amount as u128).checked_add(native_amount as u128).ok_or(MathOverflow)?, native_decimal,
+            open_token_amount.checked_add(token_amount as u128).ok_or(MathOverflow)?, token_decimal
+        )
+    }
+
     pub fn set_action(&mut self, action: LeverageAction) -> Result<()> {
         self.state.action = action;
         Ok(())
@@ -262,6 +296,46 @@ impl Position {
         Ok(())
     }
 
+    pub fn increase(
+        &mut self,
+        fund_amount: u64,
+        leverage_fee_amount: u64,
+    ) -> Result<()> {
+        self.halt_on_deleveraging()?;
+        require_gt!(self.unit, 0, ErrorLeverage::NoPositionFound);
+        require_eq!(self.state.fund_amount, 0, Errors::IncompleteProcess);
+        require_eq!(self.state.leverage_fee_amount, 0, Errors::IncompleteProcess);
+        require_eq!(self.state.borrow_amount, 0, Errors::IncompleteProcess);
+        require_eq!(self.state.borrowing_unit, 0, Errors::IncompleteProcess);
+        require_eq!(self.state.borrowing_index, 0, Errors::IncompleteProcess);
+        require_eq!(self.state.leveraged_amount, 0, Errors::IncompleteProcess);
+        require_eq!(self.state.min_native_collateral_output, 0, Errors::IncompleteProcess);
+        self.state.fund_amount = fund_amount;
+        self.state.leverage_fee_amount = leverage_fee_amount;
+
+        Ok(())
+    }
+
+    pub fn add_collateral(
+        &mut self,
+        fund_amount: u64,
+        min_native_collateral_output: u64,
+    ) -> Result<()> {
+        self.halt_on_deleveraging()?;
+        require_gt!(self.unit, 0, ErrorLeverage::NoPositionFound);
+        require_eq!(self.state.fund_amount, 0, Errors::IncompleteProcess);
+        require_eq!(self.state.borrow_amount, 0, Errors::IncompleteProcess);
+        require_eq!(self.state.leveraged_amount, 0, Errors::IncompleteProcess);
+        require_eq!(self.state.min_native_collateral_output, 0, Errors::IncompleteProcess);
+        // No borrowing, the whole fund is swapped into the position
+        self.token_collateral_amount = self.token_collateral_amount.checked_add(fund_amount).ok_or(MathOverflow)?;
+        self.state.fund_amount = fund_amount;
+        self.state.leveraged_amount = fund_amount;
+        self.state.min_native_collateral_output = min_native_collateral_output;
+
+        Ok(())
+    }
+
     pub fn borrow_fund(
         &mut self,
         borrowing_amount: u64,
@@ -338,9 +412,12 @@ impl Position {
         self.halt_on_deleveraging()?;
         require_gt!(self.state.fund_amount, 0, Errors::IncompleteProcess);
         require_gte!(self.state.leverage_fee_amount, 0, Errors::IncompleteProcess);
-        require_gt!(self.state.borrow_amount, 0, Errors::IncompleteProcess);
-        require_gt!(self.state.borrowing_unit, 0, Errors::IncompleteProcess);
-        require_gt!(self.state.borrowing_index, 0, Errors::IncompleteProcess);
+        // Adding collateral does not borrow
+        if self.state.action != LeverageAction::AddCollateral {
+            require_gt!(self.state.borrow_amount, 0, Errors::IncompleteProcess);
+            require_gt!(self.state.borrowing_unit, 0, Errors::IncompleteProcess);
+            require_gt!(self.state.borrowing_index, 0, Errors::IncompleteProcess);
+        }
         require_gte!(self.state.borrowing_fee_amount, 0, Errors::IncompleteProcess);
         require_gt!(self.state.leveraged_amount, 0, Errors::IncompleteProcess);
         require_gt!(self.state.min_native_collateral_output, 0, Errors::IncompleteProcess);
@@ -404,6 +481,120 @@ impl Position {
         Ok(())
     }
 
+    // Health factor 1 = 10^3, values are in usd
+    pub fn health_factor(&self, native_collateral_value: u128, borrowing_value: u128, liquidation_threshold: u32) -> Result<u32> {
+        if borrowing_value == 0 {
+            return Ok(u32::MAX);
+        }
+        let health_factor = native_collateral_value
+            .checked_mul(liquidation_threshold as u128).ok_or(MathOverflow)?
+            .checked_mul(HEALTH_FACTOR_ONE as u128).ok_or(MathOverflow)?
+            .checked_div(PERCENT_MAX as u128).ok_or(MathOverflow)?
+            .checked_div(borrowing_value).ok_or(MathOverflow)?;
+        Ok(health_factor.min(u32::MAX as u128) as u32)
+    }
+
+    // Leverage 1 = 10^3, values are in usd
+    pub fn current_leverage(&self, native_collateral_value: u128, borrowing_value: u128) -> Result<u32> {
+        let equity_value = native_collateral_value.saturating_sub(borrowing_value);
+        if equity_value == 0 {
+            return Ok(u32::MAX);
+        }
+        let leverage = native_collateral_value
+            .checked_mul(LEVERAGE_ONE as u128).ok_or(MathOverflow)?
+            .checked_div(equity_value).ok_or(MathOverflow)?;
+        Ok(leverage.min(u32::MAX as u128) as u32)
+    }
+
+    // Collateral value to sell and repay as debt to bring the position down to the target leverage
+    pub fn release_value_for_leverage(&self, native_collateral_value: u128, borrowing_value: u128, target_leverage: u32) -> Result<u128> {
+        let equity_value = native_collateral_value.saturating_sub(borrowing_value);
+        let target_value = equity_value
+            .checked_mul(target_leverage as u128).ok_or(MathOverflow)?
+            .checked_div(LEVERAGE_ONE as u128).ok_or(MathOverflow)?;
+        Ok(native_collateral_value.saturating_sub(target_value).min(borrowing_value))
+    }
+
+    pub fn set_nft_mint(&mut self, nft_mint: Pubkey) -> Result<()> {
+        require_keys_eq!(self.nft_mint, Pubkey::default(), ErrorLeverage::PositionNftAlreadyMinted);
+        self.nft_mint = nft_mint;
+        Ok(())
+    }
+
+    // Position without nft belongs to the obligation owner, otherwise to whoever holds the nft
+    pub fn require_holder(&self, owner: Pubkey, holder: Pubkey, holder_nft_account: &Option<Box<InterfaceAccount<TokenAccount>>>) -> Result<()> {
+        if self.nft_mint == Pubkey::default() {
+            require_keys_eq!(owner, holder, ErrorLeverage::InvalidPositionHolder);
+            return Ok(());
+        }
+
+        let nft_account = holder_nft_account.as_ref().ok_or(ErrorLeverage::InvalidPositionHolder)?;
+        require_keys_eq!(nft_account.mint, self.nft_mint, ErrorLeverage::InvalidPositionHolder);
+        require_keys_eq!(nft_account.owner, holder, ErrorLeverage::InvalidPositionHolder);
+        require_eq!(nft_account.amount, 1, ErrorLeverage::InvalidPositionHolder);
+        Ok(())
+    }
+
+    pub fn set_keeper(&mut self, keeper: Pubkey) -> Result<()> {
+        self.state.keeper = keeper;
+        Ok(())
+    }
+
+    pub fn set_swap_router(&mut self, swap_router: Pubkey) -> Result<()> {
+        self.state.swap_router = swap_router;
+        Ok(())
+    }
+
+    pub fn set_realized_profit(&mut self, realized_profit: u64) -> Result<()> {
+        self.state.realized_profit = realized_profit;
+        Ok(())
+    }
+
+    pub fn keeper_release(&mut self, release_balance: u64) -> Result<()> {
+        self.halt_on_leveraging()?;
+        require_gt!(self.state.release_amount, 0, Errors::IncompleteProcess);
+        require_gt!(self.state.release_unit, 0, Errors::IncompleteProcess);
+        require_gt!(self.state.release_min_output, 0, Errors::IncompleteProcess);
+        require_eq!(self.state.release_output, 0, Errors::IncompleteProcess);
+        require_eq!(self.state.repay_borrow_amount, 0, Errors::IncompleteProcess);
+        self.state.release_balance = release_balance;
+
+        Ok(())
+    }
+
+    pub fn keeper_repay_borrow(
+        &mut self,
+        release_output: u64,
+        repay_borrow_amount: u64,
+    ) -> Result<()> {
+        require_eq!(self.state.release_output, 0, Errors::IncompleteProcess);
+        self.repay_borrow(repay_borrow_amount)?;
+        self.state.release_output = release_output;
+
+        Ok(())
+    }
+
+    // Swap output left after repaying the borrow and paying the fees, belongs to the position owner
+    pub fn remaining_output(&self) -> Result<u64> {
+        Ok(self.state.release_output
+            .saturating_sub(self.state.repay_borrow_amount)
+            .saturating_sub(self.state.protocol_fee_amount)
+            .saturating_sub(self.state.liquidation_fee_amount)
+            .saturating_sub(self.state.deleverage_fee_amount))
+    }
+
+    pub fn pay_deleverage_fee(
+        &mut self,
+        deleverage_fee_amount: u64
+    ) -> Result<()> {
+        require!(self.state.action == LeverageAction::Deleverage, Errors::IncompleteProcess);
+        require_gt!(self.state.release_output, 0, Errors::IncompleteProcess);
+        require_eq!(self.state.deleverage_fee_amount, 0, Errors::IncompleteProcess);
+        self.state.deleverage_fee_amount = deleverage_fee_amount;
+
+        Ok(())
+    }
+
     pub fn release_reduce(
         &mut self,
         release_amount: u64, release_unit: u64, release_index: u128,
@@ -469,6 +660,8 @@ impl Position {
         &mut self,
         liquidation_fee_amount: u64
     ) -> Result<()> {
+        require!(self.state.action == LeverageAction::Liquidate, ErrorLeverage::CannotPayLiquidationFeeBeforeLiquidate);
+        require_gt!(self.state.release_output, 0, Errors::IncompleteProcess);
         require_eq!(self.state.liquidation_fee_amount, 0, Errors::IncompleteProcess);
         self.state.liquidation_fee_amount = liquidation_fee_amount;
 
@@ -69,8 +69,17 @@ pub struct PositionState {
     pub health_factor: u32,
     #[derivative(Debug = "ignore")]
     pub align3: [u8; 4],
+    // KEEPER
+    pub keeper: Pubkey,
+    pub release_balance: u64,
+    pub release_output: u64,
+    pub deleverage_fee_amount: u64,
+    // SWAP
+    pub swap_router: Pubkey,
+    // TAKE PROFIT
+    pub realized_profit: u64,
     #[derivative(Debug = "ignore")]
-    pub padding1: [u64; 63],
+    pub padding1: [u64; 51],
 }
 
 impl Default for PositionState {
@@ -126,7 +135,13 @@ impl Default for PositionState {
             liquidation_fee_amount: 0,
             health_factor: 0,
             align3: [0; 4],
-            padding1: [0; 63],
+            keeper: Pubkey::default(),
+            release_balance: 0,
+            release_output: 0,
+            deleverage_fee_amount: 0,
+            swap_router: Pubkey::default(),
+            realized_profit: 0,
+            padding1: [0; 51],
         }
     }
 }
@@ -184,10 +199,21 @@ impl PositionState {
             liquidation_fee_amount: 0,
             health_factor: 0,
             align3: [0; 4],
-            padding1: [0; 63],
+            keeper: Pubkey::default(),
+            release_balance: 0,
+            release_output: 0,
+            deleverage_fee_amount: 0,
+            swap_router: Pubkey::default(),
+            realized_profit: 0,
+            padding1: [0; 51],
         }
     }
 
+    // Actions unwound through the keeper release, repay borrow and closing pipeline
+    pub fn is_keeper_action(&self) -> bool {
+        matches!(self.action, LeverageAction::Safe | LeverageAction::Eject | LeverageAction::Liquidate | LeverageAction::TakeProfit | LeverageAction::Deleverage)
+    }
+
     pub fn halt_on_leveraging(&mut self) -> Result<()> {
         match self.action {
             LeverageAction::Idle => {
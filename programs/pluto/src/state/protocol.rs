@@ -3,7 +3,9 @@ use anchor_lang::prelude::*;
 use derivative::Derivative;
 use crate::error::{Errors, ErrorEarn};
 use crate::error::ErrorMath::MathOverflow;
+use crate::state::Role;
 use crate::util::{constant, decimals};
+use crate::util::pause::PauseFlags;
 use crate::util::constant::{INDEX_DECIMALS, INDEX_ONE, PERCENT_DECIMALS, UNIT_DECIMALS};
 
 #[derive(InitSpace, Derivative, PartialEq)]
@@ -18,15 +20,24 @@ pub struct Protocol {
     pub align0: [u8; 5],
     pub creator: Pubkey,
     pub owner: Pubkey,
-    pub freeze: bool,
-    pub freeze_earn: bool,
-    pub freeze_lend: bool,
-    pub freeze_leverage: bool,
+    pub pause_flags: u16, // paused operations of every vault, see PauseFlags
     #[derivative(Debug = "ignore")]
-    pub align1: [u8; 4],
+    pub align1: [u8; 6],
     pub last_updated: i64,
+    pub treasury: Pubkey, // fee sweep destinations, owners of the receiving token accounts
+    pub insurance_fund: Pubkey,
+    pub buyback: Pubkey,
+    pub treasury_ratio: u32, // share of swept fees in percentage 100% = 10^5
+    pub insurance_ratio: u32, // share of swept fees in percentage 100% = 10^5
+    pub buyback_ratio: u32, // share of swept fees in percentage 100% = 10^5
     #[derivative(Debug = "ignore")]
-    pub padding1: [u64; 64],
+    pub align2: [u8; 4],
+    pub pending_owner: Pubkey, // proposed owner, becomes the owner once accepted
+    pub timelock_delay: i64, // seconds a queued config change waits before it can be executed
+    pub pending_timelock_delay: i64, // lower delay waiting for the current delay to elapse
+    pub pending_timelock_delay_after: i64, // 0 = no pending delay
+    #[derivative(Debug = "ignore")]
+    pub padding1: [u64; 43],
 }
 
 impl Default for Protocol {
@@ -38,13 +49,21 @@ impl Default for Protocol {
             align0: [0;5],
             creator: Pubkey::default(),
             owner: Pubkey::default(),
-            freeze: false,
-            freeze_earn: false,
-            freeze_lend: false,
-            freeze_leverage: false,
-            align1: [0;4],
+            pause_flags: 0,
+            align1: [0;6],
             last_updated: 0,
-            padding1: [0; 64],
+            treasury: Pubkey::default(),
+            insurance_fund: Pubkey::default(),
+            buyback: Pubkey::default(),
+            treasury_ratio: 0,
+            insurance_ratio: 0,
+            buyback_ratio: 0,
+            align2: [0;4],
+            pending_owner: Pubkey::default(),
+            timelock_delay: 0,
+            pending_timelock_delay: 0,
+            pending_timelock_delay_after: 0,
+            padding1: [0; 43],
         }
     }
 }
@@ -57,10 +76,8 @@ impl Protocol {
         self.bump = params.bump;
         self.creator = params.creator;
         self.owner = params.owner;
-        self.freeze = params.freeze;
-        self.freeze_earn = params.freeze_earn;
-        self.freeze_lend = params.freeze_lend;
-        self.freeze_leverage = params.freeze_leverage;
+        require!(PauseFlags::is_valid(params.pause_flags), Errors::InvalidPauseFlags);
+        self.pause_flags = params.pause_flags;
 
         self.last_updated = Clock::get()?.unix_timestamp;
 
@@ -68,34 +85,130 @@ impl Protocol {
     }
 
     pub fn set_protocol(&mut self, params: SetProtocolParams) -> Result<()> {
-        self.freeze = params.freeze;
-        self.freeze_earn = params.freeze_earn;
-        self.freeze_lend = params.freeze_lend;
-        self.freeze_leverage = params.freeze_leverage;
+        require!(PauseFlags::is_valid(params.pause_flags), Errors::InvalidPauseFlags);
+        self.pause_flags = params.pause_flags;
         self.last_updated = Clock::get()?.unix_timestamp;
 
         Ok(())
     }
 
-    pub fn change_owner(&mut self, owner: Pubkey) -> Result<()> {
+    // Guardians can only pause more operations, never resume one
+    pub fn required_role(&self, params: &SetProtocolParams) -> Option<Role> {
+        let pauses_only = params.pause_flags & self.pause_flags == self.pause_flags;
+        pauses_only.then_some(Role::Guardian)
+    }
+
+    // Pubkey::default() withdraws the proposal
+    pub fn propose_owner(&mut self, pending_owner: Pubkey) -> Result<()> {
+        self.pending_owner = pending_owner;
+        Ok(())
+    }
+
+    pub fn accept_owner(&mut self, owner: Pubkey) -> Result<()> {
+        require_keys_neq!(self.pending_owner, Pubkey::default(), Errors::NotPendingOwner);
+        require_keys_eq!(self.pending_owner, owner, Errors::NotPendingOwner);
         self.owner = owner;
+        self.pending_owner = Pubkey::default();
+        self.last_updated = Clock::get()?.unix_timestamp;
+
         Ok(())
     }
+
+    // A longer delay applies at once, a shorter one only after the current delay elapsed.
+    // Returns whether the delay was applied.
+    pub fn set_timelock_delay(&mut self, timelock_delay: i64) -> Result<bool> {
+        require!(timelock_delay >= 0 && timelock_delay <= constant::MAX_TIMELOCK_DELAY, Errors::InvalidTimelockDelay);
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        let elapsed = self.pending_timelock_delay_after > 0
+            && self.pending_timelock_delay == timelock_delay
+            && timestamp >= self.pending_timelock_delay_after;
+
+        if timelock_delay >= self.timelock_delay || elapsed {
+            self.timelock_delay = timelock_delay;
+            self.pending_timelock_delay = 0;
+            self.pending_timelock_delay_after = 0;
+            self.last_updated = timestamp;
+            return Ok(true);
+        }
+
+        if self.pending_timelock_delay_after == 0 || self.pending_timelock_delay != timelock_delay {
+            self.pending_timelock_delay = timelock_delay;
+            self.pending_timelock_delay_after = timestamp.checked_add(self.timelock_delay).ok_or(MathOverflow)?;
+        }
+
+        Ok(false)
+    }
+
+    pub fn execute_after(&self) -> Result<i64> {
+        Ok(Clock::get()?.unix_timestamp.checked_add(self.timelock_delay).ok_or(MathOverflow)?)
+    }
+
+    pub fn set_fee_distribution(&mut self, params: SetFeeDistributionParams) -> Result<()> {
+        require_keys_neq!(params.treasury, Pubkey::default(), Errors::InvalidFeeDistribution);
+        require_keys_neq!(params.insurance_fund, Pubkey::default(), Errors::InvalidFeeDistribution);
+        require_keys_neq!(params.buyback, Pubkey::default(), Errors::InvalidFeeDistribution);
+
+        let total_ratio = params.treasury_ratio
+            .checked_add(params.insurance_ratio).ok_or(MathOverflow)?
+            .checked_add(params.buyback_ratio).ok_or(MathOverflow)?;
+        require_eq!(total_ratio, constant::PERCENT_MAX, Errors::InvalidFeeDistribution);
+
+        self.treasury = params.treasury;
+        self.insurance_fund = params.insurance_fund;
+        self.buyback = params.buyback;
+        self.treasury_ratio = params.treasury_ratio;
+        self.insurance_ratio = params.insurance_ratio;
+        self.buyback_ratio = params.buyback_ratio;
+        self.last_updated = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+
+    // Splits swept fees by the ratios, the buyback share takes the rounding remainder
+    pub fn split_fees(&self, amount: u64) -> Result<FeeSplit> {
+        require_keys_neq!(self.treasury, Pubkey::default(), Errors::FeeDistributionNotSet);
+
+        let treasury_amount = (amount as u128)
+            .checked_mul(self.treasury_ratio as u128).ok_or(MathOverflow)?
+            .checked_div(constant::PERCENT_MAX as u128).ok_or(MathOverflow)? as u64;
+        let insurance_amount = (amount as u128)
+            .checked_mul(self.insurance_ratio as u128).ok_or(MathOverflow)?
+            .checked_div(constant::PERCENT_MAX as u128).ok_or(MathOverflow)? as u64;
+        let buyback_amount = amount
+            .checked_sub(treasury_amount).ok_or(MathOverflow)?
+            .checked_sub(insurance_amount).ok_or(MathOverflow)?;
+
+        Ok(FeeSplit {
+            treasury_amount,
+            insurance_amount,
+            buyback_amount,
+        })
+    }
 }
 
 pub struct InitProtocolParams {
     pub bump: u8,
     pub creator: Pubkey,
     pub owner: Pubkey,
-    pub freeze: bool,
-    pub freeze_earn: bool,
-    pub freeze_lend: bool,
-    pub freeze_leverage: bool,
+    pub pause_flags: u16,
 }
 
 pub struct SetProtocolParams {
-    pub freeze: bool,
-    pub freeze_earn: bool,
-    pub freeze_lend: bool,
-    pub freeze_leverage: bool,
+    pub pause_flags: u16,
+}
+
+pub struct SetFeeDistributionParams {
+    pub treasury: Pubkey,
+    pub insurance_fund: Pubkey,
+    pub buyback: Pubkey,
+    pub treasury_ratio: u32,
+    pub insurance_ratio: u32,
+    pub buyback_ratio: u32,
+}
+
+pub struct FeeSplit {
+    pub treasury_amount: u64,
+    pub insurance_amount: u64,
+    pub buyback_amount: u64,
 }
\ No newline at end of file
@@ -0,0 +1,103 @@
+use anchor_lang::{account, InitSpace};
+use anchor_lang::prelude::*;
+use derivative::Derivative;
+use crate::error::Errors;
+use crate::util::constant::MAX_ROLE_MEMBERS;
+
+#[derive(InitSpace, Debug, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Guardian, // can only pause
+    RiskManager, // can tune ltv and thresholds within bounds
+    FeeManager, // can only change fees and sweep them
+}
+
+impl Role {
+    pub fn mask(&self) -> u8 {
+        1 << (*self as u8)
+    }
+}
+
+// Signers allowed to run part of the owner actions of a protocol
+#[derive(InitSpace, Derivative, PartialEq)]
+#[derivative(Debug)]
+#[account(zero_copy(unsafe))]
+#[repr(C)]
+pub struct Roles {
+    pub is_initialized: bool,
+    pub version: u8,
+    pub bump: u8,
+    #[derivative(Debug = "ignore")]
+    pub align0: [u8; 5],
+    pub protocol: Pubkey,
+    pub last_updated: i64,
+    pub members: [Pubkey; 16], // default = empty slot
+    pub member_roles: [u8; 16], // role masks of the members
+    #[derivative(Debug = "ignore")]
+    pub padding1: [u64; 16],
+}
+
+impl Default for Roles {
+    fn default() -> Self {
+        Self {
+            is_initialized: false,
+            version: 0,
+            bump: 0,
+            align0: [0; 5],
+            protocol: Pubkey::default(),
+            last_updated: 0,
+            members: [Pubkey::default(); MAX_ROLE_MEMBERS],
+            member_roles: [0; MAX_ROLE_MEMBERS],
+            padding1: [0; 16],
+        }
+    }
+}
+
+impl Roles {
+    pub fn init(&mut self, params: InitRolesParams) -> Result<()> {
+        *self = Self::default();
+        self.is_initialized = true;
+        self.version = 1;
+        self.bump = params.bump;
+        self.protocol = params.protocol;
+        self.last_updated = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    pub fn has_role(&self, member: &Pubkey, role: Role) -> bool {
+        *member != Pubkey::default() && self.members.iter()
+            .zip(self.member_roles.iter())
+            .any(|(slot, roles)| slot == member && roles & role.mask() != 0)
+    }
+
+    pub fn grant(&mut self, member: Pubkey, role: Role) -> Result<()> {
+        require_keys_neq!(member, Pubkey::default(), Errors::InvalidAddress);
+        require!(!self.has_role(&member, role), Errors::RoleAlreadyGranted);
+
+        // A member keeps its slot, new members take the first empty one
+        let index = self.members.iter().position(|slot| *slot == member)
+            .or_else(|| self.members.iter().position(|slot| *slot == Pubkey::default()))
+            .ok_or(Errors::MaxRoleMembersReached)?;
+        self.members[index] = member;
+        self.member_roles[index] |= role.mask();
+        self.last_updated = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    pub fn revoke(&mut self, member: Pubkey, role: Role) -> Result<()> {
+        require!(self.has_role(&member, role), Errors::RoleNotGranted);
+
+        let index = self.members.iter().position(|slot| *slot == member)
+            .ok_or(Errors::RoleNotGranted)?;
+        self.member_roles[index] &= !role.mask();
+        if self.member_roles[index] == 0 {
+            self.members[index] = Pubkey::default();
+        }
+        self.last_updated = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+}
+
+pub struct InitRolesParams {
+    pub bump: u8,
+    pub protocol: Pubkey,
+}
\ No newline at end of file
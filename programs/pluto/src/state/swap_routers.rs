@@ -0,0 +1,101 @@
+use std::mem::size_of;
+use anchor_lang::{account, Discriminator, InitSpace};
+use anchor_lang::prelude::*;
+use anchor_lang::__private::bytemuck;
+use anchor_lang::solana_program::instruction::Instruction;
+use derivative::Derivative;
+use crate::error::ErrorLeverage;
+use crate::util::constant::{JUPITER_SWAP_PROGRAM_ID, MAX_SWAP_ROUTERS, MERCURY_POOL_ADDRESS, MERCURY_PROGRAM_ID};
+
+// Programs a leverage vault accepts for the swap leg between its instructions
+#[derive(InitSpace, Derivative, PartialEq)]
+#[derivative(Debug)]
+#[account(zero_copy(unsafe))]
+#[repr(C)]
+pub struct SwapRouters {
+    pub is_initialized: bool,
+    pub version: u8,
+    pub bump: u8,
+    #[derivative(Debug = "ignore")]
+    pub align0: [u8; 5],
+    pub vault: Pubkey,
+    pub routers: [Pubkey; 8], // default = empty slot
+    #[derivative(Debug = "ignore")]
+    pub padding1: [u64; 16],
+}
+
+impl Default for SwapRouters {
+    fn default() -> Self {
+        Self {
+            is_initialized: false,
+            version: 0,
+            bump: 0,
+            align0: [0; 5],
+            vault: Pubkey::default(),
+            routers: [Pubkey::default(); MAX_SWAP_ROUTERS],
+            padding1: [0; 16],
+        }
+    }
+}
+
+impl SwapRouters {
+    pub fn init(&mut self, params: InitSwapRoutersParams) -> Result<()> {
+        *self = Self::default();
+        self.is_initialized = true;
+        self.version = 1;
+        self.bump = params.bump;
+        self.vault = params.vault;
+        // Keeps the router every vault accepted before having a list
+        self.routers[0] = JUPITER_SWAP_PROGRAM_ID;
+        Ok(())
+    }
+
+    pub fn contains(&self, router: &Pubkey) -> bool {
+        *router != Pubkey::default() && self.routers.contains(router)
+    }
+
+    pub fn add(&mut self, router: Pubkey) -> Result<()> {
+        require_keys_neq!(router, Pubkey::default(), ErrorLeverage::SwapRouterNotAllowed);
+        require!(!self.contains(&router), ErrorLeverage::SwapRouterAlreadyAllowed);
+        let slot = self.routers.iter_mut()
+            .find(|slot| **slot == Pubkey::default())
+            .ok_or(ErrorLeverage::MaxSwapRoutersReached)?;
+        *slot = router;
+        Ok(())
+    }
+
+    pub fn remove(&mut self, router: Pubkey) -> Result<()> {
+        let slot = self.routers.iter_mut()
+            .find(|slot| **slot == router && router != Pubkey::default())
+            .ok_or(ErrorLeverage::SwapRouterNotAllowed)?;
+        *slot = Pubkey::default();
+        Ok(())
+    }
+
+    // Whether the instruction is a swap through a router allowed by the vault,
+    // vaults without a router list only allow jupiter
+    pub fn is_allowed_swap(swap_routers: &AccountInfo, ix: &Instruction) -> Result<bool> {
+        let allowed = if swap_routers.data_is_empty() {
+            ix.program_id == JUPITER_SWAP_PROGRAM_ID
+        } else {
+            require_keys_eq!(*swap_routers.owner, crate::ID, ErrorLeverage::InvalidSwapRouters);
+            let data = swap_routers.try_borrow_data()?;
+            require!(data.len() >= 8 + size_of::<SwapRouters>(), ErrorLeverage::InvalidSwapRouters);
+            require!(data[..8] == SwapRouters::discriminator(), ErrorLeverage::InvalidSwapRouters);
+            let routers: &SwapRouters = bytemuck::from_bytes(&data[8..8 + size_of::<SwapRouters>()]);
+            routers.contains(&ix.program_id)
+        };
+
+        // Mercury swaps must go through the pinned pool
+        if allowed && ix.program_id == MERCURY_PROGRAM_ID {
+            require!(ix.accounts.iter().any(|account| account.pubkey == MERCURY_POOL_ADDRESS), ErrorLeverage::InvalidMercuryPool);
+        }
+
+        Ok(allowed)
+    }
+}
+
+pub struct InitSwapRoutersParams {
+    pub bump: u8,
+    pub vault: Pubkey,
+}
\ No newline at end of file
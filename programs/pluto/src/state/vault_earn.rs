@@ -3,8 +3,9 @@ use anchor_lang::prelude::*;
 use derivative::Derivative;
 use crate::error::{Errors, ErrorEarn, ErrorMath};
 use crate::error::ErrorMath::MathOverflow;
-use crate::state::{EarnConfig, Rate};
-use crate::util::{constant, decimals};
+use crate::state::{EarnConfig, OracleGuard, Rate};
+use crate::util::{constant, decimals, oracle};
+use crate::util::oracle_source::OracleSource;
 use crate::util::constant::{INDEX_DECIMALS, INDEX_ONE, PERCENT_DECIMALS, PROTOCOL_CAP_RATIO, UNIT_DECIMALS};
 
 #[derive(InitSpace, Derivative, PartialEq)]
@@ -40,8 +41,23 @@ pub struct VaultEarn {
     pub index: u128,
     pub last_index_updated: i64,
     pub apy: Rate,
+    pub share_mint: Pubkey, // token-2022 mint of the lender shares, 1 share = 1 unit
+    pub price_source: OracleSource,
     #[derivative(Debug = "ignore")]
-    pub padding1: [u64; 64],
+    pub align2: [u8; 7],
+    pub fixed_price: u64, // 1.00 = 10^FIXED_PRICE_EXPONENT
+    pub oracle_guard: OracleGuard,
+    pub flash_loan_amount: u64, // liquidity out on a flash loan, repaid within the same transaction
+    pub withdraw_queue_tail: u64, // ticket of the next queued withdrawal
+    pub withdraw_queued_amount: u64, // cumulative amount of queued withdrawals
+    pub withdraw_filled_amount: u64, // cumulative amount of queued withdrawals covered by liquidity, filled in queue order
+    pub withdraw_claimed_amount: u64, // cumulative amount of queued withdrawals paid out
+    pub insurance_fund_amount: u64, // part of the liquidity held to cover bad debt, not lent out
+    pub insurance_covered_amount: u64, // cumulative bad debt covered by the insurance fund
+    pub bad_debt_amount: u64, // cumulative bad debt socialized to lenders through the index
+    pub share_index: u128, // average index the outstanding shares were minted at, cost basis of the protocol fee
+    #[derivative(Debug = "ignore")]
+    pub padding1: [u64; 44],
 }
 
 impl Default for VaultEarn {
@@ -70,8 +86,22 @@ impl Default for VaultEarn {
             unit_borrowed: 0,
             unit_lent: 0,
             unit_leverage: 0,
-            padding1: [0; 64],
+            padding1: [0; 44],
             apy: Rate::default(),
+            share_mint: Pubkey::default(),
+            price_source: OracleSource::Pyth,
+            align2: [0; 7],
+            fixed_price: 0,
+            oracle_guard: OracleGuard::default(),
+            flash_loan_amount: 0,
+            withdraw_queue_tail: 0,
+            withdraw_queued_amount: 0,
+            withdraw_filled_amount: 0,
+            withdraw_claimed_amount: 0,
+            insurance_fund_amount: 0,
+            insurance_covered_amount: 0,
+            bad_debt_amount: 0,
+            share_index: 0,
         }
     }
 }
@@ -99,9 +129,36 @@ impl VaultEarn {
         Ok(())
     }
 
-    pub fn change_price_oracle(&mut self, price_oracle: Pubkey, price_feed: [u8; 64]) -> Result<()> {
+    pub fn change_price_oracle(&mut self, price_source: OracleSource, price_oracle: Pubkey, price_feed: [u8; 64], fixed_price: u64) -> Result<()> {
+        self.price_source = price_source;
         self.price_oracle = price_oracle;
         self.price_feed = price_feed;
+        self.fixed_price = fixed_price;
+        Ok(())
+    }
+
+    // Returns the token price and the absolute value of its exponent
+    pub fn price(&self, price_oracle: &AccountInfo) -> Result<(u64, u32)> {
+        oracle::get_price(self.price_source, price_oracle, &self.price_feed, self.fixed_price, &self.oracle_guard)
+    }
+
+    pub fn set_share_mint(&mut self, share_mint: Pubkey) -> Result<()> {
+        require_keys_eq!(self.share_mint, Pubkey::default(), ErrorEarn::ShareMintAlreadyCreated);
+        self.share_mint = share_mint;
+        Ok(())
+    }
+
+    // Minted shares enter the average at their cost basis, a share carries no per holder index once transferred
+    pub fn mint_share(&mut self, share_supply: u64, unit: u64, index: u128) -> Result<()> {
+        if unit == 0 {
+            return Ok(());
+        }
+        let total_unit = (share_supply as u128).checked_add(unit as u128).ok_or(MathOverflow)?;
+        self.share_index = (share_supply as u128)
+            .checked_mul(self.share_index).ok_or(MathOverflow)?
+            .checked_add((unit as u128).checked_mul(index).ok_or(MathOverflow)?).ok_or(MathOverflow)?
+            .checked_div(total_unit).ok_or(MathOverflow)?;
+
         Ok(())
     }
 
@@ -161,19 +218,36 @@ impl VaultEarn {
         self.unit_to_amount(borrowable)
     }
 
-    pub fn borrow_available_amount(&mut self, config: &EarnConfig) -> Result<u128> {
+    // Borrow headroom under the ltv, utilization and borrow caps, never more than the liquidity not reserved for queued withdrawals and the insurance fund
+    pub fn borrow_available_amount(&mut self, config: &EarnConfig, liquidity_amount: u64) -> Result<u128> {
         let mut borrowable = decimals::mul_floor(UNIT_DECIMALS, self.unit_supply, UNIT_DECIMALS, config.ltv as u128, PERCENT_DECIMALS)?;
         borrowable = decimals::div_floor(UNIT_DECIMALS, borrowable, UNIT_DECIMALS, 100, 0)?;
         if borrowable == 0 || borrowable <= self.unit_borrowed {
             return Ok(0);
         }
-        let borrow_available_unit = borrowable.checked_sub(self.unit_borrowed).ok_or(MathOverflow)?;
-        self.unit_to_amount(borrow_available_unit)
+        let mut borrow_available_unit = borrowable.checked_sub(self.unit_borrowed).ok_or(MathOverflow)?;
+        // Utilization ceiling bounds the borrowed units the same way the ltv does
+        if config.max_utilization > 0 {
+            let mut utilization_unit = decimals::mul_floor(UNIT_DECIMALS, self.unit_supply, UNIT_DECIMALS, config.max_utilization as u128, PERCENT_DECIMALS)?;
+            utilization_unit = decimals::div_floor(UNIT_DECIMALS, utilization_unit, UNIT_DECIMALS, 100, 0)?;
+            borrow_available_unit = borrow_available_unit.min(utilization_unit.saturating_sub(self.unit_borrowed));
+        }
+        let mut borrow_available = self.unit_to_amount(borrow_available_unit)?;
+        if config.borrow_cap > 0 {
+            let leverage_amount = self.unit_to_amount(self.unit_leverage)?;
+            borrow_available = borrow_available.min((config.borrow_cap as u128).saturating_sub(leverage_amount));
+        }
+        borrow_available = borrow_available.min(self.withdraw_free_amount(liquidity_amount) as u128);
+        Ok(borrow_available)
     }
 
     pub fn mint(&mut self, config: &EarnConfig, unit: u64) -> Result<()> {
         require!(unit > 0, Errors::InvalidAmountZero);
         self.unit_supply = self.unit_supply.checked_add(unit as u128).ok_or(MathOverflow)?;
+        if config.supply_cap > 0 {
+            let supply_amount = self.unit_to_amount(self.unit_supply)?;
+            require_gte!(config.supply_cap as u128, supply_amount, ErrorEarn::SupplyCapExceeded);
+        }
         Ok(())
     }
 
@@ -196,15 +270,24 @@ impl VaultEarn {
         require!(unit > 0, Errors::InvalidAmountZero);
         self.unit_lent = self.unit_lent.checked_sub(unit as u128).ok_or(MathOverflow)?;
         self.unit_borrowed = self.unit_borrowed.checked_sub(unit as u128).ok_or(MathOverflow)?;
+        let amount = self.unit_to_amount(unit as u128)?;
+        self.fill_withdraw_queue(amount)?;
 
         Ok(())
     }
 
-    pub fn leverage(&mut self, borrowing_amount: u64) -> Result<()> {
+    pub fn leverage(&mut self, config: &EarnConfig, borrowing_amount: u64) -> Result<()> {
         require!(borrowing_amount > 0, Errors::InvalidAmountZero);
         let unit = decimals::div_ceil(UNIT_DECIMALS, borrowing_amount as u128, self.token_decimal, self.index, INDEX_DECIMALS)?;
         self.unit_leverage = self.unit_leverage.checked_add(unit).ok_or(MathOverflow)?;
         self.unit_borrowed = self.unit_borrowed.checked_add(unit).ok_or(MathOverflow)?;
+        if config.borrow_cap > 0 {
+            let leverage_amount = self.unit_to_amount(self.unit_leverage)?;
+            require_gte!(config.borrow_cap as u128, leverage_amount, ErrorEarn::BorrowCapExceeded);
+        }
+        if config.max_utilization > 0 {
+            require_gte!(config.max_utilization, self.utilization_rate()?, ErrorEarn::MaxUtilizationExceeded);
+        }
 
         Ok(())
     }
@@ -213,10 +296,96 @@ impl VaultEarn {
         require!(unit > 0, Errors::InvalidAmountZero);
         self.unit_leverage = self.unit_leverage.checked_sub(unit as u128).ok_or(MathOverflow)?;
         self.unit_borrowed = self.unit_borrowed.checked_sub(unit as u128).ok_or(MathOverflow)?;
+        let amount = self.unit_to_amount(unit as u128)?;
+        self.fill_withdraw_queue(amount)?;
+
+        Ok(())
+    }
+
+    // Liquidity owed to queued withdrawals, filled or not, that is not paid out yet
+    pub fn withdraw_reserved_amount(&self) -> u64 {
+        self.withdraw_queued_amount.saturating_sub(self.withdraw_claimed_amount)
+    }
+
+    // Liquidity free for withdrawals and borrowing once the queue and the insurance fund are served
+    pub fn withdraw_free_amount(&self, liquidity_amount: u64) -> u64 {
+        liquidity_amount
+            .saturating_sub(self.withdraw_reserved_amount())
+            .saturating_sub(self.insurance_fund_amount)
+    }
+
+    // Returns the ticket and the queued amount ahead of it
+    pub fn enqueue_withdraw(&mut self, amount: u64) -> Result<(u64, u64)> {
+        require!(amount > 0, Errors::InvalidAmountZero);
+        let ticket = self.withdraw_queue_tail;
+        let queued_before = self.withdraw_queued_amount;
+        self.withdraw_queue_tail = self.withdraw_queue_tail.checked_add(1).ok_or(MathOverflow)?;
+        self.withdraw_queued_amount = self.withdraw_queued_amount.checked_add(amount).ok_or(MathOverflow)?;
+
+        Ok((ticket, queued_before))
+    }
+
+    // Returned liquidity fills queued withdrawals first, in queue order
+    pub fn fill_withdraw_queue(&mut self, amount: u128) -> Result<()> {
+        let unfilled = self.withdraw_queued_amount.saturating_sub(self.withdraw_filled_amount);
+        let filled = amount.min(unfilled as u128) as u64;
+        self.withdraw_filled_amount = self.withdraw_filled_amount.checked_add(filled).ok_or(MathOverflow)?;
+
+        Ok(())
+    }
+
+    // Fills from liquidity not yet set aside for filled withdrawals, such as new deposits
+    pub fn fill_withdraw_queue_from_liquidity(&mut self, liquidity_amount: u64) -> Result<()> {
+        let set_aside = self.withdraw_filled_amount.saturating_sub(self.withdraw_claimed_amount);
+        self.fill_withdraw_queue(liquidity_amount.saturating_sub(set_aside) as u128)
+    }
+
+    pub fn is_withdraw_filled(&self, queued_before: u64, amount: u64) -> Result<bool> {
+        Ok(self.withdraw_filled_amount >= queued_before.checked_add(amount).ok_or(MathOverflow)?)
+    }
+
+    pub fn claim_withdraw(&mut self, queued_before: u64, amount: u64) -> Result<()> {
+        require!(self.is_withdraw_filled(queued_before, amount)?, ErrorEarn::WithdrawNotFilled);
+        self.withdraw_claimed_amount = self.withdraw_claimed_amount.checked_add(amount).ok_or(MathOverflow)?;
+
+        Ok(())
+    }
+
+    // Tokens of the insurance fund are already in the vault liquidity
+    pub fn fund_insurance(&mut self, amount: u64) -> Result<()> {
+        self.insurance_fund_amount = self.insurance_fund_amount.checked_add(amount).ok_or(MathOverflow)?;
 
         Ok(())
     }
 
+    // Debt written off without repayment, covered by the insurance fund first then by lowering the index
+    pub fn cover_bad_debt(&mut self, shortfall_amount: u64) -> Result<BadDebt> {
+        require!(shortfall_amount > 0, Errors::InvalidAmountZero);
+        let old_index = self.index;
+
+        let covered_amount = shortfall_amount.min(self.insurance_fund_amount);
+        self.insurance_fund_amount = self.insurance_fund_amount.checked_sub(covered_amount).ok_or(MathOverflow)?;
+        self.insurance_covered_amount = self.insurance_covered_amount.checked_add(covered_amount).ok_or(MathOverflow)?;
+
+        let socialized_amount = shortfall_amount.checked_sub(covered_amount).ok_or(MathOverflow)?;
+        if socialized_amount > 0 {
+            if self.unit_supply > 0 {
+                // Ceil so lenders never keep a claim on the missing liquidity
+                let delta_index = decimals::div_ceil(INDEX_DECIMALS, socialized_amount as u128, self.token_decimal, self.unit_supply, UNIT_DECIMALS)?;
+                self.index = self.index.saturating_sub(delta_index).max(1);
+            }
+            self.bad_debt_amount = self.bad_debt_amount.checked_add(socialized_amount).ok_or(MathOverflow)?;
+            // The repayment filled the withdraw queue with liquidity that never arrived
+            self.withdraw_filled_amount = self.withdraw_filled_amount.saturating_sub(socialized_amount).max(self.withdraw_claimed_amount);
+        }
+
+        Ok(BadDebt {
+            covered_amount,
+            socialized_amount,
+            old_index,
+        })
+    }
+
     pub fn set_index(&mut self, index: u128, apy: u32) -> Result<()> {
         require!(index > 0, Errors::InvalidAmountZero);
         self.index = index;
@@ -226,6 +395,47 @@ impl VaultEarn {
         Ok(())
     }
 
+    pub fn flash_borrow(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, Errors::InvalidAmountZero);
+        require_eq!(self.flash_loan_amount, 0, ErrorEarn::FlashLoanInProgress);
+        self.flash_loan_amount = amount;
+
+        Ok(())
+    }
+
+    // The fee stays in the liquidity and is shared by all lenders through the index
+    pub fn flash_repay(&mut self, amount: u64, fee_amount: u64) -> Result<()> {
+        require_gt!(self.flash_loan_amount, 0, ErrorEarn::NoFlashLoan);
+        require_eq!(self.flash_loan_amount, amount, ErrorEarn::InvalidFlashRepay);
+        self.flash_loan_amount = 0;
+        if fee_amount > 0 && self.unit_supply > 0 {
+            let delta_index = decimals::div_floor(INDEX_DECIMALS, fee_amount as u128, self.token_decimal, self.unit_supply, UNIT_DECIMALS)?;
+            self.index = self.index.checked_add(delta_index).ok_or(MathOverflow)?;
+        }
+
+        Ok(())
+    }
+
+    // Compound the index from the rate model, no-op while the rate model is not configured
+    pub fn accrue_interest(&mut self, config: &EarnConfig) -> Result<()> {
+        if !config.has_rate_model() {
+            return Ok(());
+        }
+        let timestamp = Clock::get()?.unix_timestamp;
+        let elapsed = timestamp.saturating_sub(self.last_index_updated);
+        if elapsed <= 0 {
+            return Ok(());
+        }
+        let utilization_rate = self.utilization_rate()?;
+        let borrow_rate = config.borrow_rate(utilization_rate)?;
+        let supply_rate = config.supply_rate(utilization_rate, borrow_rate)?;
+        self.index = decimals::compound(self.index, supply_rate, elapsed)?;
+        self.last_index_updated = timestamp;
+        self.apy.update_rate(supply_rate, timestamp)?;
+
+        Ok(())
+    }
+
     pub fn unit_to_amount(&mut self, unit: u128) -> Result<u128> {
         // Floor to prevent extra token withdraw
         let amount = decimals::mul_floor(self.token_decimal, unit, UNIT_DECIMALS, self.index, INDEX_DECIMALS)?;
@@ -247,4 +457,10 @@ pub struct InitVaultEarnParams {
     pub token_mint: Pubkey,
     pub token_decimal: u8,
     pub index: u128,
+}
+
+pub struct BadDebt {
+    pub covered_amount: u64,
+    pub socialized_amount: u64,
+    pub old_index: u128,
 }
\ No newline at end of file
@@ -2,9 +2,10 @@ use anchor_lang::{account, InitSpace};
 use anchor_lang::prelude::*;
 use derivative::Derivative;
 use crate::error::{Errors, ErrorLeverage, ErrorMath};
-use crate::state::Rate;
-use crate::util::{constant, decimals};
-use crate::util::constant::{FLOOR_CAP_RATIO, INDEX_DECIMALS, PERCENT_DECIMALS, PROTOCOL_CAP_RATIO};
+use crate::state::{EarnConfig, FallbackOracle, OracleGuard, Rate, VaultEarn};
+use crate::util::{constant, decimals, oracle};
+use crate::util::oracle_source::OracleSource;
+use crate::util::constant::{FLOOR_CAP_RATIO, INDEX_DECIMALS, MAX_FALLBACK_PRICE_ORACLES, PERCENT_DECIMALS, PROTOCOL_CAP_RATIO};
 
 #[derive(InitSpace, Derivative, PartialEq)]
 #[derivative(Debug)]
@@ -50,8 +51,17 @@ pub struct VaultLeverage {
     pub last_index_updated: i64,
     pub borrowing_apy: Rate,
     pub apy: Rate,
+    pub token_collateral_price_source: OracleSource,
+    pub native_collateral_price_source: OracleSource,
     #[derivative(Debug = "ignore")]
-    pub padding1: [u64; 64],
+    pub align3: [u8; 6],
+    pub token_collateral_fixed_price: u64, // 1.00 = 10^FIXED_PRICE_EXPONENT
+    pub native_collateral_fixed_price: u64, // 1.00 = 10^FIXED_PRICE_EXPONENT
+    pub oracle_guard: OracleGuard,
+    pub token_collateral_fallback_price_oracles: [FallbackOracle; 2],
+    pub native_collateral_fallback_price_oracles: [FallbackOracle; 2],
+    #[derivative(Debug = "ignore")]
+    pub padding1: [u64; 5],
 }
 
 impl Default for VaultLeverage {
@@ -89,7 +99,15 @@ impl Default for VaultLeverage {
             last_index_updated: 0,
             borrowing_apy: Rate::default(),
             apy: Rate::default(),
-            padding1: [0; 64],
+            token_collateral_price_source: OracleSource::Pyth,
+            native_collateral_price_source: OracleSource::Pyth,
+            align3: [0; 6],
+            token_collateral_fixed_price: 0,
+            native_collateral_fixed_price: 0,
+            oracle_guard: OracleGuard::default(),
+            token_collateral_fallback_price_oracles: [FallbackOracle::default(); MAX_FALLBACK_PRICE_ORACLES],
+            native_collateral_fallback_price_oracles: [FallbackOracle::default(); MAX_FALLBACK_PRICE_ORACLES],
+            padding1: [0; 5],
         }
     }
 }
@@ -124,14 +142,73 @@ impl VaultLeverage {
         Ok(())
     }
 
-    pub fn change_price_oracle(&mut self, token_collateral_price_oracle: Pubkey, token_collateral_price_feed: [u8; 64], native_collateral_price_oracle: Pubkey, native_collateral_price_feed: [u8; 64]) -> Result<()> {
+    pub fn change_price_oracle(
+        &mut self,
+        token_collateral_price_source: OracleSource,
+        token_collateral_price_oracle: Pubkey,
+        token_collateral_price_feed: [u8; 64],
+        token_collateral_fixed_price: u64,
+        native_collateral_price_source: OracleSource,
+        native_collateral_price_oracle: Pubkey,
+        native_collateral_price_feed: [u8; 64],
+        native_collateral_fixed_price: u64,
+    ) -> Result<()> {
+        self.token_collateral_price_source = token_collateral_price_source;
         self.token_collateral_price_oracle = token_collateral_price_oracle;
         self.token_collateral_price_feed = token_collateral_price_feed;
+        self.token_collateral_fixed_price = token_collateral_fixed_price;
+        self.native_collateral_price_source = native_collateral_price_source;
         self.native_collateral_price_oracle = native_collateral_price_oracle;
         self.native_collateral_price_feed = native_collateral_price_feed;
+        self.native_collateral_fixed_price = native_collateral_fixed_price;
+        self.check_price_oracles()
+    }
+
+    // Setting an empty oracle clears the slot
+    pub fn set_fallback_price_oracle(&mut self, index: usize, token_collateral: FallbackOracle, native_collateral: FallbackOracle) -> Result<()> {
+        require_gt!(MAX_FALLBACK_PRICE_ORACLES, index, Errors::InvalidFallbackPriceOracle);
+        require!(token_collateral.source != OracleSource::Fixed, Errors::InvalidFallbackPriceOracle);
+        require!(native_collateral.source != OracleSource::Fixed, Errors::InvalidFallbackPriceOracle);
+
+        self.token_collateral_fallback_price_oracles[index] = token_collateral;
+        self.native_collateral_fallback_price_oracles[index] = native_collateral;
+        self.check_price_oracles()
+    }
+
+    // Each side must read distinct oracles, a repeated oracle would outweigh the others in the median
+    fn check_price_oracles(&self) -> Result<()> {
+        for (primary_source, primary, fallbacks) in [
+            (self.token_collateral_price_source, self.token_collateral_price_oracle, &self.token_collateral_fallback_price_oracles),
+            (self.native_collateral_price_source, self.native_collateral_price_oracle, &self.native_collateral_fallback_price_oracles),
+        ] {
+            for (i, fallback) in fallbacks.iter().enumerate().filter(|(_, fallback)| fallback.is_set()) {
+                if primary_source != OracleSource::Fixed {
+                    require_keys_neq!(fallback.oracle, primary, Errors::DuplicatePriceOracle);
+                }
+                for other in fallbacks[i + 1..].iter().filter(|other| other.is_set()) {
+                    require_keys_neq!(fallback.oracle, other.oracle, Errors::DuplicatePriceOracle);
+                }
+            }
+        }
         Ok(())
     }
 
+    // Returns the median token collateral price of the available oracles, the absolute value of its exponent and the oracles used
+    pub fn token_collateral_price(&self, price_oracle: &AccountInfo, fallback_oracles: &[AccountInfo]) -> Result<(u64, u32, u8)> {
+        oracle::get_median_price(
+            self.token_collateral_price_source, price_oracle, &self.token_collateral_price_feed, self.token_collateral_fixed_price,
+            &self.token_collateral_fallback_price_oracles, fallback_oracles, &self.oracle_guard,
+        )
+    }
+
+    // Returns the median native collateral price of the available oracles, the absolute value of its exponent and the oracles used
+    pub fn native_collateral_price(&self, price_oracle: &AccountInfo, fallback_oracles: &[AccountInfo]) -> Result<(u64, u32, u8)> {
+        oracle::get_median_price(
+            self.native_collateral_price_source, price_oracle, &self.native_collateral_price_feed, self.native_collateral_fixed_price,
+            &self.native_collateral_fallback_price_oracles, fallback_oracles, &self.oracle_guard,
+        )
+    }
+
     // Multiply with unit to get the token value
     pub fn protocol_fee_factor(&self, protocol_fee: u32, utilization_rate: u32, open_borrowing_index: u128, close_borrowing_index: u128) -> Result<u128> {
         // Borrow APY (before Fee) x (Protocol Fee + (2 x Protocol Fee x (UR - 50%))
@@ -194,6 +271,32 @@ impl VaultLeverage {
         self.update_time()?;
         Ok(())
     }
+
+    // Compound the borrowing index by the borrow rate of the borrow vault
+    pub fn accrue_interest(&mut self, borrow_rate: u32) -> Result<()> {
+        let timestamp = Clock::get()?.unix_timestamp;
+        let elapsed = timestamp.saturating_sub(self.last_index_updated);
+        if elapsed <= 0 {
+            return Ok(());
+        }
+        self.borrowing_index = decimals::compound(self.borrowing_index, borrow_rate, elapsed)?;
+        self.last_index_updated = timestamp;
+        self.borrowing_apy.update_rate(borrow_rate, timestamp)?;
+
+        self.update_time()?;
+        Ok(())
+    }
+
+    // Accrue the borrow vault then the borrowing index at its borrow rate, before any debt is priced.
+    // No-op while the rate model is not configured, the indexer keeps the indexes then.
+    pub fn accrue_interest_from(&mut self, borrow_vault: &mut VaultEarn, earn_config: &EarnConfig) -> Result<()> {
+        if !earn_config.has_rate_model() {
+            return Ok(());
+        }
+        borrow_vault.accrue_interest(earn_config)?;
+        let borrow_rate = earn_config.borrow_rate(borrow_vault.utilization_rate()?)?;
+        self.accrue_interest(borrow_rate)
+    }
 }
 
 pub struct InitVaultLeverageParams {
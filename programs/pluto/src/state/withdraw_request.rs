@@ -0,0 +1,91 @@
+use anchor_lang::{account, InitSpace};
+use anchor_lang::prelude::*;
+use derivative::Derivative;
+use crate::error::Errors;
+
+// Withdrawal waiting in the vault queue, units burned at the index of the request
+#[derive(InitSpace, Derivative, PartialEq)]
+#[derivative(Debug)]
+#[account(zero_copy(unsafe))]
+#[repr(C)]
+pub struct WithdrawRequest {
+    pub is_initialized: bool,
+    pub version: u8,
+    pub bump: u8,
+    #[derivative(Debug = "ignore")]
+    pub align0: [u8; 5],
+    pub owner: Pubkey,
+    pub vault: Pubkey,
+    pub lender: Pubkey,
+    pub ticket: u64,
+    pub unit: u64,
+    pub index: u128,
+    pub amount: u64, // owed before fees
+    pub fee_amount: u64,
+    pub protocol_fee_amount: u64,
+    pub queued_before: u64, // vault queued amount when requested, filled once the vault fills past it
+    pub created_at: i64,
+    #[derivative(Debug = "ignore")]
+    pub padding1: [u64; 8],
+}
+
+impl Default for WithdrawRequest {
+    fn default() -> Self {
+        Self {
+            is_initialized: false,
+            version: 0,
+            bump: 0,
+            align0: [0; 5],
+            owner: Pubkey::default(),
+            vault: Pubkey::default(),
+            lender: Pubkey::default(),
+            ticket: 0,
+            unit: 0,
+            index: 0,
+            amount: 0,
+            fee_amount: 0,
+            protocol_fee_amount: 0,
+            queued_before: 0,
+            created_at: 0,
+            padding1: [0; 8],
+        }
+    }
+}
+
+impl WithdrawRequest {
+    pub fn init(&mut self, params: InitWithdrawRequestParams) -> Result<()> {
+        require!(params.amount > 0, Errors::InvalidAmountZero);
+        let clock = Clock::get()?;
+        *self = Self::default();
+        self.is_initialized = true;
+        self.version = 1;
+        self.bump = params.bump;
+        self.owner = params.owner;
+        self.vault = params.vault;
+        self.lender = params.lender;
+        self.ticket = params.ticket;
+        self.unit = params.unit;
+        self.index = params.index;
+        self.amount = params.amount;
+        self.fee_amount = params.fee_amount;
+        self.protocol_fee_amount = params.protocol_fee_amount;
+        self.queued_before = params.queued_before;
+        self.created_at = clock.unix_timestamp;
+
+        Ok(())
+    }
+}
+
+pub struct InitWithdrawRequestParams {
+    pub bump: u8,
+    pub owner: Pubkey,
+    pub vault: Pubkey,
+    pub lender: Pubkey,
+    pub ticket: u64,
+    pub unit: u64,
+    pub index: u128,
+    pub amount: u64,
+    pub fee_amount: u64,
+    pub protocol_fee_amount: u64,
+    pub queued_before: u64,
+}
\ No newline at end of file
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::error::Errors;
+use crate::state::{Protocol, Role, Roles};
+use crate::util::constant::{PERCENT_MAX, RISK_MANAGER_MAX_CAP_STEP, RISK_MANAGER_MAX_STEP};
+
+// The owner passes every check, other signers need the role granted in the protocol roles.
+// No role means the action is reserved to the owner.
+pub fn require_role(
+    protocol: &AccountLoader<Protocol>,
+    roles: &Option<AccountLoader<Roles>>,
+    authority: &Pubkey,
+    role: Option<Role>,
+) -> Result<()> {
+    if protocol.load()?.owner == *authority {
+        return Ok(());
+    }
+
+    let role = role.ok_or(Errors::NotOwner)?;
+    let roles = roles.as_ref().ok_or(Errors::MissingRole)?.load()?;
+    require_keys_eq!(roles.protocol, protocol.key(), Errors::InvalidProtocol);
+    require!(roles.has_role(authority, role), Errors::MissingRole);
+
+    Ok(())
+}
+
+// Bounds of a risk manager change of a ltv or threshold
+pub fn is_within_risk_step(old: u32, new: u32) -> bool {
+    old.abs_diff(new) <= RISK_MANAGER_MAX_STEP
+}
+
+// Bounds of a risk manager change of a supply or borrow cap, 0 = no cap can only be changed by the owner
+pub fn is_within_cap_step(old: u64, new: u64) -> bool {
+    if old == new {
+        return true;
+    }
+    if old == 0 || new == 0 {
+        return false;
+    }
+    old.abs_diff(new) as u128 * PERCENT_MAX as u128 <= old as u128 * RISK_MANAGER_MAX_CAP_STEP as u128
+}
+
+// Whether an optional config update field differs from the current value
+pub fn is_changed<T: PartialEq>(value: Option<T>, current: T) -> bool {
+    value.is_some_and(|value| value != current)
+}
\ No newline at end of file
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::Pubkey;
 use anchor_spl::metadata::mpl_token_metadata;
 
-fn find_metadata_account(mint: &Pubkey) -> (Pubkey, u8) {
+pub fn find_metadata_account(mint: &Pubkey) -> (Pubkey, u8) {
     Pubkey::find_program_address(
         &[
             "metadata".as_bytes(),
@@ -10,4 +10,16 @@ fn find_metadata_account(mint: &Pubkey) -> (Pubkey, u8) {
         ],
         &mpl_token_metadata::ID,
     )
+}
+
+pub fn find_master_edition_account(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            "metadata".as_bytes(),
+            mpl_token_metadata::ID.as_ref(),
+            mint.as_ref(),
+            "edition".as_bytes(),
+        ],
+        &mpl_token_metadata::ID,
+    )
 }
\ No newline at end of file
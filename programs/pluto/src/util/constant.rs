@@ -13,12 +13,19 @@ pub const LAMPORTS_ONE: u64 = 10u64.pow(9);
 
 pub const FLOOR_CAP_RATIO: u32 = 80 * PERCENT_ONE; // 80%
 pub const PROTOCOL_CAP_RATIO: u32 = 50 * PERCENT_ONE; // 50%
+pub const MAX_PROTOCOL_FEE: u32 = 50 * PERCENT_ONE; // 50%, protocol share of the interest
+pub const MAX_TRANSACTION_FEE: u32 = 5 * PERCENT_ONE; // 5%, deposit, withdraw, borrow, leverage, deleverage and closing fees
+pub const MAX_LIQUIDATION_FEE: u32 = 20 * PERCENT_ONE; // 20%
+pub const MAX_FLASH_FEE: u32 = PERCENT_ONE; // 1%
+pub const MAX_LIQUIDATOR_SLIPPAGE: u32 = PERCENT_ONE / 2; // 0.5%, slippage allowed to callers other than the keeper
+pub const RISK_MANAGER_MAX_STEP: u32 = 5 * PERCENT_ONE; // 5%, largest ltv or threshold move by a risk manager
+pub const RISK_MANAGER_MAX_CAP_STEP: u32 = 20 * PERCENT_ONE; // 20%, largest supply or borrow cap move by a risk manager, relative to the current cap
 
 pub const MAX_DECIMALS: u8 = 38;
 pub const MAX_DECIMALS_64: u8 = 20;
 
-pub const MIN_LEVERAGE: u32 = 101; // 1.01
-pub const MAX_LEVERAGE: u32 = 500 * 10u32.pow(2); // 500x
+pub const MIN_LEVERAGE: u32 = 1010; // 1.01x, leverage 1 = 10^3
+pub const MAX_LEVERAGE: u32 = 500 * LEVERAGE_ONE; // 500x
 pub const LEVERAGE_STEP: u32 = 1 * 10u32.pow(2); // 500x
 
 pub const MIN_DEPOSIT_LIMIT: u64 = 1;
@@ -34,18 +41,30 @@ pub const TIME_ONE_DAY: i64 = 24 * TIME_ONE_HOUR;
 pub const TIME_ONE_WEEK: i64 = 7 * TIME_ONE_DAY;
 pub const TIME_ONE_MONTH: i64 = 30 * TIME_ONE_DAY;
 pub const TIME_ONE_YEAR: i64 = 365 * TIME_ONE_DAY;
+pub const MAX_TIMELOCK_DELAY: i64 = TIME_ONE_MONTH;
 
 pub const LEVERAGE_MAX_SAFETY: u32 = 5000; // 5.00
 pub const LEVERAGE_ONE: u32 = 1000; // 1.00
 
-pub const MAX_OBLIGATION_POSITIONS: u8 = 3;
+pub const OBLIGATION_INLINE_POSITIONS: usize = 3;
+pub const MAX_OBLIGATION_POSITIONS: usize = i8::MAX as usize; // Position number is stored as i8
+
+pub const POSITION_NFT_NAME: &str = "Pluto Position";
+pub const POSITION_NFT_SYMBOL: &str = "PLUTO";
 
 pub const MAX_ORACLE_AGE: u64 = 180;
+pub const MAX_ORACLE_AGE_LIMIT: u64 = 3600; // upper bound of the per vault max age
+pub const MAX_FALLBACK_PRICE_ORACLES: usize = 2; // per vault side, next to the primary oracle
+pub const FIXED_PRICE_EXPONENT: u32 = 8; // 1.00 = 10^8
+pub const USD_DECIMALS: u8 = 6;
 
 pub const USDC_PRICE_FEEDS: &[u8; 64] = b"eaa020c61cc479712813461ce153894a96a6c00b21ed0cfc2798d1f9a9e9c94a";
 
 pub const WSOL_TOKEN_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+pub const MAX_SWAP_ROUTERS: usize = 8; // allowed swap routers per leverage vault
+pub const MAX_ROLE_MEMBERS: usize = 16; // role holders per protocol
 pub const JUPITER_SWAP_PROGRAM_ID: Pubkey = pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+pub const SWITCHBOARD_ON_DEMAND_PROGRAM_ID: Pubkey = pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
 
 pub const MERCURY_PROGRAM_ID: Pubkey = pubkey!("E4HWdh2qyNYdjuQqvkXUyj73Z2FAnyq5w5PRDfDxzn59");
 pub const MERCURY_AMM_ADDRESS: Pubkey = pubkey!("89AUkCRrbkrnDnUcdMHukfdRPeQDfJ1Tj6V8oZpWCbt5");
@@ -53,4 +72,5 @@ pub const MERCURY_POOL_ADDRESS: Pubkey = pubkey!("24r3X9TnsyzYfZDNycoSd2kUmJQeFW
 pub const MERCURY_POOL_AUTHORITY_ADDRESS: Pubkey = pubkey!("76W2GRHvyA8HQJSWwvbywugdcSpSmqobNarHVYGWgxGg");
 
 // Liquidation can execute only if HF is less than this value
-pub const LIQUIDATION_HF_THRESHOLD: u8 = 1;
\ No newline at end of file
+pub const LIQUIDATION_HF_THRESHOLD: u8 = 1;
+pub const HEALTH_FACTOR_ONE: u32 = 1000; // 1.00
\ No newline at end of file
@@ -2,8 +2,8 @@ use crate::error::ErrorMath;
 use anchor_lang::prelude::*;
 use fixed::traits::Fixed;
 use crate::error::ErrorMath::{DivideByZero, MathOverflow, MathOverflow1, MathOverflow2};
-use crate::util::constant::INDEX_ONE;
-use crate::util::fraction::{Fraction};
+use crate::util::constant::{INDEX_ONE, PERCENT_MAX, TIME_ONE_YEAR};
+use crate::util::fraction::{Fraction, FractionExtra};
 
 pub fn mul(t_decimals: u8, a: u128, a_decimals: u8, b: u128, b_decimals: u8) -> Result<u128> {
     let res_decimals = a_decimals + b_decimals;
@@ -76,4 +76,20 @@ pub fn pow(a: u128, b: u32) -> Result<u128> {
         y = a.checked_mul(y).unwrap().checked_div(INDEX_ONE).unwrap()
     }
     Ok(y)
+}
+
+// Compound an index by a yearly rate (100% = 10^5) per second over the elapsed seconds
+pub fn compound(index: u128, yearly_rate: u32, elapsed: i64) -> Result<u128> {
+    if yearly_rate == 0 || elapsed <= 0 {
+        return Ok(index);
+    }
+    let rate_per_second = Fraction::from_num(yearly_rate)
+        .checked_div(Fraction::from_num(PERCENT_MAX)).ok_or(Error::from(DivideByZero))?
+        .checked_div(Fraction::from_num(TIME_ONE_YEAR)).ok_or(Error::from(DivideByZero))?;
+    let elapsed = u32::try_from(elapsed).map_err(|_| MathOverflow)?;
+    let factor = Fraction::ONE.checked_add(rate_per_second).ok_or(Error::from(MathOverflow))?
+        .checked_pow(elapsed).ok_or(Error::from(MathOverflow1))?;
+    let res = Fraction::from_num(index).checked_mul(factor).ok_or(Error::from(MathOverflow2))?;
+
+    Ok(res.to_num::<u128>())
 }
\ No newline at end of file
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::state::FeeSplit;
+use crate::util::transfer_token::transfer_token_with_signer;
+
+// Moves a fee vault balance to the protocol destinations, signed by the config authority owning the fee vault
+pub fn sweep_fees<'info>(
+    fee_vault: AccountInfo<'info>,
+    treasury_ata: AccountInfo<'info>,
+    insurance_fund_ata: AccountInfo<'info>,
+    buyback_ata: AccountInfo<'info>,
+    config_authority: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    decimals: u8,
+    split: &FeeSplit,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let destinations = [
+        (treasury_ata, split.treasury_amount),
+        (insurance_fund_ata, split.insurance_amount),
+        (buyback_ata, split.buyback_amount),
+    ];
+
+    for (destination, amount) in destinations {
+        if amount > 0 {
+            transfer_token_with_signer(
+                fee_vault.clone(),
+                destination,
+                config_authority.clone(),
+                token_program.clone(),
+                mint.clone(),
+                amount,
+                decimals,
+                signer_seeds,
+            )?;
+        }
+    }
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::util::transfer_token::transfer_token;
+
+// Pays a fee from the payer, the insurance share to the borrow vault liquidity and the rest to the fee vault.
+// The caller records the insurance share on the borrow vault.
+pub fn pay_fee_with_insurance<'info>(
+    from: AccountInfo<'info>,
+    fee_vault: AccountInfo<'info>,
+    borrow_vault_liquidity: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    decimals: u8,
+    fee_amount: u64,
+    insurance_amount: u64,
+) -> Result<()> {
+    if insurance_amount > 0 {
+        transfer_token(
+            from.clone(),
+            borrow_vault_liquidity,
+            authority.clone(),
+            token_program.clone(),
+            mint.clone(),
+            insurance_amount,
+            decimals,
+        )?;
+    }
+
+    let fee_vault_amount = fee_amount.saturating_sub(insurance_amount);
+    if fee_vault_amount > 0 {
+        transfer_token(
+            from,
+            fee_vault,
+            authority,
+            token_program,
+            mint,
+            fee_vault_amount,
+            decimals,
+        )?;
+    }
+
+    Ok(())
+}
\ No newline at end of file
@@ -6,3 +6,12 @@ pub mod common;
 pub mod calculate;
 pub mod fraction;
 pub mod action;
+pub mod oracle;
+pub mod oracle_source;
+pub mod share_token;
+pub mod fee_sweep;
+pub mod insurance;
+pub mod access;
+pub mod pause;
+
+pub mod swap;
\ No newline at end of file
@@ -0,0 +1,220 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Owner;
+use pyth_solana_receiver_sdk::price_update::{get_feed_id_from_hex, PriceUpdateV2, VerificationLevel};
+use crate::error::Errors;
+use crate::error::ErrorMath::MathOverflow;
+use crate::state::{FallbackOracle, OracleGuard};
+use crate::util::constant::{FIXED_PRICE_EXPONENT, PERCENT_MAX, SWITCHBOARD_ON_DEMAND_PROGRAM_ID, USD_DECIMALS};
+use crate::util::decimals;
+use crate::util::oracle_source::OracleSource;
+
+// Switchboard on demand pull feed layout, values are scaled by 10^18
+const SWITCHBOARD_PULL_FEED_DISCRIMINATOR: [u8; 8] = [196, 27, 108, 196, 10, 215, 219, 40];
+const SWITCHBOARD_LAST_UPDATE_TIMESTAMP_OFFSET: usize = 8 + 2208;
+const SWITCHBOARD_RESULT_VALUE_OFFSET: usize = 8 + 2256;
+const SWITCHBOARD_RESULT_STD_DEV_OFFSET: usize = SWITCHBOARD_RESULT_VALUE_OFFSET + 16;
+const SWITCHBOARD_DECIMALS: u32 = 18;
+const SWITCHBOARD_PRICE_EXPONENT: u32 = 8;
+
+// Returns the price and the absolute value of its exponent
+pub fn get_price(source: OracleSource, price_oracle: &AccountInfo, price_feed: &[u8; 64], fixed_price: u64, guard: &OracleGuard) -> Result<(u64, u32)> {
+    match source {
+        OracleSource::Pyth => get_pyth_price(price_oracle, price_feed, guard),
+        OracleSource::SwitchboardOnDemand => get_switchboard_price(price_oracle, guard),
+        OracleSource::Fixed => {
+            require!(fixed_price > 0, Errors::PriceOracleError);
+            Ok((fixed_price, FIXED_PRICE_EXPONENT))
+        }
+    }
+}
+
+// Median price of the primary and fallback oracles passing the guard, with the absolute value of its exponent
+// and the bitmask of the oracles used, bit 0 is the primary oracle and bit 1 + i the fallback oracle i
+pub fn get_median_price(
+    source: OracleSource,
+    price_oracle: &AccountInfo,
+    price_feed: &[u8; 64],
+    fixed_price: u64,
+    fallbacks: &[FallbackOracle],
+    fallback_accounts: &[AccountInfo],
+    guard: &OracleGuard,
+) -> Result<(u64, u32, u8)> {
+    let mut prices: Vec<(u64, u32)> = Vec::with_capacity(1 + fallbacks.len());
+    let mut sources: u8 = 0;
+    let mut first_error = None;
+
+    match get_price(source, price_oracle, price_feed, fixed_price, guard) {
+        Ok(price) => {
+            prices.push(price);
+            sources |= 1;
+        }
+        Err(error) => first_error = Some(error),
+    }
+
+    for (i, fallback) in fallbacks.iter().enumerate().filter(|(_, fallback)| fallback.is_set()) {
+        let account = fallback_accounts.iter()
+            .find(|account| account.key() == fallback.oracle)
+            .ok_or(Errors::MissingPriceOracle)?;
+
+        match get_price(fallback.source, account, &fallback.feed, 0, guard) {
+            Ok(price) => {
+                prices.push(price);
+                sources |= 1 << (i + 1);
+            }
+            Err(error) => {
+                msg!("fallback price oracle {:?} skipped: {:?}", fallback.oracle, error);
+                first_error.get_or_insert(error);
+            }
+        }
+    }
+
+    // Without any usable price, report why the first oracle was rejected
+    if prices.is_empty() {
+        return Err(first_error.unwrap_or_else(|| Errors::PriceOracleError.into()));
+    }
+
+    let (price, exponent) = median(&prices)?;
+    Ok((price, exponent, sources))
+}
+
+// Median of prices with different exponents, scaled to the largest exponent
+fn median(prices: &[(u64, u32)]) -> Result<(u64, u32)> {
+    let exponent = prices.iter().map(|(_, exponent)| *exponent).max().ok_or(Errors::PriceOracleError)?;
+    let mut scaled = prices.iter()
+        .map(|(price, price_exponent)| {
+            (*price as u128).checked_mul(10u128.checked_pow(exponent - price_exponent).ok_or(MathOverflow)?).ok_or(MathOverflow.into())
+        })
+        .collect::<Result<Vec<u128>>>()?;
+    scaled.sort_unstable();
+
+    let middle = scaled.len() / 2;
+    let price = if scaled.len() % 2 == 0 {
+        scaled[middle - 1].checked_add(scaled[middle]).ok_or(MathOverflow)? / 2
+    } else {
+        scaled[middle]
+    };
+
+    Ok((u64::try_from(price).map_err(|_| MathOverflow)?, exponent))
+}
+
+// Checks the oracle account is of the given source, without the price freshness
+pub fn validate_source(source: OracleSource, price_oracle: &AccountInfo, price_feed: &[u8; 64], fixed_price: u64) -> Result<()> {
+    match source {
+        OracleSource::Pyth => {
+            load_pyth_price_update(price_oracle)?;
+            let feed = std::str::from_utf8(price_feed).map_err(|_| Errors::InvalidPriceOracle)?;
+            get_feed_id_from_hex(feed)?;
+        }
+        OracleSource::SwitchboardOnDemand => {
+            load_switchboard_pull_feed(price_oracle)?;
+        }
+        OracleSource::Fixed => {
+            require!(fixed_price > 0, Errors::InvalidOracleSource);
+        }
+    }
+
+    Ok(())
+}
+
+fn load_pyth_price_update(price_oracle: &AccountInfo) -> Result<PriceUpdateV2> {
+    require_keys_eq!(*price_oracle.owner, PriceUpdateV2::owner(), Errors::InvalidOracleSource);
+    let data = price_oracle.try_borrow_data()?;
+    PriceUpdateV2::try_deserialize(&mut &data[..]).map_err(|_| Errors::InvalidOracleSource.into())
+}
+
+fn get_pyth_price(price_oracle: &AccountInfo, price_feed: &[u8; 64], guard: &OracleGuard) -> Result<(u64, u32)> {
+    let price_update = load_pyth_price_update(price_oracle)?;
+    let feed = std::str::from_utf8(price_feed).map_err(|_| Errors::InvalidPriceOracle)?;
+    let feed_id = get_feed_id_from_hex(feed)?;
+    require!(price_update.verification_level.gte(VerificationLevel::Full), Errors::PriceOracleError);
+
+    let message = &price_update.price_message;
+    require!(message.feed_id == feed_id, Errors::InvalidPriceOracle);
+    require!(message.price > 0, Errors::PriceOracleError);
+    require!(message.exponent <= 0, Errors::PriceOracleError);
+
+    check_age(message.publish_time, guard)?;
+    check_confidence(message.price as u128, message.conf as u128, guard)?;
+    if message.ema_price > 0 {
+        check_ema_deviation(message.price as u128, message.ema_price as u128, guard)?;
+    }
+
+    Ok((message.price as u64, message.exponent.unsigned_abs()))
+}
+
+// Returns the last update timestamp, the result value and its standard deviation of the pull feed
+fn load_switchboard_pull_feed(price_oracle: &AccountInfo) -> Result<(i64, i128, i128)> {
+    require_keys_eq!(*price_oracle.owner, SWITCHBOARD_ON_DEMAND_PROGRAM_ID, Errors::InvalidOracleSource);
+    let data = price_oracle.try_borrow_data()?;
+    require!(data.len() >= SWITCHBOARD_RESULT_STD_DEV_OFFSET + 16, Errors::InvalidOracleSource);
+    require!(data[..8] == SWITCHBOARD_PULL_FEED_DISCRIMINATOR, Errors::InvalidOracleSource);
+
+    let last_update_timestamp = i64::from_le_bytes(
+        data[SWITCHBOARD_LAST_UPDATE_TIMESTAMP_OFFSET..SWITCHBOARD_LAST_UPDATE_TIMESTAMP_OFFSET + 8].try_into().unwrap()
+    );
+    let value = i128::from_le_bytes(
+        data[SWITCHBOARD_RESULT_VALUE_OFFSET..SWITCHBOARD_RESULT_VALUE_OFFSET + 16].try_into().unwrap()
+    );
+    let std_dev = i128::from_le_bytes(
+        data[SWITCHBOARD_RESULT_STD_DEV_OFFSET..SWITCHBOARD_RESULT_STD_DEV_OFFSET + 16].try_into().unwrap()
+    );
+
+    Ok((last_update_timestamp, value, std_dev))
+}
+
+// Pull feeds carry no ema price, only the age and the confidence are checked
+fn get_switchboard_price(price_oracle: &AccountInfo, guard: &OracleGuard) -> Result<(u64, u32)> {
+    let (last_update_timestamp, value, std_dev) = load_switchboard_pull_feed(price_oracle)?;
+    require!(value > 0, Errors::PriceOracleError);
+    require!(std_dev >= 0, Errors::PriceOracleError);
+
+    check_age(last_update_timestamp, guard)?;
+    check_confidence(value as u128, std_dev as u128, guard)?;
+
+    let price = (value as u128)
+        .checked_div(10u128.pow(SWITCHBOARD_DECIMALS - SWITCHBOARD_PRICE_EXPONENT)).ok_or(MathOverflow)?;
+    require!(price > 0, Errors::PriceOracleError);
+
+    Ok((u64::try_from(price).map_err(|_| MathOverflow)?, SWITCHBOARD_PRICE_EXPONENT))
+}
+
+fn check_age(publish_time: i64, guard: &OracleGuard) -> Result<()> {
+    let age = Clock::get()?.unix_timestamp.saturating_sub(publish_time);
+    require!(age >= 0 && age as u64 <= guard.max_age(), Errors::StalePriceOracle);
+    Ok(())
+}
+
+fn check_confidence(price: u128, confidence: u128, guard: &OracleGuard) -> Result<()> {
+    if guard.max_confidence_rate == 0 {
+        return Ok(());
+    }
+
+    // confidence / price <= max_confidence_rate / PERCENT_MAX
+    let lhs = confidence.checked_mul(PERCENT_MAX as u128).ok_or(MathOverflow)?;
+    let rhs = price.checked_mul(guard.max_confidence_rate as u128).ok_or(MathOverflow)?;
+    require!(lhs <= rhs, Errors::PriceConfidenceTooWide);
+    Ok(())
+}
+
+fn check_ema_deviation(price: u128, ema_price: u128, guard: &OracleGuard) -> Result<()> {
+    if guard.max_ema_deviation_rate == 0 {
+        return Ok(());
+    }
+
+    // |price - ema_price| / ema_price <= max_ema_deviation_rate / PERCENT_MAX
+    let lhs = price.abs_diff(ema_price).checked_mul(PERCENT_MAX as u128).ok_or(MathOverflow)?;
+    let rhs = ema_price.checked_mul(guard.max_ema_deviation_rate as u128).ok_or(MathOverflow)?;
+    require!(lhs <= rhs, Errors::PriceEmaDeviationTooHigh);
+    Ok(())
+}
+
+// Value of the amount in usd, 1 = 10^USD_DECIMALS
+pub fn value(amount: u64, decimal: u8, price: u64, exponent: u32) -> Result<u128> {
+    decimals::mul(USD_DECIMALS, amount as u128, decimal, price as u128, exponent as u8)
+}
+
+// Amount of the token worth the given usd value
+pub fn amount(value: u128, decimal: u8, price: u64, exponent: u32) -> Result<u64> {
+    require!(price > 0, Errors::PriceOracleError);
+    Ok(decimals::div(decimal, value, USD_DECIMALS, price as u128, exponent as u8)? as u64)
+}
\ No newline at end of file
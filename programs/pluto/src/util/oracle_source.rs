@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+#[derive(InitSpace, Debug, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum OracleSource {
+    Pyth,
+    SwitchboardOnDemand,
+    Fixed,
+}
\ No newline at end of file
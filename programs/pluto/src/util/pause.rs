@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::error::Errors;
+use crate::util::action::LeverageAction;
+
+// Operations that can be paused one by one, as bits of the pause flags of the protocol and the configs
+pub struct PauseFlags;
+
+impl PauseFlags {
+    pub const DEPOSIT: u16 = 1 << 0; // earn deposits and added collateral
+    pub const WITHDRAW: u16 = 1 << 1; // earn withdrawals and withdraw queue
+    pub const OPEN: u16 = 1 << 2; // opening and increasing positions
+    pub const CLOSE: u16 = 1 << 3; // closing, deleveraging and position settings
+    pub const LIQUIDATE: u16 = 1 << 4;
+    pub const FLASH: u16 = 1 << 5;
+    pub const INDEX_UPDATE: u16 = 1 << 6;
+    pub const ALL: u16 = (1 << 7) - 1;
+
+    pub fn is_valid(pause_flags: u16) -> bool {
+        pause_flags & !Self::ALL == 0
+    }
+
+    // Operation a keeper step belongs to, ejection is never paused so frozen vaults can be unwound
+    pub fn of_action(action: LeverageAction) -> u16 {
+        match action {
+            LeverageAction::Open | LeverageAction::AddPosition => Self::OPEN,
+            LeverageAction::AddCollateral => Self::DEPOSIT,
+            LeverageAction::Close | LeverageAction::Safe | LeverageAction::Deleverage | LeverageAction::TakeProfit => Self::CLOSE,
+            LeverageAction::Liquidate => Self::LIQUIDATE,
+            LeverageAction::Idle | LeverageAction::Eject => 0,
+        }
+    }
+}
+
+// An operation is paused as soon as the protocol or one of the configs involved pauses it
+pub fn is_paused(pause_flags: &[u16], operation: u16) -> bool {
+    pause_flags.iter().any(|flags| flags & operation != 0)
+}
+
+pub fn require_not_paused(pause_flags: &[u16], operation: u16) -> Result<()> {
+    require!(!is_paused(pause_flags, operation), Errors::OperationPaused);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,46 @@
+use anchor_lang::{
+    prelude::{AccountInfo, CpiContext},
+    Result,
+};
+use anchor_spl::token_interface::{burn, mint_to, Burn, MintTo};
+
+pub fn mint_share_token_with_signer<'info>(
+    mint: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let cpi_accounts = MintTo {
+        mint,
+        to,
+        authority,
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer_seeds);
+
+    mint_to(cpi_ctx, amount)?;
+
+    Ok(())
+}
+
+pub fn burn_share_token<'info>(
+    mint: AccountInfo<'info>,
+    from: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = Burn {
+        mint,
+        from,
+        authority,
+    };
+
+    let cpi_ctx = CpiContext::new(token_program, cpi_accounts);
+
+    burn(cpi_ctx, amount)?;
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke;
+use anchor_spl::token_interface::TokenAccount;
+use crate::error::ErrorLeverage;
+use crate::state::SwapRouters;
+
+// Route accounts are the trailing remaining accounts, the leading ones are left for the fallback oracles
+pub fn route_accounts<'a, 'info>(remaining_accounts: &'a [AccountInfo<'info>], route_accounts_len: u8) -> Result<&'a [AccountInfo<'info>]> {
+    let route_accounts_len = route_accounts_len as usize;
+    require!(route_accounts_len > 0 && route_accounts_len <= remaining_accounts.len(), ErrorLeverage::InvalidRouteAccounts);
+    Ok(&remaining_accounts[remaining_accounts.len() - route_accounts_len..])
+}
+
+// Swap by cpi into a router allowed by the vault, with the route built by the caller.
+// Only the signers of the outer instruction sign the route, never a vault authority.
+pub fn swap<'info>(
+    swap_routers: &AccountInfo<'info>,
+    swap_program: &AccountInfo<'info>,
+    route_accounts: &[AccountInfo<'info>],
+    route_data: Vec<u8>,
+) -> Result<()> {
+    let ix = Instruction {
+        program_id: swap_program.key(),
+        accounts: route_accounts.iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: route_data,
+    };
+
+    require!(SwapRouters::is_allowed_swap(swap_routers, &ix)?, ErrorLeverage::SwapRouterNotAllowed);
+
+    let mut account_infos = route_accounts.to_vec();
+    account_infos.push(swap_program.clone());
+
+    invoke(&ix, &account_infos)?;
+
+    Ok(())
+}
+
+// Balance read straight from the account data, for token accounts changed by a cpi
+pub fn token_balance(account: &AccountInfo) -> Result<u64> {
+    let data = account.try_borrow_data()?;
+    Ok(TokenAccount::try_deserialize(&mut &data[..])?.amount)
+}
\ No newline at end of file
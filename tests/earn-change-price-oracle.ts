@@ -16,8 +16,9 @@ describe("pluto", () => {
   it("Is initialized!", async () => {
     let usdcFeed = Array.from(Uint8Array.from(Buffer.from(accounts.tokenCollateralPriceFeed, "hex")));
     // Add your test here.
+    // Pegged assets can use { fixed: {} } with the price in 10^8 instead of a feed
     const ix = await program.methods.earnVaultChangePriceOracle(
-        usdcFeed,
+        { pyth: {} }, usdcFeed, new anchor.BN(0),
     ).accounts({
       vault: new anchor.web3.PublicKey(accounts.earnVault),
     }).instruction();
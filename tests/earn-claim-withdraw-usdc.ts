@@ -0,0 +1,44 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Pluto } from "../target/types/pluto";
+import { PublicKey } from "@solana/web3.js";
+// @ts-ignore
+import accs from "./accounts.json";
+
+describe("earn-claim-withdraw-usdc", () => {
+  const provider = anchor.AnchorProvider.env();
+  // Configure the client to use the local cluster.
+  anchor.setProvider(provider);
+  const accounts = accs.dev.usdc;
+  const program = anchor.workspace.Pluto as Program<Pluto>;
+
+  it("Is initialized!", async () => {
+    const vaultAccount = new PublicKey(accounts.earnVault);
+    const ticket = new anchor.BN(0);
+
+    const [withdrawRequest] = PublicKey.findProgramAddressSync(
+        [Buffer.from("withdraw_request_v01"), vaultAccount.toBuffer(), ticket.toArrayLike(Buffer, "le", 8)],
+        program.programId,
+    );
+
+    const claim_withdraw_ix = await program.methods.earnVaultClaimWithdraw().accounts({
+      vault: vaultAccount,
+      withdrawRequest: withdrawRequest,
+    }).instruction();
+
+    let trx = new anchor.web3.Transaction();
+    trx.add(
+        anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({
+          units: 500000
+        }),
+        anchor.web3.ComputeBudgetProgram.setComputeUnitPrice({
+          microLamports: 0,
+        }),
+        claim_withdraw_ix,
+    );
+
+    let tx = await provider.sendAndConfirm(trx);
+
+    console.log(`TX: ${tx}`);
+  });
+});
\ No newline at end of file
@@ -0,0 +1,46 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Pluto } from "../target/types/pluto";
+import { PublicKey } from "@solana/web3.js";
+import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+// @ts-ignore
+import accs from "./accounts.json";
+
+describe("earn-config-collect-fees-usdc", () => {
+  const provider = anchor.AnchorProvider.env();
+  // Configure the client to use the local cluster.
+  anchor.setProvider(provider);
+  const accounts = accs.dev.usdc;
+  const program = anchor.workspace.Pluto as Program<Pluto>;
+
+  it("Is initialized!", async () => {
+    const protocol = await program.account.protocol.fetch(new PublicKey(accounts.protocol));
+    const tokenMint = new PublicKey(accounts.tokenMintA);
+
+    const collect_fees_ix = await program.methods.earnConfigCollectFees().accounts({
+      protocol: new PublicKey(accounts.protocol),
+      config: new PublicKey(accounts.earnConfig),
+      earnFeeVault: new PublicKey(accounts.feeVault),
+      treasuryAta: getAssociatedTokenAddressSync(tokenMint, protocol.treasury, true),
+      insuranceFundAta: getAssociatedTokenAddressSync(tokenMint, protocol.insuranceFund, true),
+      buybackAta: getAssociatedTokenAddressSync(tokenMint, protocol.buyback, true),
+      tokenMint: tokenMint,
+      tokenProgram: new PublicKey(accounts.tokenProgramA),
+    }).instruction();
+
+    let trx = new anchor.web3.Transaction();
+    trx.add(
+        anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({
+          units: 500000
+        }),
+        anchor.web3.ComputeBudgetProgram.setComputeUnitPrice({
+          microLamports: 0,
+        }),
+        collect_fees_ix,
+    );
+
+    let tx = await provider.sendAndConfirm(trx);
+
+    console.log(`TX: ${tx}`);
+  });
+});
\ No newline at end of file
@@ -16,7 +16,7 @@ describe("earn-config-create-sol", () => {
     // Add your test here.
     // const tx = await program.methods.createVault().rpc();
     const tx = await program.methods.earnConfigCreate(
-        false, // Frozen
+        0, // Pause flags (none)
         2000, // Protocol Fee (2%)
         90000, // LTV (90%)
         0, // Deposit Fee (0%)
@@ -0,0 +1,26 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import { Pluto } from "../target/types/pluto";
+import { PublicKey } from "@solana/web3.js";
+// @ts-ignore
+import accs from "./accounts.json";
+
+describe("earn-config-execute-set-usdc", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const accounts = accs.production.usdc;
+
+  const program = anchor.workspace.Pluto as Program<Pluto>;
+
+  it("Is initialized!", async () => {
+    // Applies the change queued by earn-config-queue-set-usdc once the timelock elapsed
+    const tx = await program.methods.earnConfigExecuteSet().accounts({
+      protocol: new PublicKey(accounts.protocol),
+      config: accounts.earnConfig,
+    }).rpc({
+        skipPreflight:false
+    }).catch(e => console.error(e));
+
+    console.log("Your transaction signature", tx);
+  });
+});
\ No newline at end of file
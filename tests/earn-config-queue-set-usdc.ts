@@ -0,0 +1,43 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program, web3} from "@coral-xyz/anchor";
+import { Pluto } from "../target/types/pluto";
+import {PublicKey} from "@solana/web3.js";
+// @ts-ignore
+import accs from "./accounts.json";
+
+describe("earn-config-queue-set-usdc", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(anchor.AnchorProvider.env());
+  let provider = anchor.getProvider();
+  const accounts = accs.production.usdc;
+
+  const program = anchor.workspace.Pluto as Program<Pluto>;
+
+  it("Is initialized!", async () => {
+    // Add your test here.
+    // const tx = await program.methods.createVault().rpc();
+    const tx = await program.methods.earnConfigQueueSet({
+        earnFeeVault: null,
+        pauseFlags: null,
+        protocolFee: 3000, // Protocol Fee (3%)
+        ltv: null,
+        depositFee: null,
+        minDepositLimit: null,
+        maxDepositLimit: null,
+        withdrawFee: null,
+        minWithdrawLimit: null,
+        maxWithdrawLimit: null,
+        borrowFee: null,
+        minBorrowLimit: null,
+        maxBorrowLimit: null,
+        floorCapRate: null,
+    }).accounts({
+      protocol: new PublicKey(accounts.protocol),
+      config: accounts.earnConfig,
+    }).rpc({
+        skipPreflight:false
+    }).catch(e => console.error(e));
+
+    console.log("Your transaction signature", tx);
+  });
+});
\ No newline at end of file
@@ -0,0 +1,30 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program, web3} from "@coral-xyz/anchor";
+import { Pluto } from "../target/types/pluto";
+import {PublicKey} from "@solana/web3.js";
+// @ts-ignore
+import accs from "./accounts.json";
+
+describe("earn-config-set-caps-usdc", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(anchor.AnchorProvider.env());
+  let provider = anchor.getProvider();
+  const accounts = accs.dev.usdc;
+
+  const program = anchor.workspace.Pluto as Program<Pluto>;
+
+  it("Is initialized!", async () => {
+    const tx = await program.methods.earnConfigSetCaps(
+        new anchor.BN(1_000_000 * 1e6), // supply cap 1,000,000 USDC
+        new anchor.BN(500_000 * 1e6), // borrow cap 500,000 USDC
+        90 * 1e3, // max utilization 90%
+    ).accounts({
+      protocol: new PublicKey(accounts.protocol),
+      config: new PublicKey(accounts.earnConfig),
+    }).rpc({
+        skipPreflight:false
+    }).catch(e => console.error(e));
+
+    console.log("Your transaction signature", tx);
+  });
+});
\ No newline at end of file
@@ -16,27 +16,27 @@ describe("earn-config-set-pyusd", () => {
   it("Is initialized!", async () => {
     // Add your test here.
     // const tx = await program.methods.createVault().rpc();
-    const tx = await program.methods.earnConfigSet(
-        false, // Frozen
-        2000, // Protocol Fee (2%)
-        90000, // LTV (90%)
-        0, // Deposit Fee (0%)
-        new anchor.BN(10).mul(new anchor.BN(10).pow(new anchor.BN(accounts.tokenDecimalA))), // Min Deposit Limit (0)
-        new anchor.BN(100000).mul(new anchor.BN(10).pow(new anchor.BN(accounts.tokenDecimalA))), // Max Deposit Limit (1,000,000)
-        0, // Withdraw Fee (0%)
-        new anchor.BN(1), // Min Withdraw Limit (0)
-        new anchor.BN(100000).mul(new anchor.BN(10).pow(new anchor.BN(accounts.tokenDecimalA))), // Max Withdraw Limit (1,000,000)
-        0, // Borrow Fee (0%)
-        new anchor.BN(1), // Min Borrow Limit (0)
-        new anchor.BN(10000).mul(new anchor.BN(10).pow(new anchor.BN(accounts.tokenDecimalA))), // Max Borrow Limit (1,000,000)
-        64000 // Floor Cap (64%)
-    ).accounts({
+    const tx = await program.methods.earnConfigSet({
+        earnFeeVault: new PublicKey(accounts.feeVault),
+        pauseFlags: 0, // Pause flags (none)
+        protocolFee: 2000, // Protocol Fee (2%)
+        ltv: 90000, // LTV (90%)
+        depositFee: 0, // Deposit Fee (0%)
+        minDepositLimit: new anchor.BN(10).mul(new anchor.BN(10).pow(new anchor.BN(accounts.tokenDecimalA))), // Min Deposit Limit (0)
+        maxDepositLimit: new anchor.BN(100000).mul(new anchor.BN(10).pow(new anchor.BN(accounts.tokenDecimalA))), // Max Deposit Limit (1,000,000)
+        withdrawFee: 0, // Withdraw Fee (0%)
+        minWithdrawLimit: new anchor.BN(1), // Min Withdraw Limit (0)
+        maxWithdrawLimit: new anchor.BN(100000).mul(new anchor.BN(10).pow(new anchor.BN(accounts.tokenDecimalA))), // Max Withdraw Limit (1,000,000)
+        borrowFee: 0, // Borrow Fee (0%)
+        minBorrowLimit: new anchor.BN(1), // Min Borrow Limit (0)
+        maxBorrowLimit: new anchor.BN(10000).mul(new anchor.BN(10).pow(new anchor.BN(accounts.tokenDecimalA))), // Max Borrow Limit (1,000,000)
+        floorCapRate: 64000, // Floor Cap (64%)
+    }).accounts({
       config: accounts.earnConfig,
-      feeVault: accounts.feeVault,
     }).rpc({
         skipPreflight:false
     }).catch(e => console.error(e));
 
     console.log("Your transaction signature", tx);
   });
-});
+});
\ No newline at end of file
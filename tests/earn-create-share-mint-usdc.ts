@@ -0,0 +1,38 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Pluto } from "../target/types/pluto";
+import { PublicKey } from "@solana/web3.js";
+// @ts-ignore
+import accs from "./accounts.json";
+
+describe("earn-create-share-mint-usdc", () => {
+  const provider = anchor.AnchorProvider.env();
+  // Configure the client to use the local cluster.
+  anchor.setProvider(provider);
+  const accounts = accs.dev.usdc;
+  const program = anchor.workspace.Pluto as Program<Pluto>;
+
+  it("Is initialized!", async () => {
+    // Add your test here.
+    const vaultAccount = new PublicKey(accounts.earnVault);
+
+    const create_share_mint_ix = await program.methods.earnVaultCreateShareMint().accounts({
+      vault: vaultAccount,
+    }).instruction();
+
+    let trx = new anchor.web3.Transaction();
+    trx.add(
+        anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({
+          units: 500000
+        }),
+        anchor.web3.ComputeBudgetProgram.setComputeUnitPrice({
+          microLamports: 0,
+        }),
+        create_share_mint_ix,
+    );
+
+    let tx = await provider.sendAndConfirm(trx);
+
+    console.log(`TX: ${tx}`);
+  });
+});
\ No newline at end of file
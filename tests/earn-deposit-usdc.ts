@@ -21,6 +21,11 @@ describe("deposit-usdc", () => {
       vault: vaultAccount,
     }).instruction();
 
+    // Mint the deposited units as share tokens
+    const tokenize_ix = await program.methods.earnVaultTokenizeLender().accounts({
+      vault: vaultAccount,
+    }).instruction();
+
     let trx = new anchor.web3.Transaction();
     trx.add(
         anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({
@@ -30,6 +35,7 @@ describe("deposit-usdc", () => {
           microLamports: 0,
         }),
         deposit_ix,
+        tokenize_ix,
     );
 
     let tx = await provider.sendAndConfirm(trx);
@@ -0,0 +1,37 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Pluto } from "../target/types/pluto";
+import { PublicKey } from "@solana/web3.js";
+// @ts-ignore
+import accs from "./accounts.json";
+
+describe("earn-fund-insurance-usdc", () => {
+  const provider = anchor.AnchorProvider.env();
+  // Configure the client to use the local cluster.
+  anchor.setProvider(provider);
+  const accounts = accs.dev.usdc;
+  const program = anchor.workspace.Pluto as Program<Pluto>;
+
+  it("Is initialized!", async () => {
+    const vaultAccount = new PublicKey(accounts.earnVault);
+
+    const fund_insurance_ix = await program.methods.earnVaultFundInsurance(new anchor.BN(1000*1e6)).accounts({
+      vault: vaultAccount,
+    }).instruction();
+
+    let trx = new anchor.web3.Transaction();
+    trx.add(
+        anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({
+          units: 500000
+        }),
+        anchor.web3.ComputeBudgetProgram.setComputeUnitPrice({
+          microLamports: 0,
+        }),
+        fund_insurance_ix,
+    );
+
+    let tx = await provider.sendAndConfirm(trx);
+
+    console.log(`TX: ${tx}`);
+  });
+});
\ No newline at end of file
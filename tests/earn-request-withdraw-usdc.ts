@@ -0,0 +1,45 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Pluto } from "../target/types/pluto";
+import { PublicKey } from "@solana/web3.js";
+// @ts-ignore
+import accs from "./accounts.json";
+
+describe("earn-request-withdraw-usdc", () => {
+  const provider = anchor.AnchorProvider.env();
+  // Configure the client to use the local cluster.
+  anchor.setProvider(provider);
+  const accounts = accs.dev.usdc;
+  const program = anchor.workspace.Pluto as Program<Pluto>;
+
+  it("Is initialized!", async () => {
+    const vaultAccount = new PublicKey(accounts.earnVault);
+    const vault = await program.account.vaultEarn.fetch(vaultAccount);
+
+    const [withdrawRequest] = PublicKey.findProgramAddressSync(
+        [Buffer.from("withdraw_request_v01"), vaultAccount.toBuffer(), vault.withdrawQueueTail.toArrayLike(Buffer, "le", 8)],
+        program.programId,
+    );
+    console.log(`Withdraw request: ${withdrawRequest.toBase58()}`);
+
+    const request_withdraw_ix = await program.methods.earnVaultRequestWithdraw(new anchor.BN(10*1e8)).accounts({
+      vault: vaultAccount,
+      withdrawRequest: withdrawRequest,
+    }).instruction();
+
+    let trx = new anchor.web3.Transaction();
+    trx.add(
+        anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({
+          units: 500000
+        }),
+        anchor.web3.ComputeBudgetProgram.setComputeUnitPrice({
+          microLamports: 0,
+        }),
+        request_withdraw_ix,
+    );
+
+    let tx = await provider.sendAndConfirm(trx);
+
+    console.log(`TX: ${tx}`);
+  });
+});
\ No newline at end of file
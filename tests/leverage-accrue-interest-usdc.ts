@@ -0,0 +1,29 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program, web3} from "@coral-xyz/anchor";
+import { Pluto } from "../target/types/pluto";
+import {PublicKey} from "@solana/web3.js";
+// @ts-ignore
+import accs from "./accounts.json";
+
+describe("leverage-accrue-interest-usdc", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(anchor.AnchorProvider.env());
+  let provider = anchor.getProvider();
+  const accounts = accs.dev.usdc;
+
+  const program = anchor.workspace.Pluto as Program<Pluto>;
+
+  it("Is initialized!", async () => {
+    const tx = await program.methods.leverageVaultAccrueInterest().accounts({
+      protocol: new PublicKey(accounts.protocol),
+      leverageConfig: new PublicKey(accounts.leverageConfig),
+      earnConfig: new PublicKey(accounts.earnConfig),
+      borrowVault: new PublicKey(accounts.earnVault),
+      vault: new PublicKey(accounts.leverageVault),
+    }).rpc({
+        skipPreflight:false
+    }).catch(e => console.error(e));
+
+    console.log("Your transaction signature", tx);
+  });
+});
@@ -0,0 +1,47 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Pluto } from "../target/types/pluto";
+// @ts-ignore
+import accs from "./accounts.json";
+
+describe("pluto", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider();
+
+  const program = anchor.workspace.Pluto as Program<Pluto>;
+
+  let accounts = accs.dev;
+
+  it("Is initialized!", async () => {
+    // Add your test here.
+    // The router list starts with jupiter, then allow the mercury program for the swap leg
+    const mercuryProgram = new anchor.web3.PublicKey("E4HWdh2qyNYdjuQqvkXUyj73Z2FAnyq5w5PRDfDxzn59");
+    const createIx = await program.methods.leverageVaultCreateSwapRouters().accounts({
+      vault: new anchor.web3.PublicKey(accounts.leverageVault),
+    }).instruction();
+    const ix = await program.methods.leverageVaultAddSwapRouter(mercuryProgram).accounts({
+      vault: new anchor.web3.PublicKey(accounts.leverageVault),
+    }).instruction();
+
+    let transaction = new anchor.web3.Transaction();
+
+    transaction.add(
+        anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({
+          units: 200000
+        }),
+        anchor.web3.ComputeBudgetProgram.setComputeUnitPrice({
+          microLamports: 1000
+        })
+    );
+
+    transaction.add(createIx, ix);
+
+    let tx = await provider.sendAndConfirm(transaction, [],{
+        skipPreflight: true,
+    });
+
+    console.log("Your transaction signature", tx);
+    console.log("Your transaction", transaction);
+  });
+});
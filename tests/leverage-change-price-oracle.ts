@@ -17,8 +17,10 @@ describe("pluto", () => {
     let usdcFeed = Array.from(Uint8Array.from(Buffer.from(accounts.tokenCollateralPriceFeed, "hex")));
     let jlpFeed = Array.from(Uint8Array.from(Buffer.from(accounts.nativeCollateralPriceFeed, "hex")));
     // Add your test here.
+    // Sources are { pyth: {} }, { switchboardOnDemand: {} } or { fixed: {} } with the price in 10^8
     const ix = await program.methods.leverageVaultChangePriceOracle(
-        usdcFeed, jlpFeed,
+        { pyth: {} }, usdcFeed, new anchor.BN(0),
+        { pyth: {} }, jlpFeed, new anchor.BN(0),
     ).accounts({
       vault: new anchor.web3.PublicKey(accounts.leverageVault),
       tokenCollateralPriceOracle: new anchor.web3.PublicKey(accounts.tokenCollateralPriceOracle),
@@ -64,10 +64,12 @@ describe("leverage-close-sol", () => {
 
       const release_ix = await program.methods.leverageVaultRelease(position).accounts({
         vault: vaultAccount,
+        owner: provider.wallet.publicKey,
       }).instruction();
 
       const repay_borrow_ix = await program.methods.leverageVaultRepayBorrow(position).accounts({
         vault: vaultAccount,
+        owner: provider.wallet.publicKey,
       }).instruction();
 
       const closing_ix = await program.methods.leverageVaultClosing(position).accounts({
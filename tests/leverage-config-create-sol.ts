@@ -16,7 +16,7 @@ describe("leverage-config-create-sol", () => {
     // Add your test here.
     // const tx = await program.methods.createVault().rpc();
     const tx = await program.methods.leverageConfigCreate(
-        false, // Frozen
+        0, // Pause flags (none)
         2000, // Protocol Fee (2%)
         1100, // Min Leverage (1.1x)
         7000, // Max Leverage (7x)
@@ -49,5 +49,4 @@ describe("leverage-config-create-sol", () => {
 
     console.log("Your transaction signature", tx);
   });
-});
-
+});
\ No newline at end of file
@@ -0,0 +1,28 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program, web3} from "@coral-xyz/anchor";
+import { Pluto } from "../target/types/pluto";
+import {PublicKey} from "@solana/web3.js";
+// @ts-ignore
+import accs from "./accounts.json";
+
+describe("leverage-config-set-insurance-ratio-usdc", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(anchor.AnchorProvider.env());
+  let provider = anchor.getProvider();
+  const accounts = accs.dev.usdc;
+
+  const program = anchor.workspace.Pluto as Program<Pluto>;
+
+  it("Is initialized!", async () => {
+    const tx = await program.methods.leverageConfigSetInsuranceRatio(
+        20000, // 20% of protocol and liquidation fees to the insurance fund
+    ).accounts({
+      protocol: new PublicKey(accounts.protocol),
+      config: new PublicKey(accounts.leverageConfig),
+    }).rpc({
+        skipPreflight:false
+    }).catch(e => console.error(e));
+
+    console.log("Your transaction signature", tx);
+  });
+});
\ No newline at end of file
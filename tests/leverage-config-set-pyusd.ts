@@ -15,34 +15,34 @@ describe("leverage-config-set-pyusd", () => {
   it("Is initialized!", async () => {
     // Add your test here.
     // const tx = await program.methods.createVault().rpc();
-    const tx = await program.methods.leverageConfigSet(
-        false, // Frozen
-        2000, // Protocol Fee (2%)
-        1100, // Min Leverage (1.1x)
-        9500, // Max Leverage (9.5x)
-        100, // Leverage Step (0.1x)
-        0, // Leverage Fee (0%)
-        new anchor.BN(1).mul(new anchor.BN(10).pow(new anchor.BN(accounts.tokenDecimalA))), // Min Leverage Limit (1)
-        new anchor.BN(100000).mul(new anchor.BN(10).pow(new anchor.BN(accounts.tokenDecimalA))), // Max Leverage Limit (1,000,000)
-        0, // Deleverage Fee (0%)
-        new anchor.BN(1), // Min Deleverage Limit (1)
-        new anchor.BN(100000).mul(new anchor.BN(10).pow(new anchor.BN(accounts.tokenDecimalB))), // Max Deleverage Limit (1,000,000)
-        0, // Closing Fee (0%)
-        2500, // Spread Rate (2.5%)
-        2500, // Liquidation Fee (2.5%)
-        95000, // Liquidation Threshold (95%)
-        0, // Liquidation Protocol Ratio (0%)
-        500, // Slippage Rate (0.3%)
-        new anchor.BN(2 * 86400), // Emergency Eject Duration (2 days)
-        1050, // Saver threshold 1.05 health factor
-        500, // Saver Target 0.5x leverage
-    ).accounts({
+    const tx = await program.methods.leverageConfigSet({
+        leverageFeeVault: new PublicKey(accounts.feeVault),
+        pauseFlags: 0, // Pause flags (none)
+        protocolFee: 2000, // Protocol Fee (2%)
+        minLeverage: 1100, // Min Leverage (1.1x)
+        maxLeverage: 9500, // Max Leverage (9.5x)
+        leverageStep: 100, // Leverage Step (0.1x)
+        leverageFee: 0, // Leverage Fee (0%)
+        minLeverageLimit: new anchor.BN(1).mul(new anchor.BN(10).pow(new anchor.BN(accounts.tokenDecimalA))), // Min Leverage Limit (1)
+        maxLeverageLimit: new anchor.BN(100000).mul(new anchor.BN(10).pow(new anchor.BN(accounts.tokenDecimalA))), // Max Leverage Limit (1,000,000)
+        deleverageFee: 0, // Deleverage Fee (0%)
+        minDeleverageLimit: new anchor.BN(1), // Min Deleverage Limit (1)
+        maxDeleverageLimit: new anchor.BN(100000).mul(new anchor.BN(10).pow(new anchor.BN(accounts.tokenDecimalB))), // Max Deleverage Limit (1,000,000)
+        closingFee: 0, // Closing Fee (0%)
+        spreadRate: 2500, // Spread Rate (2.5%)
+        liquidationFee: 2500, // Liquidation Fee (2.5%)
+        liquidationThreshold: 95000, // Liquidation Threshold (95%)
+        liquidationProtocolRatio: 0, // Liquidation Protocol Ratio (0%)
+        slippageRate: 500, // Slippage Rate (0.3%)
+        emergencyEjectPeriod: new anchor.BN(2 * 86400), // Emergency Eject Duration (2 days)
+        saverThreshold: 1050, // Saver threshold 1.05 health factor
+        saverTargetReduction: 500, // Saver Target 0.5x leverage
+    }).accounts({
       config: accounts.leverageConfig,
-      feeVault: accounts.feeVault,
     }).rpc({
         skipPreflight:false
     }).catch(e => console.error(e));
 
     console.log("Your transaction signature", tx);
   });
-});
+});
\ No newline at end of file
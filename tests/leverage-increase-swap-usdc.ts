@@ -0,0 +1,124 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Pluto } from "../target/types/pluto";
+import { PublicKey } from "@solana/web3.js";
+import {createJupiterApiClient} from "@jup-ag/api";
+// @ts-ignore
+import accs from './accounts.json';
+
+const provider = anchor.AnchorProvider.env();
+anchor.setProvider(provider);
+
+describe("leverage-increase-swap-usdc", () => {
+  // Configure the client to use the local cluster.
+
+  const program = anchor.workspace.Pluto as Program<Pluto>;
+  const accounts = accs.dev.usdc;
+  const tokenMintA = new PublicKey(accounts.tokenMintA);
+  const tokenMintB = new PublicKey(accounts.tokenMintB);
+
+  it("Is initialized!", async () => {
+    try {
+      const jupiterQuoteApi = createJupiterApiClient()
+
+      const position = 0;
+      const leverage_fee = (100 - 0.0) / 100; // 0.1% fee
+      const borrow_fee = (100 - 0.0) / 100; // 0.1% fee
+      const amountToSwap = ((10 * leverage_fee) + (1 * borrow_fee)) * 1e6 // 1 + 2 USDC (6 decimals)
+      // Compute routes
+      const quote = await jupiterQuoteApi.quoteGet({
+        inputMint: tokenMintA.toString(),
+        outputMint: tokenMintB.toString(),
+        amount: Math.floor(amountToSwap),
+        maxAccounts: 24,
+      })
+
+      // The swap runs by cpi inside the increase, only the route is taken from jupiter
+      const {
+        swapInstruction,
+        addressLookupTableAddresses,
+      } = await jupiterQuoteApi.swapInstructionsPost({
+        swapRequest: {
+          quoteResponse: quote,
+          userPublicKey: provider.wallet.publicKey.toBase58(),
+          wrapAndUnwrapSol: false,
+          dynamicSlippage: {
+            maxBps: 30,
+          },
+        },
+      })
+
+      const vaultAccount = new PublicKey(accounts.leverageVault);
+      const owner = new PublicKey(process.env.POSITION_OWNER ?? provider.wallet.publicKey.toBase58());
+
+      // Route accounts go last, after any fallback price oracles of the vault
+      const routeAccounts = swapInstruction.accounts.map((key) => ({
+        pubkey: new PublicKey(key.pubkey),
+        isSigner: key.isSigner,
+        isWritable: key.isWritable,
+      }));
+
+      const increase_swap_ix = await program.methods.leverageVaultIncreaseSwap(
+          position,
+          new anchor.BN(10 * 1e6),
+          1100,
+          routeAccounts.length,
+          Buffer.from(swapInstruction.data, "base64"),
+      ).accounts({
+        vault: vaultAccount,
+        owner,
+        swapProgram: new PublicKey(swapInstruction.programId),
+      }).remainingAccounts(routeAccounts).instruction();
+
+      let instructions = [
+        anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({
+          units: 1400000
+        }),
+        anchor.web3.ComputeBudgetProgram.setComputeUnitPrice({
+          microLamports: 0,
+        }),
+        increase_swap_ix,
+      ];
+
+      const blockhash = (await provider.connection.getLatestBlockhash()).blockhash;
+
+      const addressLookupTableAccounts = await getAdressLookupTableAccounts([
+          accounts.lookupTable,
+          ...addressLookupTableAddresses
+      ]);
+      const messageV0 = new anchor.web3.TransactionMessage({
+        payerKey: provider.wallet.publicKey,
+        recentBlockhash: blockhash,
+        instructions,
+      }).compileToV0Message(addressLookupTableAccounts);
+      const trx = new anchor.web3.VersionedTransaction(messageV0);
+
+      let tx = await provider.sendAndConfirm(trx,[], {skipPreflight: true});
+      console.log(`TX: ${tx}`);
+    } catch (err) {
+      console.error(err)
+    }
+  });
+});
+
+export const getAdressLookupTableAccounts = async (
+    keys: string[]
+): Promise<anchor.web3.AddressLookupTableAccount[]> => {
+  const addressLookupTableAccountInfos =
+      await provider.connection.getMultipleAccountsInfo(
+          keys.map((key) => new PublicKey(key))
+      );
+
+  return addressLookupTableAccountInfos.reduce((acc, accountInfo, index) => {
+    const addressLookupTableAddress = keys[index];
+    if (accountInfo) {
+      const addressLookupTableAccount = new anchor.web3.AddressLookupTableAccount({
+        key: new PublicKey(addressLookupTableAddress),
+        state: anchor.web3.AddressLookupTableAccount.deserialize(accountInfo.data),
+      });
+      acc.push(addressLookupTableAccount);
+    }
+
+    return acc;
+  }, new Array<anchor.web3.AddressLookupTableAccount>());
+};
\ No newline at end of file
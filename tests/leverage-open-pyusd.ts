@@ -73,6 +73,7 @@ describe("leverage-open-pyusd", () => {
 
       const confiscate_ix = await program.methods.leverageVaultConfiscate().accounts({
         vault: vaultAccount,
+        owner: provider.wallet.publicKey,
       }).instruction();
 
       let instructions = [
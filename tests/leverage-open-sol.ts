@@ -75,6 +75,7 @@ describe("leverage-open-sol", () => {
 
       const confiscate_ix = await program.methods.leverageVaultConfiscate().accounts({
         vault: vaultAccount,
+        owner: provider.wallet.publicKey,
       }).instruction();
 
       let instructions = [
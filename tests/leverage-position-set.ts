@@ -23,17 +23,21 @@ describe("leverage-position-set", () => {
   it("Is initialized!", async () => {
     try {
       const vaultAccount = new PublicKey(accounts.leverageVault);
+      const owner = new PublicKey(process.env.POSITION_OWNER ?? provider.wallet.publicKey.toBase58());
 
       const set_safety_ix = await program.methods.leverageVaultSetSafetyMode(0, true).accounts({
         vault: vaultAccount,
+        owner,
       }).instruction();
 
       const set_emergency_eject_ix = await program.methods.leverageVaultSetEmergencyEject(0, true).accounts({
         vault: vaultAccount,
+        owner,
       }).instruction();
 
       const set_profit_taker_ix = await program.methods.leverageVaultSetProfitTaker(0, true, 10000, 10000).accounts({
         vault: vaultAccount,
+        owner,
       }).instruction();
 
       let instructions = [
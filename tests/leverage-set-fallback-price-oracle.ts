@@ -0,0 +1,51 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Pluto } from "../target/types/pluto";
+// @ts-ignore
+import accs from "./accounts.json";
+
+describe("pluto", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider();
+
+  const program = anchor.workspace.Pluto as Program<Pluto>;
+
+  let accounts = accs.dev;
+
+  it("Is initialized!", async () => {
+    // Add your test here.
+    // Fallback slot 0 of both sides, leverage handlers then take these oracles as remaining accounts
+    let usdcFeed = Array.from(Uint8Array.from(Buffer.from(process.env.FALLBACK_TOKEN_COLLATERAL_PRICE_FEED, "hex")));
+    let jlpFeed = Array.from(Uint8Array.from(Buffer.from(process.env.FALLBACK_NATIVE_COLLATERAL_PRICE_FEED, "hex")));
+    const ix = await program.methods.leverageVaultSetFallbackPriceOracle(
+        0,
+        { pyth: {} }, usdcFeed,
+        { pyth: {} }, jlpFeed,
+    ).accounts({
+      vault: new anchor.web3.PublicKey(accounts.leverageVault),
+      tokenCollateralPriceOracle: new anchor.web3.PublicKey(process.env.FALLBACK_TOKEN_COLLATERAL_PRICE_ORACLE),
+      nativeCollateralPriceOracle: new anchor.web3.PublicKey(process.env.FALLBACK_NATIVE_COLLATERAL_PRICE_ORACLE),
+    }).instruction();
+
+    let transaction = new anchor.web3.Transaction();
+
+    transaction.add(
+        anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({
+          units: 200000
+        }),
+        anchor.web3.ComputeBudgetProgram.setComputeUnitPrice({
+          microLamports: 1000
+        })
+    );
+
+    transaction.add(ix);
+
+    let tx = await provider.sendAndConfirm(transaction, [],{
+        skipPreflight: true,
+    });
+
+    console.log("Your transaction signature", tx);
+    console.log("Your transaction", transaction);
+  });
+});
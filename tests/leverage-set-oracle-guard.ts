@@ -0,0 +1,45 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Pluto } from "../target/types/pluto";
+// @ts-ignore
+import accs from "./accounts.json";
+
+describe("pluto", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.getProvider();
+
+  const program = anchor.workspace.Pluto as Program<Pluto>;
+
+  let accounts = accs.dev;
+
+  it("Is initialized!", async () => {
+    // Add your test here.
+    // 60 seconds max age, 1% confidence / price, 5% deviation from the ema price, 0 keeps the default
+    const ix = await program.methods.leverageVaultSetOracleGuard(
+        new anchor.BN(60), 1000, 5000,
+    ).accounts({
+      vault: new anchor.web3.PublicKey(accounts.leverageVault),
+    }).instruction();
+
+    let transaction = new anchor.web3.Transaction();
+
+    transaction.add(
+        anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({
+          units: 200000
+        }),
+        anchor.web3.ComputeBudgetProgram.setComputeUnitPrice({
+          microLamports: 1000
+        })
+    );
+
+    transaction.add(ix);
+
+    let tx = await provider.sendAndConfirm(transaction, [],{
+        skipPreflight: true,
+    });
+
+    console.log("Your transaction signature", tx);
+    console.log("Your transaction", transaction);
+  });
+});
@@ -12,11 +12,11 @@ describe("protocol-create", () => {
     // Add your test here.
     // const tx = await program.methods.createVault().rpc();
     const tx = await program.methods.protocolCreate(
-        false, false, true, false
+        0 // Pause flags (none)
     ).rpc({
         skipPreflight:false
     }).catch(e => console.error(e));
 
     console.log("Your transaction signature", tx);
   });
-});
+});
\ No newline at end of file
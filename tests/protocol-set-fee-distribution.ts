@@ -0,0 +1,29 @@
+import * as anchor from "@coral-xyz/anchor";
+import {Program} from "@coral-xyz/anchor";
+import { Pluto } from "../target/types/pluto";
+import { PublicKey } from "@solana/web3.js";
+// @ts-ignore
+import accs from "./accounts.json";
+
+describe("protocol-set-fee-distribution", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const accounts = accs.dev.usdc;
+
+  const program = anchor.workspace.Pluto as Program<Pluto>;
+
+  it("Is initialized!", async () => {
+    const owner = anchor.AnchorProvider.env().wallet.publicKey;
+
+    // 70% treasury, 20% insurance fund, 10% buyback
+    const tx = await program.methods.protocolSetFeeDistribution(
+        owner, owner, owner, 70000, 20000, 10000
+    ).accounts({
+      protocol: new PublicKey(accounts.protocol),
+    }).rpc({
+        skipPreflight:false
+    }).catch(e => console.error(e));
+
+    console.log("Your transaction signature", tx);
+  });
+});
\ No newline at end of file
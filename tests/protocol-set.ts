@@ -1,22 +1,29 @@
 import * as anchor from "@coral-xyz/anchor";
 import {Program} from "@coral-xyz/anchor";
 import { Pluto } from "../target/types/pluto";
+import { PublicKey } from "@solana/web3.js";
+// @ts-ignore
+import accs from "./accounts.json";
 
 describe("protocol-set", () => {
   // Configure the client to use the local cluster.
   anchor.setProvider(anchor.AnchorProvider.env());
+  const accounts = accs.dev.usdc;
 
   const program = anchor.workspace.Pluto as Program<Pluto>;
 
   it("Is initialized!", async () => {
     // Add your test here.
     // const tx = await program.methods.createVault().rpc();
+    // pause opening positions (bit 2), closes and liquidations keep running
     const tx = await program.methods.protocolSet(
-        false, false, true, false
-    ).rpc({
+        1 << 2
+    ).accounts({
+      protocol: new PublicKey(accounts.protocol),
+    }).rpc({
         skipPreflight:false
     }).catch(e => console.error(e));
 
     console.log("Your transaction signature", tx);
   });
-});
+});
\ No newline at end of file